@@ -1,8 +1,8 @@
 use anyhow::Result;
 use std::path::PathBuf;
-use mcp_core::database::migration::{export_servers_to_file, import_claude_desktop_config, ExportData};
+use mcp_core::database::migration::{export_servers_to_file, import_claude_desktop_config, ExportData, SecretExport};
 
-pub fn export_servers(db_path: Option<PathBuf>, output_path: PathBuf) -> Result<()> {
+pub fn export_servers(db_path: Option<PathBuf>, output_path: PathBuf, secrets_key_file: Option<PathBuf>) -> Result<()> {
     // Use default database path if not provided
     let db_path = db_path.unwrap_or_else(|| {
         let mut path = dirs::data_dir()
@@ -15,7 +15,18 @@ pub fn export_servers(db_path: Option<PathBuf>, output_path: PathBuf) -> Result<
     println!("Exporting servers from: {}", db_path.display());
     println!("Output file: {}", output_path.display());
     
-    export_servers_to_file(&db_path, &output_path)?;
+    let secrets = match secrets_key_file {
+        Some(key_file) => {
+            println!("Secrets re-encrypted with: {}", key_file.display());
+            SecretExport::ReEncrypt(key_file)
+        }
+        None => {
+            println!("Secrets are excluded");
+            SecretExport::Exclude
+        }
+    };
+
+    export_servers_to_file(&db_path, &output_path, &secrets)?;
     
     println!("Export completed successfully!");
     Ok(())
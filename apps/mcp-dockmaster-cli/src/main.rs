@@ -1,13 +1,19 @@
+use std::collections::HashMap;
 use std::path;
 
 use clap::{Parser, Subcommand};
 use log::{error, info};
 use mcp_core::{
     core::{
-        mcp_core::MCPCore, mcp_core_database_ext::McpCoreDatabaseExt,
-        mcp_core_proxy_ext::McpCoreProxyExt,
+        mcp_core::MCPCore, mcp_core_composite_ext::McpCoreCompositeExt,
+        mcp_core_database_ext::McpCoreDatabaseExt, mcp_core_profiles_ext::McpCoreProfilesExt, mcp_core_proxy_ext::McpCoreProxyExt,
+        mcp_core_secrets_ext::McpCoreSecretsExt, mcp_core_sessions_ext::McpCoreSessionsExt,
     },
     init_logging,
+    models::types::{
+        ArgumentPreset, ClientPolicy, CompositeTool, Profile, ProfileTool, SchemaStrictness,
+        ToolOverrides,
+    },
     utils::default_storage_path,
 };
 
@@ -66,6 +72,199 @@ enum Commands {
         enabled: bool,
     },
 
+    /// Enable or disable a single tool of a server
+    ToolActive {
+        /// Server ID
+        #[arg(short, long)]
+        server_id: String,
+
+        /// Tool ID or name
+        #[arg(short, long)]
+        tool_id: String,
+
+        /// Whether the tool is exposed to clients (true or false)
+        #[arg(short, long, action = clap::ArgAction::Set)]
+        active: bool,
+    },
+
+    /// Expose a tool of a server under another name or descriptions
+    ToolOverride {
+        /// Server ID
+        #[arg(short, long)]
+        server_id: String,
+
+        /// Tool ID or name
+        #[arg(short, long)]
+        tool_id: String,
+
+        /// Name the tool is exposed as
+        #[arg(short, long)]
+        name: Option<String>,
+
+        /// Description the tool is exposed with
+        #[arg(short, long)]
+        description: Option<String>,
+
+        /// Argument description as ARGUMENT=DESCRIPTION, can be repeated
+        #[arg(short, long = "arg")]
+        args: Vec<String>,
+
+        /// Remove the overrides and expose the tool as its server reports it
+        #[arg(long)]
+        reset: bool,
+    },
+
+    /// Preset arguments of a tool, without --pin or --default the presets are removed
+    ToolPresets {
+        /// Server ID
+        #[arg(short, long)]
+        server_id: String,
+
+        /// Tool ID or name
+        #[arg(short, long)]
+        tool_id: String,
+
+        /// Argument hidden from the model and always sent, as ARGUMENT=VALUE, can be repeated
+        #[arg(short, long)]
+        pin: Vec<String>,
+
+        /// Argument the model may override, as ARGUMENT=VALUE, can be repeated
+        #[arg(short, long)]
+        default: Vec<String>,
+    },
+
+    /// Cache the results of a tool, without --ttl caching is disabled
+    ToolCache {
+        /// Server ID
+        #[arg(short, long)]
+        server_id: String,
+
+        /// Tool ID or name
+        #[arg(short, long)]
+        tool_id: String,
+
+        /// Seconds a result stays cached
+        #[arg(long)]
+        ttl: Option<u64>,
+    },
+
+    /// Show the hit and miss counters of the result cache
+    CacheStats,
+
+    /// List the profiles
+    Profiles,
+
+    /// Create or replace a profile, served at /sse/{name}
+    ProfileSave {
+        /// Profile name
+        #[arg(short, long)]
+        name: String,
+
+        /// Profile description
+        #[arg(short, long, default_value = "")]
+        description: String,
+
+        /// Server whose tools are all included, can be repeated
+        #[arg(short, long = "server")]
+        servers: Vec<String>,
+
+        /// Single tool included, as SERVER_ID/TOOL_ID, can be repeated
+        #[arg(short, long = "tool")]
+        tools: Vec<String>,
+
+        /// Estimated tokens the tool definitions may take, lowest-priority tools are minified
+        /// then dropped to fit
+        #[arg(long)]
+        token_budget: Option<u32>,
+
+        /// Normalization of the listed input schemas: off, standard or strict
+        #[arg(long)]
+        schema_strictness: Option<SchemaStrictness>,
+    },
+
+    /// Delete a profile
+    ProfileDelete {
+        /// Profile name
+        #[arg(short, long)]
+        name: String,
+    },
+
+    /// List the client policies
+    Policies,
+
+    /// Create or replace the policy applied to the clients connected through a profile
+    PolicySave {
+        /// Profile of the connections, `*` for connections without a policy of their own
+        #[arg(short, long)]
+        profile: String,
+
+        /// Server the clients may use, can be repeated; without it every server is allowed
+        #[arg(short, long = "server")]
+        servers: Vec<String>,
+
+        /// Maximum tool calls per minute
+        #[arg(short, long)]
+        max_calls_per_minute: Option<u32>,
+
+        /// Tool calls wait until approved in the MCP Dockmaster UI
+        #[arg(short, long)]
+        require_approval: bool,
+
+        /// Normalization of the listed input schemas: off, standard or strict, overrides the
+        /// profile's
+        #[arg(long)]
+        schema_strictness: Option<SchemaStrictness>,
+    },
+
+    /// Delete the client policy of a profile
+    PolicyDelete {
+        /// Profile name
+        #[arg(short, long)]
+        profile: String,
+    },
+
+    /// List the composite tools
+    CompositeTools,
+
+    /// Create or replace a composite tool from its JSON definition
+    CompositeSave {
+        /// Path of a JSON file with the name, description, input_schema, steps and output
+        #[arg(short, long)]
+        file: path::PathBuf,
+    },
+
+    /// Delete a composite tool
+    CompositeDelete {
+        /// Composite tool name
+        #[arg(short, long)]
+        name: String,
+    },
+
+    /// List the vault secrets and the servers using them
+    Secrets,
+
+    /// Create or rotate a vault secret, referenced from server env values as ${secret:NAME}
+    SecretSave {
+        /// Secret name
+        #[arg(short, long)]
+        name: String,
+
+        /// Secret value, read from standard input when omitted
+        #[arg(short, long)]
+        value: Option<String>,
+
+        /// What the secret is for
+        #[arg(short, long, default_value = "")]
+        description: String,
+    },
+
+    /// Delete a vault secret no server uses
+    SecretDelete {
+        /// Secret name
+        #[arg(short, long)]
+        name: String,
+    },
+
     /// Update a tool's configuration
     Config {
         /// Server ID
@@ -103,6 +302,11 @@ enum Commands {
         /// Database path (optional, uses default if not provided)
         #[arg(short, long)]
         db_path: Option<std::path::PathBuf>,
+
+        /// Key file of the install importing the export, secrets are re-encrypted with its key
+        /// instead of being excluded
+        #[arg(long)]
+        secrets_key_file: Option<std::path::PathBuf>,
     },
 
     /// Import Claude Desktop config
@@ -192,6 +396,10 @@ async fn main() {
                 println!("   Type: {}", server.definition.tools_type);
                 println!("   Status: {}", server.status);
                 println!("   Tool Count: {}", server.tool_count);
+                println!(
+                    "   Tool Definitions: ~{} tokens ({} bytes)",
+                    server.tool_size.tokens, server.tool_size.bytes
+                );
                 println!();
             }
 
@@ -206,6 +414,9 @@ async fn main() {
                     tool.proxy_id.as_deref().unwrap_or("None")
                 );
                 println!("   Description: {}", tool.description);
+                if let Some(size) = tool.size {
+                    println!("   Size: ~{} tokens ({} bytes)", size.tokens, size.bytes);
+                }
                 println!();
             }
         }
@@ -225,6 +436,358 @@ async fn main() {
             println!("Tool status update is not directly supported through the CLI.");
             println!("Please use the MCP Dockmaster UI to update tool status.");
         }
+        Commands::ToolActive {
+            server_id,
+            tool_id,
+            active,
+        } => {
+            info!("Updating tool {tool_id} of server {server_id} (active={active})");
+
+            match mcp_core.set_tool_active(server_id, tool_id, active).await {
+                Ok(response) => {
+                    println!("{}", response.message);
+                }
+                Err(e) => {
+                    error!("Error updating tool: {e}");
+                    println!("Error updating tool: {e}");
+                }
+            }
+        }
+        Commands::ToolOverride {
+            server_id,
+            tool_id,
+            name,
+            description,
+            args,
+            reset,
+        } => {
+            info!("Updating overrides of tool {tool_id} of server {server_id}");
+
+            let mut argument_descriptions = HashMap::new();
+            for arg in args {
+                match arg.split_once('=') {
+                    Some((argument, description)) => {
+                        argument_descriptions
+                            .insert(argument.to_string(), description.to_string());
+                    }
+                    None => {
+                        println!("Invalid argument description '{arg}', expected ARGUMENT=DESCRIPTION");
+                        return;
+                    }
+                }
+            }
+            let overrides = (!reset).then_some(ToolOverrides {
+                name,
+                description,
+                argument_descriptions,
+            });
+
+            match mcp_core
+                .set_tool_overrides(server_id, tool_id, overrides)
+                .await
+            {
+                Ok(response) => {
+                    println!("{}", response.message);
+                }
+                Err(e) => {
+                    error!("Error updating tool: {e}");
+                    println!("Error updating tool: {e}");
+                }
+            }
+        }
+        Commands::ToolPresets {
+            server_id,
+            tool_id,
+            pin,
+            default,
+        } => {
+            info!("Updating argument presets of tool {tool_id} of server {server_id}");
+
+            let mut presets = HashMap::new();
+            let pinned = pin.into_iter().map(|preset| (preset, false));
+            let defaults = default.into_iter().map(|preset| (preset, true));
+            for (preset, overridable) in pinned.chain(defaults) {
+                let Some((argument, value)) = preset.split_once('=') else {
+                    println!("Invalid preset '{preset}', expected ARGUMENT=VALUE");
+                    return;
+                };
+                // VALUE is JSON when it parses as such, a plain string otherwise
+                let value = serde_json::from_str(value)
+                    .unwrap_or_else(|_| serde_json::Value::String(value.to_string()));
+                presets.insert(argument.to_string(), ArgumentPreset { value, overridable });
+            }
+
+            match mcp_core
+                .set_tool_argument_presets(server_id, tool_id, presets)
+                .await
+            {
+                Ok(response) => {
+                    println!("{}", response.message);
+                }
+                Err(e) => {
+                    error!("Error updating tool: {e}");
+                    println!("Error updating tool: {e}");
+                }
+            }
+        }
+        Commands::ToolCache {
+            server_id,
+            tool_id,
+            ttl,
+        } => {
+            info!("Updating cache TTL of tool {tool_id} of server {server_id}");
+            match mcp_core.set_tool_cache_ttl(server_id, tool_id, ttl).await {
+                Ok(response) => {
+                    println!("{}", response.message);
+                }
+                Err(e) => {
+                    error!("Error updating tool: {e}");
+                    println!("Error updating tool: {e}");
+                }
+            }
+        }
+        Commands::CacheStats => {
+            let stats = mcp_core.result_cache_stats().await;
+            println!("Result cache:");
+            println!("  Hits: {}", stats.hits);
+            println!("  Misses: {}", stats.misses);
+            println!("  Entries: {} ({} bytes)", stats.entries, stats.bytes);
+        }
+        Commands::Profiles => match mcp_core.list_profiles().await {
+            Ok(profiles) => {
+                println!("Profiles:");
+                for profile in profiles {
+                    println!("- {} (/sse/{})", profile.name, profile.name);
+                    if !profile.description.is_empty() {
+                        println!("  {}", profile.description);
+                    }
+                    println!("  Servers: {}", profile.servers.join(", "));
+                    if let Some(token_budget) = profile.token_budget {
+                        println!("  Token budget: {token_budget}");
+                    }
+                    if let Some(schema_strictness) = profile.schema_strictness {
+                        println!("  Schema strictness: {schema_strictness}");
+                    }
+                    for tool in profile.tools {
+                        println!("  Tool: {}/{}", tool.server_id, tool.tool_id);
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Error listing profiles: {e}");
+                println!("Error listing profiles: {e}");
+            }
+        },
+        Commands::ProfileSave {
+            name,
+            description,
+            servers,
+            tools,
+            token_budget,
+            schema_strictness,
+        } => {
+            info!("Saving profile {name}");
+
+            let mut profile_tools = Vec::new();
+            for tool in tools {
+                let Some((server_id, tool_id)) = tool.split_once('/') else {
+                    println!("Invalid tool '{tool}', expected SERVER_ID/TOOL_ID");
+                    return;
+                };
+                profile_tools.push(ProfileTool {
+                    server_id: server_id.to_string(),
+                    tool_id: tool_id.to_string(),
+                });
+            }
+            let profile = Profile {
+                name: name.clone(),
+                description,
+                servers,
+                tools: profile_tools,
+                token_budget,
+                schema_strictness,
+            };
+
+            match mcp_core.save_profile(profile).await {
+                Ok(()) => println!("Profile '{name}' saved, served at /sse/{name}"),
+                Err(e) => {
+                    error!("Error saving profile: {e}");
+                    println!("Error saving profile: {e}");
+                }
+            }
+        }
+        Commands::ProfileDelete { name } => match mcp_core.delete_profile(&name).await {
+            Ok(()) => println!("Profile '{name}' deleted"),
+            Err(e) => {
+                error!("Error deleting profile: {e}");
+                println!("Error deleting profile: {e}");
+            }
+        },
+        Commands::Policies => match mcp_core.list_client_policies().await {
+            Ok(policies) => {
+                println!("Client Policies:");
+                for policy in policies {
+                    println!("- {}", policy.profile);
+                    match &policy.allowed_servers {
+                        Some(servers) => println!("  Allowed servers: {}", servers.join(", ")),
+                        None => println!("  Allowed servers: all"),
+                    }
+                    if let Some(max_calls_per_minute) = policy.max_calls_per_minute {
+                        println!("  Rate limit: {max_calls_per_minute} calls per minute");
+                    }
+                    println!("  Requires approval: {}", policy.require_approval);
+                    if let Some(schema_strictness) = policy.schema_strictness {
+                        println!("  Schema strictness: {schema_strictness}");
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Error listing client policies: {e}");
+                println!("Error listing client policies: {e}");
+            }
+        },
+        Commands::PolicySave {
+            profile,
+            servers,
+            max_calls_per_minute,
+            require_approval,
+            schema_strictness,
+        } => {
+            info!("Saving client policy of profile {profile}");
+
+            let policy = ClientPolicy {
+                profile: profile.clone(),
+                allowed_servers: (!servers.is_empty()).then_some(servers),
+                max_calls_per_minute,
+                require_approval,
+                schema_strictness,
+            };
+            match mcp_core.save_client_policy(policy).await {
+                Ok(()) => println!("Client policy of profile '{profile}' saved"),
+                Err(e) => {
+                    error!("Error saving client policy: {e}");
+                    println!("Error saving client policy: {e}");
+                }
+            }
+        }
+        Commands::PolicyDelete { profile } => {
+            match mcp_core.delete_client_policy(&profile).await {
+                Ok(()) => println!("Client policy of profile '{profile}' deleted"),
+                Err(e) => {
+                    error!("Error deleting client policy: {e}");
+                    println!("Error deleting client policy: {e}");
+                }
+            }
+        }
+        Commands::CompositeTools => match mcp_core.list_composite_tools().await {
+            Ok(tools) => {
+                println!("Composite Tools:");
+                for tool in tools {
+                    println!("- {}", tool.name);
+                    if !tool.description.is_empty() {
+                        println!("  {}", tool.description);
+                    }
+                    for step in tool.steps {
+                        println!("  {}: {}/{}", step.id, step.server_id, step.tool_id);
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Error listing composite tools: {e}");
+                println!("Error listing composite tools: {e}");
+            }
+        },
+        Commands::CompositeSave { file } => {
+            let tool = std::fs::read_to_string(&file)
+                .map_err(|e| format!("Failed to read {}: {e}", file.display()))
+                .and_then(|content| {
+                    serde_json::from_str::<CompositeTool>(&content)
+                        .map_err(|e| format!("Invalid composite tool definition: {e}"))
+                });
+            let tool = match tool {
+                Ok(tool) => tool,
+                Err(e) => {
+                    error!("{e}");
+                    println!("{e}");
+                    return;
+                }
+            };
+
+            let name = tool.name.clone();
+            info!("Saving composite tool {name}");
+            match mcp_core.save_composite_tool(tool).await {
+                Ok(()) => println!("Composite tool '{name}' saved"),
+                Err(e) => {
+                    error!("Error saving composite tool: {e}");
+                    println!("Error saving composite tool: {e}");
+                }
+            }
+        }
+        Commands::CompositeDelete { name } => match mcp_core.delete_composite_tool(&name).await {
+            Ok(()) => println!("Composite tool '{name}' deleted"),
+            Err(e) => {
+                error!("Error deleting composite tool: {e}");
+                println!("Error deleting composite tool: {e}");
+            }
+        },
+        Commands::Secrets => match mcp_core.list_secrets().await {
+            Ok(secrets) => {
+                println!("Secrets:");
+                for secret in secrets {
+                    println!("- {}", secret.name);
+                    if !secret.description.is_empty() {
+                        println!("  {}", secret.description);
+                    }
+                    if secret.servers.is_empty() {
+                        println!("  Used by: none");
+                    } else {
+                        println!("  Used by: {}", secret.servers.join(", "));
+                    }
+                }
+            }
+            Err(e) => {
+                error!("Error listing secrets: {e}");
+                println!("Error listing secrets: {e}");
+            }
+        },
+        Commands::SecretSave {
+            name,
+            value,
+            description,
+        } => {
+            let value = match value {
+                Some(value) => value,
+                None => {
+                    let mut value = String::new();
+                    if let Err(e) = std::io::stdin().read_line(&mut value) {
+                        error!("Failed to read the secret value: {e}");
+                        println!("Failed to read the secret value: {e}");
+                        return;
+                    }
+                    value.trim_end_matches(['\r', '\n']).to_string()
+                }
+            };
+
+            info!("Saving secret {name}");
+            match mcp_core.save_secret(&name, &description, &value).await {
+                Ok(servers) if servers.is_empty() => println!("Secret '{name}' saved"),
+                Ok(servers) => println!(
+                    "Secret '{name}' saved, used by {}",
+                    servers.join(", ")
+                ),
+                Err(e) => {
+                    error!("Error saving secret: {e}");
+                    println!("Error saving secret: {e}");
+                }
+            }
+        }
+        Commands::SecretDelete { name } => match mcp_core.delete_secret(&name).await {
+            Ok(()) => println!("Secret '{name}' deleted"),
+            Err(e) => {
+                error!("Error deleting secret: {e}");
+                println!("Error deleting secret: {e}");
+            }
+        },
         Commands::Config { server_id, .. } => {
             info!("Updating server configuration: {server_id}");
 
@@ -269,11 +832,15 @@ async fn main() {
                 }
             }
         }
-        Commands::Export { output, db_path } => {
+        Commands::Export {
+            output,
+            db_path,
+            secrets_key_file,
+        } => {
             info!("Exporting server configurations");
 
             // Use the export functionality
-            match export::export_servers(db_path, output) {
+            match export::export_servers(db_path, output, secrets_key_file) {
                 Ok(_) => {
                     println!("Export completed successfully");
                 }
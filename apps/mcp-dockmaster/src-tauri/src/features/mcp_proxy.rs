@@ -1,16 +1,21 @@
 use mcp_core::{
     core::{
-        mcp_core::MCPCore, mcp_core_database_ext::McpCoreDatabaseExt,
-        mcp_core_installers_ext::McpCoreInstallersExt, mcp_core_proxy_ext::McpCoreProxyExt,
+        mcp_core::MCPCore, mcp_core_composite_ext::McpCoreCompositeExt,
+        mcp_core_database_ext::McpCoreDatabaseExt,
+        mcp_core_installers_ext::McpCoreInstallersExt,
+        mcp_core_profiles_ext::McpCoreProfilesExt, mcp_core_proxy_ext::McpCoreProxyExt,
+        mcp_core_secrets_ext::McpCoreSecretsExt, mcp_core_sessions_ext::McpCoreSessionsExt,
     },
     models::types::{
-        CustomServerRegistrationRequest, DiscoverServerToolsRequest, ServerConfigUpdateRequest, 
-        ServerRegistrationRequest, ServerRegistrationResponse, ServerToolInfo, 
-        ServerUninstallResponse, ServerUpdateRequest, ToolConfigUpdateResponse, 
-        ToolExecutionRequest, ToolExecutionResponse, ToolUninstallRequest, ToolUpdateResponse,
+        ArgumentPreset, ClientPolicy, CompositeTool, CustomServerRegistrationRequest, PendingApproval, Profile, DiscoverServerToolsRequest, ServerConfigUpdateRequest, 
+        ResultCacheStats, Secret, ServerRegistrationRequest, ServerRegistrationResponse, ServerToolInfo, 
+        ServerUninstallResponse, ServerUpdateRequest, SessionInfo, ToolConfigUpdateResponse, 
+        ToolExecutionRequest, ToolExecutionResponse, ToolOverrides, ToolUninstallRequest,
+        ToolUpdateResponse,
     },
     types::{IsProcessRunningRequest, RuntimeServer},
 };
+use std::collections::HashMap;
 use tauri::State;
 
 /// Register a new tool with the MCP server
@@ -103,7 +108,7 @@ pub async fn clear_database_command(mcp_core: State<'_, MCPCore>) -> Result<(),
 }
 
 /// Restart a tool by its ID
-#[tauri::command(rename_all = "camelCase")]
+#[tauri::command]
 pub async fn restart_server_command(
     mcp_core: State<'_, MCPCore>,
     server_id: String,
@@ -111,6 +116,66 @@ pub async fn restart_server_command(
     mcp_core.restart_server_command(server_id).await
 }
 
+/// Enable or disable a single tool of a server
+#[tauri::command]
+pub async fn set_tool_active(
+    mcp_core: State<'_, MCPCore>,
+    server_id: String,
+    tool_id: String,
+    active: bool,
+) -> Result<ToolUpdateResponse, String> {
+    mcp_core.set_tool_active(server_id, tool_id, active).await
+}
+
+/// Expose a tool under another name or descriptions, `null` restores the upstream ones
+#[tauri::command]
+pub async fn set_tool_overrides(
+    mcp_core: State<'_, MCPCore>,
+    server_id: String,
+    tool_id: String,
+    overrides: Option<ToolOverrides>,
+) -> Result<ToolUpdateResponse, String> {
+    mcp_core.set_tool_overrides(server_id, tool_id, overrides).await
+}
+
+/// Replace the argument presets of a tool, an empty map removes them
+#[tauri::command]
+pub async fn set_tool_argument_presets(
+    mcp_core: State<'_, MCPCore>,
+    server_id: String,
+    tool_id: String,
+    presets: HashMap<String, ArgumentPreset>,
+) -> Result<ToolUpdateResponse, String> {
+    mcp_core
+        .set_tool_argument_presets(server_id, tool_id, presets)
+        .await
+}
+
+/// Cache the results of a tool for `ttl` seconds, `null` disables caching
+#[tauri::command]
+pub async fn set_tool_cache_ttl(
+    mcp_core: State<'_, MCPCore>,
+    server_id: String,
+    tool_id: String,
+    ttl: Option<u64>,
+) -> Result<ToolUpdateResponse, String> {
+    mcp_core.set_tool_cache_ttl(server_id, tool_id, ttl).await
+}
+
+/// Hit and miss counters of the result cache
+#[tauri::command]
+pub async fn get_result_cache_stats(
+    mcp_core: State<'_, MCPCore>,
+) -> Result<ResultCacheStats, String> {
+    Ok(mcp_core.result_cache_stats().await)
+}
+
+#[tauri::command]
+pub async fn clear_result_cache(mcp_core: State<'_, MCPCore>) -> Result<(), String> {
+    mcp_core.clear_result_cache().await;
+    Ok(())
+}
+
 // Check if Claude is installed
 #[tauri::command]
 pub async fn check_claude_installed(mcp_core: State<'_, MCPCore>) -> Result<bool, String> {
@@ -123,16 +188,22 @@ pub async fn check_cursor_installed(mcp_core: State<'_, MCPCore>) -> Result<bool
     mcp_core.is_cursor_installed()
 }
 
-// Install Claude
+// Install Claude, optionally serving a profile
 #[tauri::command]
-pub async fn install_claude(mcp_core: State<'_, MCPCore>) -> Result<(), String> {
-    mcp_core.install_claude()
+pub async fn install_claude(
+    mcp_core: State<'_, MCPCore>,
+    profile: Option<String>,
+) -> Result<(), String> {
+    mcp_core.install_claude(profile.as_deref())
 }
 
-// Install Cursor
+// Install Cursor, optionally serving a profile
 #[tauri::command]
-pub async fn install_cursor(mcp_core: State<'_, MCPCore>) -> Result<(), String> {
-    mcp_core.install_cursor()
+pub async fn install_cursor(
+    mcp_core: State<'_, MCPCore>,
+    profile: Option<String>,
+) -> Result<(), String> {
+    mcp_core.install_cursor(profile.as_deref())
 }
 
 // Get Claude config
@@ -153,6 +224,123 @@ pub async fn get_generic_config(mcp_core: State<'_, MCPCore>) -> Result<String,
     mcp_core.get_generic_config()
 }
 
+/// List the profiles
+#[tauri::command]
+pub async fn list_profiles(mcp_core: State<'_, MCPCore>) -> Result<Vec<Profile>, String> {
+    mcp_core.list_profiles().await
+}
+
+/// Create or replace a profile
+#[tauri::command]
+pub async fn save_profile(mcp_core: State<'_, MCPCore>, profile: Profile) -> Result<(), String> {
+    mcp_core.save_profile(profile).await
+}
+
+/// Delete a profile
+#[tauri::command]
+pub async fn delete_profile(mcp_core: State<'_, MCPCore>, name: String) -> Result<(), String> {
+    mcp_core.delete_profile(&name).await
+}
+
+/// List the composite tools
+#[tauri::command]
+pub async fn list_composite_tools(
+    mcp_core: State<'_, MCPCore>,
+) -> Result<Vec<CompositeTool>, String> {
+    mcp_core.list_composite_tools().await
+}
+
+/// Create or replace a composite tool
+#[tauri::command]
+pub async fn save_composite_tool(
+    mcp_core: State<'_, MCPCore>,
+    tool: CompositeTool,
+) -> Result<(), String> {
+    mcp_core.save_composite_tool(tool).await
+}
+
+/// Delete a composite tool
+#[tauri::command]
+pub async fn delete_composite_tool(
+    mcp_core: State<'_, MCPCore>,
+    name: String,
+) -> Result<(), String> {
+    mcp_core.delete_composite_tool(&name).await
+}
+
+/// List the vault secrets and the servers using them, without their values
+#[tauri::command]
+pub async fn list_secrets(mcp_core: State<'_, MCPCore>) -> Result<Vec<Secret>, String> {
+    mcp_core.list_secrets().await
+}
+
+/// Create or rotate a vault secret, returns the servers using it, restarted when enabled
+#[tauri::command]
+pub async fn save_secret(
+    mcp_core: State<'_, MCPCore>,
+    name: String,
+    description: String,
+    value: String,
+) -> Result<Vec<String>, String> {
+    mcp_core.save_secret(&name, &description, &value).await
+}
+
+/// Delete a vault secret no server uses
+#[tauri::command]
+pub async fn delete_secret(mcp_core: State<'_, MCPCore>, name: String) -> Result<(), String> {
+    mcp_core.delete_secret(&name).await
+}
+
+/// List the connected clients and their tool call counts
+#[tauri::command]
+pub async fn list_sessions(mcp_core: State<'_, MCPCore>) -> Result<Vec<SessionInfo>, String> {
+    mcp_core.list_sessions().await
+}
+
+/// List the client policies
+#[tauri::command]
+pub async fn list_client_policies(
+    mcp_core: State<'_, MCPCore>,
+) -> Result<Vec<ClientPolicy>, String> {
+    mcp_core.list_client_policies().await
+}
+
+/// Create or replace the client policy of a profile
+#[tauri::command]
+pub async fn save_client_policy(
+    mcp_core: State<'_, MCPCore>,
+    policy: ClientPolicy,
+) -> Result<(), String> {
+    mcp_core.save_client_policy(policy).await
+}
+
+/// Delete the client policy of a profile
+#[tauri::command]
+pub async fn delete_client_policy(
+    mcp_core: State<'_, MCPCore>,
+    profile: String,
+) -> Result<(), String> {
+    mcp_core.delete_client_policy(&profile).await
+}
+
+/// List the tool calls waiting for approval
+#[tauri::command]
+pub async fn list_pending_approvals(
+    mcp_core: State<'_, MCPCore>,
+) -> Result<Vec<PendingApproval>, String> {
+    mcp_core.list_pending_approvals().await
+}
+
+/// Approve or deny a tool call waiting for approval
+#[tauri::command]
+pub async fn resolve_approval(
+    mcp_core: State<'_, MCPCore>,
+    id: String,
+    approved: bool,
+) -> Result<(), String> {
+    mcp_core.resolve_approval(&id, approved).await
+}
+
 /// Import a server from a GitHub repository URL
 #[tauri::command]
 pub async fn import_server_from_url(
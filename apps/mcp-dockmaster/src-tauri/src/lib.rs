@@ -1,8 +1,10 @@
 use crate::features::mcp_proxy::{
-    analyze_github_repository, analyze_local_directory, check_database_exists_command, clear_database_command, 
-    discover_tools, execute_proxy_tool, get_tools_visibility_state, import_server_from_url, 
-    list_all_server_tools, list_servers, register_custom_server, register_server, 
-    restart_server_command, set_tools_hidden, uninstall_server, update_server_config, 
+    analyze_github_repository, analyze_local_directory, check_database_exists_command, clear_database_command, clear_result_cache,
+    delete_client_policy, delete_composite_tool, delete_profile, delete_secret, discover_tools, execute_proxy_tool, get_result_cache_stats, get_tools_visibility_state, import_server_from_url, 
+    list_all_server_tools, list_client_policies, list_composite_tools, list_pending_approvals, list_profiles, list_secrets,
+    list_servers, list_sessions, register_custom_server, register_server, 
+    resolve_approval, restart_server_command, save_client_policy, save_composite_tool, save_profile, save_secret, set_tool_active, set_tool_argument_presets, set_tool_cache_ttl, set_tool_overrides,
+    set_tools_hidden, uninstall_server, update_server_config, 
     update_server_status,
 };
 use commands::{get_app_identifier, get_mcp_proxy_server_binary_path};
@@ -314,6 +316,27 @@ pub async fn run() {
             update_server_status,
             update_server_config,
             restart_server_command,
+            set_tool_active,
+            set_tool_overrides,
+            set_tool_argument_presets,
+            set_tool_cache_ttl,
+            get_result_cache_stats,
+            clear_result_cache,
+            list_profiles,
+            save_profile,
+            delete_profile,
+            list_composite_tools,
+            save_composite_tool,
+            delete_composite_tool,
+            list_secrets,
+            save_secret,
+            delete_secret,
+            list_sessions,
+            list_client_policies,
+            save_client_policy,
+            delete_client_policy,
+            list_pending_approvals,
+            resolve_approval,
             uninstall_server,
             check_database_exists_command,
             clear_database_command,
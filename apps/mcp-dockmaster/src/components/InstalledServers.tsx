@@ -81,6 +81,10 @@ const getColorTagStyle = (color: string) => {
   return colorMap[color] || 'bg-gray-500 text-white border-gray-600';
 };
 
+// Names of the vault secrets an env value references as ${secret:name}
+const secretReferences = (value: string) =>
+  Array.from(value.matchAll(/\$\{secret:([^}]*)\}/g), (match) => match[1]);
+
 const InstalledServers: React.FC = () => {
   const { t } = useTranslation();
   const [servers, setServers] = useState<RuntimeServer[]>([]);
@@ -929,8 +933,13 @@ const InstalledServers: React.FC = () => {
                                 <Badge variant="outline" className="bg-amber-100 text-amber-800 border-amber-300">{t('common.required')}</Badge> : 
                                 <Badge variant="outline" className="bg-slate-100 text-slate-800 border-slate-300">{t('common.optional')}</Badge>
                               }
-                              {value.default && <span className="ml-2">{t('common.default')}: <span className="font-mono">{value.default}</span></span>}
+                              {value.default && !value.secret && <span className="ml-2">{t('common.default')}: <span className="font-mono">{value.default}</span></span>}
                             </div>
+                            {value.default && secretReferences(value.default).length > 0 && (
+                              <div className="text-xs mt-1">
+                                {t('installed_servers.server_info.vault_secrets_label')}: <span className="font-mono">{secretReferences(value.default).join(', ')}</span>
+                              </div>
+                            )}
                           </div>
                         ))}
                       </div>
@@ -1026,7 +1035,7 @@ const InstalledServers: React.FC = () => {
                     <label htmlFor={`env-${key}`}>{key}</label>
                     <input
                       id={`env-${key}`}
-                      type="text"
+                      type={typeof value === "object" && value?.secret ? "password" : "text"}
                       value={envVarValues[key] ?? defaultValue ?? ""}
                       onChange={(e) => handleEnvVarChange(key, e.target.value)}
                       placeholder={description || key}
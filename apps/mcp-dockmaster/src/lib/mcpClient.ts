@@ -63,6 +63,8 @@ export interface RuntimeEnvConfig {
   default: string;
   description: string;
   required: boolean;
+  /** Encrypted at rest, `default` holds the encrypted value */
+  secret?: boolean;
 }
 
 export interface InputSchemaProperty {
@@ -103,10 +105,36 @@ export interface RuntimeServer extends ServerDefinition {
   id: string;  // Using string instead of ToolId since we don't need the full Rust implementation
   status: ServerStatus;
   tool_count: number;
+  tool_size: ToolSize;
   sourceUrl?: string;
   colorTags?: string[]; // Add this line to store color tags
 }
 
+/** Size of tool definitions, `tokens` is estimated from `bytes` */
+export interface ToolSize {
+  bytes: number;
+  tokens: number;
+}
+
+export interface ToolAnnotations {
+  title?: string;
+  readOnlyHint?: boolean;
+  destructiveHint?: boolean;
+  idempotentHint?: boolean;
+  openWorldHint?: boolean;
+}
+
+export interface ToolOverrides {
+  name?: string;
+  description?: string;
+  argumentDescriptions?: Record<string, string>;
+}
+
+export interface ArgumentPreset {
+  value: any;
+  overridable?: boolean;
+}
+
 export interface ServerToolInfo {
   id: string;
   name: string;
@@ -114,6 +142,96 @@ export interface ServerToolInfo {
   inputSchema?: InputSchema;
   server_id: string;
   proxy_id?: string;
+  title?: string;
+  annotations?: ToolAnnotations;
+  overrides?: ToolOverrides;
+  argumentPresets?: Record<string, ArgumentPreset>;
+  /** Seconds results stay cached, absent when caching is off */
+  cacheTtl?: number;
+  size?: ToolSize;
+}
+
+export interface ResultCacheStats {
+  hits: number;
+  misses: number;
+  entries: number;
+  bytes: number;
+}
+
+export interface ProfileTool {
+  server_id: string;
+  tool_id: string;
+}
+
+export interface Profile {
+  name: string;
+  description?: string;
+  servers?: string[];
+  tools?: ProfileTool[];
+  /** Estimated tokens of tool definitions, lowest-priority tools are minified then dropped to fit */
+  token_budget?: number;
+  /** Normalization of the listed input schemas, unless the client's policy sets one */
+  schema_strictness?: SchemaStrictness;
+}
+
+/** How far input schemas are normalized for clients that reject parts of JSON Schema */
+export type SchemaStrictness = 'off' | 'standard' | 'strict';
+
+export interface CompositeStep {
+  /** Key of the step result, available to later steps as `$.steps.<id>` */
+  id: string;
+  server_id: string;
+  tool_id: string;
+  /** Strings starting with `$` are JSONPath expressions over the input and earlier results */
+  arguments?: Record<string, any>;
+  /** JSONPath selecting a list, the tool is called once per element available as `$.item` */
+  for_each?: string;
+}
+
+export interface CompositeTool {
+  name: string;
+  description?: string;
+  input_schema?: Record<string, any>;
+  steps: CompositeStep[];
+  /** Template of the combined output, the result of the last step when undefined */
+  output?: any;
+}
+
+/** Credential of the vault, referenced from server env values as `${secret:name}` */
+export interface Secret {
+  name: string;
+  description?: string;
+  /** IDs of the servers whose env references the secret */
+  servers: string[];
+}
+
+export interface SessionInfo {
+  session_id: string;
+  client_name: string;
+  client_version: string;
+  profile?: string;
+  connected_at: number;
+  tool_calls: number;
+}
+
+export interface ClientPolicy {
+  /** Profile of the connections, "*" applies to the connections without a policy of their own */
+  profile: string;
+  /** Servers the clients may use, undefined allows every server */
+  allowed_servers?: string[];
+  max_calls_per_minute?: number;
+  require_approval?: boolean;
+  schema_strictness?: SchemaStrictness;
+}
+
+export interface PendingApproval {
+  id: string;
+  session_id: string;
+  client_name: string;
+  server_id: string;
+  tool_name: string;
+  arguments?: Record<string, any>;
+  requested_at: number;
 }
 
 export interface ServerRegistrationResponse {
@@ -123,8 +241,13 @@ export interface ServerRegistrationResponse {
   tool_id?: string;
 }
 
+interface ToolTarget {
+  server_id: string;
+  tool_name: string;
+}
+
 interface ToolExecutionRequest {
-  tool_id: string;
+  target: ToolTarget;
   parameters: any;
 }
 
@@ -217,6 +340,162 @@ export class MCPClient {
     return await invoke<ServerUpdateResponse>('restart_server_command', { serverId });
   }
 
+  /**
+   * Enable or disable a single tool of a server
+   */
+  static async setToolActive(serverId: string, toolId: string, active: boolean): Promise<ServerUpdateResponse> {
+    return await invoke<ServerUpdateResponse>('set_tool_active', { serverId, toolId, active });
+  }
+
+  /**
+   * Expose a tool under another name or descriptions, null restores the upstream ones
+   */
+  static async setToolOverrides(
+    serverId: string,
+    toolId: string,
+    overrides: ToolOverrides | null
+  ): Promise<ServerUpdateResponse> {
+    return await invoke<ServerUpdateResponse>('set_tool_overrides', { serverId, toolId, overrides });
+  }
+
+  /**
+   * Replace the argument presets of a tool, an empty object removes them
+   */
+  static async setToolArgumentPresets(
+    serverId: string,
+    toolId: string,
+    presets: Record<string, ArgumentPreset>
+  ): Promise<ServerUpdateResponse> {
+    return await invoke<ServerUpdateResponse>('set_tool_argument_presets', { serverId, toolId, presets });
+  }
+
+  /**
+   * Cache the results of a tool for `ttl` seconds, null disables caching
+   */
+  static async setToolCacheTtl(
+    serverId: string,
+    toolId: string,
+    ttl: number | null
+  ): Promise<ServerUpdateResponse> {
+    return await invoke<ServerUpdateResponse>('set_tool_cache_ttl', { serverId, toolId, ttl });
+  }
+
+  /**
+   * Hit and miss counters of the result cache
+   */
+  static async getResultCacheStats(): Promise<ResultCacheStats> {
+    return await invoke<ResultCacheStats>('get_result_cache_stats');
+  }
+
+  static async clearResultCache(): Promise<void> {
+    return await invoke<void>('clear_result_cache');
+  }
+
+  /**
+   * List the profiles, each served at /sse/{name}
+   */
+  static async listProfiles(): Promise<Profile[]> {
+    return await invoke<Profile[]>('list_profiles');
+  }
+
+  /**
+   * Create or replace a profile
+   */
+  static async saveProfile(profile: Profile): Promise<void> {
+    return await invoke<void>('save_profile', { profile });
+  }
+
+  /**
+   * Delete a profile
+   */
+  static async deleteProfile(name: string): Promise<void> {
+    return await invoke<void>('delete_profile', { name });
+  }
+
+  /**
+   * List the composite tools
+   */
+  static async listCompositeTools(): Promise<CompositeTool[]> {
+    return await invoke<CompositeTool[]>('list_composite_tools');
+  }
+
+  /**
+   * Create or replace a composite tool
+   */
+  static async saveCompositeTool(tool: CompositeTool): Promise<void> {
+    return await invoke<void>('save_composite_tool', { tool });
+  }
+
+  /**
+   * Delete a composite tool
+   */
+  static async deleteCompositeTool(name: string): Promise<void> {
+    return await invoke<void>('delete_composite_tool', { name });
+  }
+
+  /**
+   * List the vault secrets and the servers using them
+   */
+  static async listSecrets(): Promise<Secret[]> {
+    return await invoke<Secret[]>('list_secrets');
+  }
+
+  /**
+   * Create or rotate a vault secret, the servers using it are restarted
+   */
+  static async saveSecret(name: string, description: string, value: string): Promise<string[]> {
+    return await invoke<string[]>('save_secret', { name, description, value });
+  }
+
+  /**
+   * Delete a vault secret no server uses
+   */
+  static async deleteSecret(name: string): Promise<void> {
+    return await invoke<void>('delete_secret', { name });
+  }
+
+  /**
+   * List the connected clients and their tool call counts
+   */
+  static async listSessions(): Promise<SessionInfo[]> {
+    return await invoke<SessionInfo[]>('list_sessions');
+  }
+
+  /**
+   * List the client policies
+   */
+  static async listClientPolicies(): Promise<ClientPolicy[]> {
+    return await invoke<ClientPolicy[]>('list_client_policies');
+  }
+
+  /**
+   * Create or replace the client policy of a profile
+   */
+  static async saveClientPolicy(policy: ClientPolicy): Promise<void> {
+    return await invoke<void>('save_client_policy', { policy });
+  }
+
+  /**
+   * Delete the client policy of a profile
+   */
+  static async deleteClientPolicy(profile: string): Promise<void> {
+    return await invoke<void>('delete_client_policy', { profile });
+  }
+
+  /**
+   * List the tool calls waiting for approval
+   */
+  static async listPendingApprovals(): Promise<PendingApproval[]> {
+    return await invoke<PendingApproval[]>('list_pending_approvals');
+  }
+
+  /**
+   * Approve or deny a tool call waiting for approval
+   */
+  static async resolveApproval(id: string, approved: boolean): Promise<void> {
+    return await invoke<void>('resolve_approval', { id, approved });
+  }
+
   /**
    * Uninstall a registered tool
    */
@@ -9,9 +9,9 @@ export const checkClaude = async () => {
   }
 };
 
-export const installClaude = async () => {
+export const installClaude = async (profile?: string) => {
   try {
-    return await invoke<void>("install_claude");
+    return await invoke<void>("install_claude", { profile });
   } catch (error) {
     console.error("Failed to install Claude:", error);
   }
@@ -26,9 +26,9 @@ export const checkCursor = async () => {
   }
 };
 
-export const installCursor = async () => {
+export const installCursor = async (profile?: string) => {
   try {
-    return await invoke<void>("install_cursor");
+    return await invoke<void>("install_cursor", { profile });
   } catch (error) {
     console.error("Failed to install Cursor:", error);
     throw error;
@@ -1,7 +1,10 @@
 use anyhow::Result;
 use clap::Parser;
 use rmcp::{ServiceExt, transport::stdio};
-use server::mcp_proxy_client::get_mcp_client;
+use server::{
+    discovery::{auth_token, read_discovery},
+    mcp_proxy_client::get_mcp_client,
+};
 // use tracing_subscriber::{self, EnvFilter};
 
 pub mod server;
@@ -12,6 +15,10 @@ struct Args {
     /// Use SSE transport with specified port
     #[arg(long)]
     see_target_address: Option<String>,
+
+    /// Profile to serve, its tools are read from /sse/{profile} of the default address
+    #[arg(long)]
+    profile: Option<String>,
 }
 
 #[tokio::main]
@@ -43,15 +50,27 @@ async fn main() -> Result<()> {
         tracing::warn!("Failed to parse arguments: {}. Using default values.", e);
         Args {
             see_target_address: None,
+            profile: None,
         }
     });
 
-    let sse_address = args
-        .see_target_address
-        .unwrap_or("http://127.0.0.1:11011/sse".to_string());
+    if args.see_target_address.is_some() && args.profile.is_some() {
+        tracing::warn!("Ignoring --profile because an SSE address was given");
+    }
 
     let mcp_proxy_client = loop {
-        match get_mcp_client(&sse_address).await {
+        // Read on every attempt, Dockmaster writes the discovery file when it starts
+        let discovery = read_discovery();
+        let base_address = match discovery.url.as_str() {
+            "" => "http://127.0.0.1:11011",
+            url => url.trim_end_matches('/'),
+        };
+        let sse_address = match (&args.see_target_address, &args.profile) {
+            (Some(address), _) => address.clone(),
+            (None, Some(profile)) => format!("{base_address}/sse/{profile}"),
+            (None, None) => format!("{base_address}/sse"),
+        };
+        match get_mcp_client(&sse_address, auth_token(&discovery).as_deref()).await {
             Ok(client) => break client,
             Err(e) => {
                 tracing::error!("Error getting MCP client: {:?}. Retrying...", e);
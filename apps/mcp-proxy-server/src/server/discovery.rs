@@ -0,0 +1,42 @@
+use std::{env, path::PathBuf};
+
+use serde::Deserialize;
+
+/// Environment variable holding the token, it takes precedence over the discovery file
+pub const AUTH_TOKEN_ENV: &str = "DOCKMASTER_AUTH_TOKEN";
+
+/// Environment variable holding the path of the discovery file, Dockmaster's installers set it
+/// next to the token
+pub const DISCOVERY_FILE_ENV: &str = "DOCKMASTER_DISCOVERY_FILE";
+
+/// Address and token Dockmaster writes when its HTTP server starts
+#[derive(Debug, Default, Deserialize)]
+pub struct Discovery {
+    #[serde(default)]
+    pub url: String,
+    #[serde(default)]
+    pub token: String,
+}
+
+/// Read the discovery file, an empty discovery when no path is set or Dockmaster has not
+/// written it yet
+pub fn read_discovery() -> Discovery {
+    let Some(path) = env::var_os(DISCOVERY_FILE_ENV).map(PathBuf::from) else {
+        return Discovery::default();
+    };
+    let discovery = std::fs::read_to_string(&path)
+        .map_err(|e| e.to_string())
+        .and_then(|content| serde_json::from_str(&content).map_err(|e| e.to_string()));
+    discovery.unwrap_or_else(|e| {
+        tracing::warn!("Failed to read discovery file {}: {}", path.display(), e);
+        Discovery::default()
+    })
+}
+
+/// Token to authenticate with, from the environment or the discovery file
+pub fn auth_token(discovery: &Discovery) -> Option<String> {
+    env::var(AUTH_TOKEN_ENV)
+        .ok()
+        .or_else(|| Some(discovery.token.clone()))
+        .filter(|token| !token.is_empty())
+}
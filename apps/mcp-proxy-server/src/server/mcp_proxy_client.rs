@@ -1,24 +1,48 @@
 use anyhow::Result;
+use reqwest::header::{AUTHORIZATION, HeaderMap, HeaderValue};
 use rmcp::{
     RoleClient, ServiceExt,
     model::{ClientCapabilities, ClientInfo, Implementation, InitializeRequestParam},
     service::RunningService,
-    transport::SseClientTransport,
+    transport::{SseClientTransport, sse_client::SseClientConfig},
 };
 
+/// HTTP client sending the token of the install as a Bearer header on every request
+fn http_client(auth_token: Option<&str>) -> Result<reqwest::Client> {
+    let mut headers = HeaderMap::new();
+    if let Some(token) = auth_token {
+        let mut authorization = HeaderValue::from_str(&format!("Bearer {token}"))?;
+        authorization.set_sensitive(true);
+        headers.insert(AUTHORIZATION, authorization);
+    }
+    Ok(reqwest::Client::builder()
+        .default_headers(headers)
+        .build()?)
+}
+
 pub async fn get_mcp_client(
     server_url: &str,
+    auth_token: Option<&str>,
 ) -> Result<RunningService<RoleClient, InitializeRequestParam>> {
     tracing::info!(
         "Starting MCP client initialization for server URL: {}",
         server_url
     );
+    if auth_token.is_none() {
+        tracing::warn!("No auth token found, the server may reject the connection");
+    }
 
-    let transport = SseClientTransport::start(server_url)
-        .await
-        .inspect_err(|e| {
-            tracing::error!("Error starting transport: {:?}", e);
-        })?;
+    let transport = SseClientTransport::start_with_client(
+        http_client(auth_token)?,
+        SseClientConfig {
+            sse_endpoint: server_url.into(),
+            ..Default::default()
+        },
+    )
+    .await
+    .inspect_err(|e| {
+        tracing::error!("Error starting transport: {:?}", e);
+    })?;
     tracing::info!("Transport layer started successfully.");
 
     let client_info = ClientInfo {
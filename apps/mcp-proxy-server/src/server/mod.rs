@@ -1,2 +1,3 @@
+pub mod discovery;
 pub mod mcp_proxy_client;
 pub mod mcp_proxy_server;
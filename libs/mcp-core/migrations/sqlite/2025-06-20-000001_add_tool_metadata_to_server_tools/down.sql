@@ -0,0 +1,3 @@
+-- Remove the tool metadata columns from server_tools
+ALTER TABLE server_tools DROP COLUMN annotations;
+ALTER TABLE server_tools DROP COLUMN title;
@@ -0,0 +1,3 @@
+-- Add the tool metadata reported by upstream servers to server_tools
+ALTER TABLE server_tools ADD COLUMN title TEXT;
+ALTER TABLE server_tools ADD COLUMN annotations TEXT;
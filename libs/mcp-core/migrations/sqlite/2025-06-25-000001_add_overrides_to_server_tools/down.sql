@@ -0,0 +1,2 @@
+-- Remove the tool overrides column from server_tools
+ALTER TABLE server_tools DROP COLUMN overrides;
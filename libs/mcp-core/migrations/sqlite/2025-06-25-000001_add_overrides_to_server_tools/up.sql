@@ -0,0 +1,2 @@
+-- Add the user overrides of the exposed tool name and descriptions to server_tools
+ALTER TABLE server_tools ADD COLUMN overrides TEXT;
@@ -0,0 +1,2 @@
+-- Remove the argument presets column from server_tools
+ALTER TABLE server_tools DROP COLUMN argument_presets;
@@ -0,0 +1,2 @@
+-- Add the argument values preset by the user to server_tools
+ALTER TABLE server_tools ADD COLUMN argument_presets TEXT;
@@ -0,0 +1,8 @@
+-- Create the profiles table, each profile exposes a subset of the servers and tools
+-- `servers` and `tools` are JSON arrays
+CREATE TABLE profiles (
+    name TEXT PRIMARY KEY,
+    description TEXT NOT NULL DEFAULT '',
+    servers TEXT NOT NULL DEFAULT '[]',
+    tools TEXT NOT NULL DEFAULT '[]'
+);
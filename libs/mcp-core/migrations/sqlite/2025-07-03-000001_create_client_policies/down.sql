@@ -0,0 +1,2 @@
+-- Drop the client_policies table
+DROP TABLE client_policies;
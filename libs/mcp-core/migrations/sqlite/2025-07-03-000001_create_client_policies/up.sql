@@ -0,0 +1,8 @@
+-- Create the client_policies table, rules applied to the clients with a given name
+-- `allowed_servers` is a JSON array, NULL allows every server
+CREATE TABLE client_policies (
+    client_name TEXT PRIMARY KEY,
+    allowed_servers TEXT,
+    max_calls_per_minute INTEGER,
+    require_approval BOOLEAN NOT NULL DEFAULT 0
+);
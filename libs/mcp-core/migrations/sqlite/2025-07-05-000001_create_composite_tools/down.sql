@@ -0,0 +1,2 @@
+-- Drop the composite_tools table
+DROP TABLE composite_tools;
@@ -0,0 +1,9 @@
+-- Create the composite_tools table, each composite tool runs a pipeline of upstream tool calls
+-- `input_schema`, `steps` and `output` are JSON
+CREATE TABLE composite_tools (
+    name TEXT PRIMARY KEY,
+    description TEXT NOT NULL DEFAULT '',
+    input_schema TEXT NOT NULL DEFAULT '{"type":"object","properties":{}}',
+    steps TEXT NOT NULL DEFAULT '[]',
+    output TEXT
+);
@@ -0,0 +1,2 @@
+-- Remove the token budget column from profiles
+ALTER TABLE profiles DROP COLUMN token_budget;
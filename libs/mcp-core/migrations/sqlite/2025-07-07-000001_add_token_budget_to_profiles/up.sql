@@ -0,0 +1,2 @@
+-- Add the optional token budget of the tool definitions listed to a profile's clients
+ALTER TABLE profiles ADD COLUMN token_budget INTEGER;
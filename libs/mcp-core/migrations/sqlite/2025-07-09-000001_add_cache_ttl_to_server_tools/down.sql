@@ -0,0 +1,2 @@
+-- Remove the result cache TTL column from server_tools
+ALTER TABLE server_tools DROP COLUMN cache_ttl;
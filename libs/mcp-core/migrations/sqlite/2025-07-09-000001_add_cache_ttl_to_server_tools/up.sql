@@ -0,0 +1,2 @@
+-- Add the seconds results of a tool stay cached to server_tools, NULL disables caching
+ALTER TABLE server_tools ADD COLUMN cache_ttl INTEGER;
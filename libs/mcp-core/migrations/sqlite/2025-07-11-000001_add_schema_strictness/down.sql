@@ -0,0 +1,3 @@
+-- Remove the schema strictness columns
+ALTER TABLE client_policies DROP COLUMN schema_strictness;
+ALTER TABLE profiles DROP COLUMN schema_strictness;
@@ -0,0 +1,3 @@
+-- Add the optional normalization of the input schemas listed to a profile's or a client's clients
+ALTER TABLE profiles ADD COLUMN schema_strictness TEXT;
+ALTER TABLE client_policies ADD COLUMN schema_strictness TEXT;
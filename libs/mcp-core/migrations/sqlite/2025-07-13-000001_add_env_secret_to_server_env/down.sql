@@ -0,0 +1,2 @@
+-- Remove the secret flag from server_env, encrypted values stay encrypted
+ALTER TABLE server_env DROP COLUMN env_secret;
@@ -0,0 +1,11 @@
+-- Flag the environment values holding secrets, encrypted at rest
+ALTER TABLE server_env ADD COLUMN env_secret BOOLEAN NOT NULL DEFAULT FALSE;
+
+-- Existing variables named like secrets are flagged, their values are encrypted at startup
+UPDATE server_env SET env_secret = TRUE
+WHERE UPPER(env_key) LIKE '%\_KEY' ESCAPE '\'
+   OR UPPER(env_key) LIKE '%\_TOKEN' ESCAPE '\'
+   OR UPPER(env_key) LIKE '%\_SECRET' ESCAPE '\'
+   OR UPPER(env_key) LIKE '%\_PASSWORD' ESCAPE '\'
+   OR UPPER(env_key) LIKE '%\_CREDENTIALS' ESCAPE '\'
+   OR UPPER(env_key) IN ('KEY', 'TOKEN', 'SECRET', 'PASSWORD', 'CREDENTIALS');
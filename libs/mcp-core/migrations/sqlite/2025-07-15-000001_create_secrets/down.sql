@@ -0,0 +1,2 @@
+-- Drop the secrets table
+DROP TABLE secrets;
@@ -0,0 +1,7 @@
+-- Create the secrets table, the credential vault server env values reference as `${secret:name}`
+-- `value` is encrypted with the secret key
+CREATE TABLE secrets (
+    name TEXT PRIMARY KEY NOT NULL,
+    description TEXT NOT NULL DEFAULT '',
+    value TEXT NOT NULL
+);
@@ -0,0 +1,2 @@
+-- Key the client policies on the client name again
+ALTER TABLE client_policies RENAME COLUMN profile TO client_name;
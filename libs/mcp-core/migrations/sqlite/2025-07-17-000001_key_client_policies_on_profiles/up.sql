@@ -0,0 +1,3 @@
+-- Key the client policies on the profile of the connection instead of the client-chosen name.
+-- Policies of a client name only apply to connections using a profile of that name.
+ALTER TABLE client_policies RENAME COLUMN client_name TO profile;
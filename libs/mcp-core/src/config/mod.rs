@@ -1,10 +1,14 @@
+use std::collections::HashMap;
 use std::env;
+use std::path::PathBuf;
 
 /// Configuration for MCP tool names with namespace support
 #[derive(Debug, Clone)]
 pub struct ToolConfig {
     pub prefix: String,
     pub namespace_enabled: bool,
+    /// Base names of the built-in tools not offered to clients
+    pub disabled_tools: Vec<String>,
 }
 
 impl ToolConfig {
@@ -21,9 +25,12 @@ impl ToolConfig {
             String::new()
         };
 
+        let disabled_tools = env_list("DOCKMASTER_DISABLED_TOOLS");
+
         Self {
             prefix,
             namespace_enabled,
+            disabled_tools,
         }
     }
 
@@ -40,6 +47,11 @@ impl ToolConfig {
     pub fn is_namespace_enabled(&self) -> bool {
         self.namespace_enabled
     }
+
+    /// Whether the built-in tool with this base name is offered to clients
+    pub fn is_tool_enabled(&self, base_name: &str) -> bool {
+        !self.disabled_tools.iter().any(|name| name == base_name)
+    }
 }
 
 impl Default for ToolConfig {
@@ -47,10 +59,435 @@ impl Default for ToolConfig {
         Self {
             prefix: "dockmaster_".to_string(),
             namespace_enabled: true,
+            disabled_tools: Vec::new(),
+        }
+    }
+}
+
+/// Default number of tools returned per `tools/list` page
+pub const DEFAULT_TOOLS_PAGE_SIZE: usize = 100;
+
+/// Configuration for paginated list responses served to MCP clients
+#[derive(Debug, Clone)]
+pub struct PaginationConfig {
+    /// Maximum items per page, 0 disables pagination
+    pub page_size: usize,
+}
+
+impl PaginationConfig {
+    /// Create PaginationConfig from environment variables
+    pub fn from_env() -> Self {
+        let page_size = env::var("DOCKMASTER_TOOLS_PAGE_SIZE")
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_TOOLS_PAGE_SIZE);
+
+        Self { page_size }
+    }
+}
+
+impl Default for PaginationConfig {
+    fn default() -> Self {
+        Self {
+            page_size: DEFAULT_TOOLS_PAGE_SIZE,
+        }
+    }
+}
+
+/// Configuration for requests upstream servers send to the downstream clients
+#[derive(Debug, Clone)]
+pub struct ClientForwardingConfig {
+    /// Declare sampling and roots support to upstream servers and forward their requests.
+    /// Off by default, servers then see a client without these capabilities.
+    pub enabled: bool,
+    /// Name of the client (from its `clientInfo`) that receives requests not tied to a tool call
+    pub default_client: Option<String>,
+}
+
+impl ClientForwardingConfig {
+    /// Create ClientForwardingConfig from environment variables
+    pub fn from_env() -> Self {
+        let enabled = env::var("DOCKMASTER_FORWARD_CLIENT_REQUESTS")
+            .unwrap_or_else(|_| "disabled".to_string())
+            .to_lowercase()
+            == "enabled";
+
+        let default_client = env::var("DOCKMASTER_DEFAULT_CLIENT")
+            .ok()
+            .map(|name| name.trim().to_string())
+            .filter(|name| !name.is_empty());
+
+        Self {
+            enabled,
+            default_client,
+        }
+    }
+}
+
+impl Default for ClientForwardingConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            default_client: None,
+        }
+    }
+}
+
+/// Default time a tool call waits for the user to approve it
+pub const DEFAULT_APPROVAL_TIMEOUT_SECS: u64 = 120;
+
+/// Configuration for tool calls that client policies require the user to approve
+#[derive(Debug, Clone)]
+pub struct ApprovalConfig {
+    /// Seconds to wait for a decision before the call is denied
+    pub timeout_secs: u64,
+}
+
+impl ApprovalConfig {
+    /// Create ApprovalConfig from environment variables
+    pub fn from_env() -> Self {
+        let timeout_secs = env::var("DOCKMASTER_APPROVAL_TIMEOUT_SECS")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_APPROVAL_TIMEOUT_SECS);
+
+        Self { timeout_secs }
+    }
+}
+
+impl Default for ApprovalConfig {
+    fn default() -> Self {
+        Self {
+            timeout_secs: DEFAULT_APPROVAL_TIMEOUT_SECS,
+        }
+    }
+}
+
+/// Default number of tools `find_tools` returns
+pub const DEFAULT_FIND_TOOLS_LIMIT: usize = 10;
+
+/// Configuration of the discovery mode, in which clients find tools instead of listing them all
+#[derive(Debug, Clone)]
+pub struct DiscoveryConfig {
+    /// List only `find_tools`, `call_tool`, the pinned tools and the tools a session mounted
+    pub enabled: bool,
+    /// Names of the tools always listed in discovery mode
+    pub pinned_tools: Vec<String>,
+    /// Tools `find_tools` returns when the client does not ask for a number
+    pub max_results: usize,
+}
+
+impl DiscoveryConfig {
+    /// Create DiscoveryConfig from environment variables
+    pub fn from_env() -> Self {
+        let enabled = env::var("DOCKMASTER_DISCOVERY_MODE")
+            .unwrap_or_else(|_| "disabled".to_string())
+            .to_lowercase()
+            == "enabled";
+
+        let max_results = env::var("DOCKMASTER_FIND_TOOLS_LIMIT")
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .filter(|limit| *limit > 0)
+            .unwrap_or(DEFAULT_FIND_TOOLS_LIMIT);
+
+        Self {
+            enabled,
+            pinned_tools: env_list("DOCKMASTER_PINNED_TOOLS"),
+            max_results,
+        }
+    }
+}
+
+impl Default for DiscoveryConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            pinned_tools: Vec::new(),
+            max_results: DEFAULT_FIND_TOOLS_LIMIT,
+        }
+    }
+}
+
+/// Default number of tool results kept in the result cache
+pub const DEFAULT_RESULT_CACHE_MAX_ENTRIES: usize = 1000;
+
+/// Default total size of the tool results kept in the result cache
+pub const DEFAULT_RESULT_CACHE_MAX_BYTES: usize = 16 * 1024 * 1024;
+
+/// Limits of the cache of tool results, caching itself is enabled per tool
+#[derive(Debug, Clone)]
+pub struct ResultCacheConfig {
+    pub max_entries: usize,
+    /// Total size of the cached results as serialized JSON
+    pub max_bytes: usize,
+}
+
+impl ResultCacheConfig {
+    /// Create ResultCacheConfig from environment variables
+    pub fn from_env() -> Self {
+        let max_entries = env::var("DOCKMASTER_RESULT_CACHE_MAX_ENTRIES")
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_RESULT_CACHE_MAX_ENTRIES);
+
+        let max_bytes = env::var("DOCKMASTER_RESULT_CACHE_MAX_BYTES")
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_RESULT_CACHE_MAX_BYTES);
+
+        Self {
+            max_entries,
+            max_bytes,
+        }
+    }
+}
+
+impl Default for ResultCacheConfig {
+    fn default() -> Self {
+        Self {
+            max_entries: DEFAULT_RESULT_CACHE_MAX_ENTRIES,
+            max_bytes: DEFAULT_RESULT_CACHE_MAX_BYTES,
+        }
+    }
+}
+
+/// Default size of a tool result above which it is truncated and spilled to a resource
+pub const DEFAULT_MAX_OUTPUT_BYTES: usize = 1024 * 1024;
+
+/// Default size of the chunks spilled results are read in
+pub const DEFAULT_RESULT_CHUNK_BYTES: usize = 256 * 1024;
+
+/// Default total size of the spilled results kept in memory
+pub const DEFAULT_STORED_RESULTS_MAX_BYTES: usize = 64 * 1024 * 1024;
+
+/// Limits of the tool results returned to MCP clients
+#[derive(Debug, Clone)]
+pub struct OutputLimitConfig {
+    /// Size of a result as JSON above which it is truncated, 0 disables the limit
+    pub max_bytes: usize,
+    /// Server ID -> limit applied to its tools instead of `max_bytes`
+    pub server_max_bytes: HashMap<String, usize>,
+    /// Size of the chunks the full output of a truncated result is read in
+    pub chunk_bytes: usize,
+    /// Total size of the full outputs kept, the oldest are dropped first
+    pub stored_max_bytes: usize,
+}
+
+impl OutputLimitConfig {
+    /// Create OutputLimitConfig from environment variables
+    pub fn from_env() -> Self {
+        let max_bytes = env::var("DOCKMASTER_MAX_OUTPUT_BYTES")
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_MAX_OUTPUT_BYTES);
+
+        // SERVER_ID=BYTES pairs
+        let server_max_bytes = env_list("DOCKMASTER_SERVER_MAX_OUTPUT_BYTES")
+            .into_iter()
+            .filter_map(|limit| {
+                let (server_id, bytes) = limit.split_once('=')?;
+                Some((server_id.trim().to_string(), bytes.trim().parse().ok()?))
+            })
+            .collect();
+
+        let chunk_bytes = env::var("DOCKMASTER_RESULT_CHUNK_BYTES")
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .filter(|bytes| *bytes > 0)
+            .unwrap_or(DEFAULT_RESULT_CHUNK_BYTES);
+
+        let stored_max_bytes = env::var("DOCKMASTER_STORED_RESULTS_MAX_BYTES")
+            .ok()
+            .and_then(|value| value.trim().parse::<usize>().ok())
+            .unwrap_or(DEFAULT_STORED_RESULTS_MAX_BYTES);
+
+        Self {
+            max_bytes,
+            server_max_bytes,
+            chunk_bytes,
+            stored_max_bytes,
+        }
+    }
+
+    /// Limit of the results of a server's tools, `None` for built-in and composite tools
+    pub fn max_bytes_for(&self, server_id: Option<&str>) -> usize {
+        server_id
+            .and_then(|server_id| self.server_max_bytes.get(server_id))
+            .copied()
+            .unwrap_or(self.max_bytes)
+    }
+}
+
+impl Default for OutputLimitConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_MAX_OUTPUT_BYTES,
+            server_max_bytes: HashMap::new(),
+            chunk_bytes: DEFAULT_RESULT_CHUNK_BYTES,
+            stored_max_bytes: DEFAULT_STORED_RESULTS_MAX_BYTES,
+        }
+    }
+}
+
+/// Configuration of the checks applied to tool arguments before they are forwarded
+#[derive(Debug, Clone)]
+pub struct ArgumentValidationConfig {
+    /// Refuse calls whose arguments do not satisfy the tool's input schema
+    pub enabled: bool,
+    /// Convert strings to the number or boolean the schema declares, for loosely typed models
+    pub coerce: bool,
+}
+
+impl ArgumentValidationConfig {
+    /// Create ArgumentValidationConfig from environment variables
+    pub fn from_env() -> Self {
+        let enabled = env::var("DOCKMASTER_VALIDATE_ARGUMENTS")
+            .unwrap_or_else(|_| "enabled".to_string())
+            .to_lowercase()
+            != "disabled";
+
+        let coerce = env::var("DOCKMASTER_COERCE_ARGUMENTS")
+            .unwrap_or_else(|_| "disabled".to_string())
+            .to_lowercase()
+            == "enabled";
+
+        Self { enabled, coerce }
+    }
+}
+
+impl Default for ArgumentValidationConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            coerce: false,
+        }
+    }
+}
+
+/// Where the key encrypting secret environment values is held
+#[derive(Debug, Clone, PartialEq)]
+pub enum SecretKeySource {
+    /// A key file, `None` for `secret.key` next to the database
+    File(Option<PathBuf>),
+    /// The OS keyring: Keychain, Credential Manager or Secret Service
+    Keyring,
+}
+
+/// Configuration for the encryption of secret environment values
+#[derive(Debug, Clone)]
+pub struct SecretStorageConfig {
+    pub key_source: SecretKeySource,
+}
+
+impl SecretStorageConfig {
+    /// Create SecretStorageConfig from environment variables
+    pub fn from_env() -> Self {
+        let key_source = match env::var("DOCKMASTER_SECRET_KEY_SOURCE")
+            .unwrap_or_default()
+            .to_lowercase()
+            .as_str()
+        {
+            "keyring" => SecretKeySource::Keyring,
+            _ => SecretKeySource::File(
+                env::var("DOCKMASTER_SECRET_KEY_FILE")
+                    .ok()
+                    .map(PathBuf::from),
+            ),
+        };
+
+        Self { key_source }
+    }
+}
+
+impl Default for SecretStorageConfig {
+    fn default() -> Self {
+        Self {
+            key_source: SecretKeySource::File(None),
+        }
+    }
+}
+
+/// Configuration of the bearer token required by the HTTP server on localhost
+#[derive(Debug, Clone)]
+pub struct HttpAuthConfig {
+    /// Requests without the token are rejected
+    pub enabled: bool,
+    /// Discovery file holding the address and the token, `None` for `discovery.json` in the
+    /// default storage path
+    pub discovery_file: Option<PathBuf>,
+}
+
+impl HttpAuthConfig {
+    /// Create HttpAuthConfig from environment variables
+    pub fn from_env() -> Self {
+        let enabled = !matches!(
+            env::var("DOCKMASTER_HTTP_AUTH")
+                .unwrap_or_default()
+                .to_lowercase()
+                .as_str(),
+            "disabled" | "false" | "off" | "0"
+        );
+        let discovery_file = env::var("DOCKMASTER_DISCOVERY_FILE")
+            .ok()
+            .map(PathBuf::from);
+
+        Self {
+            enabled,
+            discovery_file,
+        }
+    }
+}
+
+impl Default for HttpAuthConfig {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            discovery_file: None,
+        }
+    }
+}
+
+/// Default size of a server log above which it is rotated
+pub const DEFAULT_SERVER_LOG_MAX_BYTES: u64 = 1024 * 1024;
+
+/// Configuration of the per-server log files
+#[derive(Debug, Clone)]
+pub struct ServerLogConfig {
+    /// Size above which a log is moved aside to `<server>.log.1`, 0 disables rotation
+    pub max_bytes: u64,
+}
+
+impl ServerLogConfig {
+    /// Create ServerLogConfig from environment variables
+    pub fn from_env() -> Self {
+        let max_bytes = env::var("DOCKMASTER_SERVER_LOG_MAX_BYTES")
+            .ok()
+            .and_then(|value| value.trim().parse::<u64>().ok())
+            .unwrap_or(DEFAULT_SERVER_LOG_MAX_BYTES);
+
+        Self { max_bytes }
+    }
+}
+
+impl Default for ServerLogConfig {
+    fn default() -> Self {
+        Self {
+            max_bytes: DEFAULT_SERVER_LOG_MAX_BYTES,
         }
     }
 }
 
+/// Comma separated values of an environment variable, without blanks
+fn env_list(name: &str) -> Vec<String> {
+    env::var(name)
+        .unwrap_or_default()
+        .split(',')
+        .map(|value| value.trim().to_string())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -79,4 +516,123 @@ mod tests {
         assert!(!config.is_namespace_enabled());
         env::remove_var("DOCKMASTER_NAMESPACE_MODE");
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_disabled_tools() {
+        env::set_var("DOCKMASTER_DISABLED_TOOLS", "restart_server, server_logs,");
+        let config = ToolConfig::from_env();
+        assert_eq!(config.disabled_tools, vec!["restart_server", "server_logs"]);
+        assert!(!config.is_tool_enabled("restart_server"));
+        assert!(config.is_tool_enabled("server_status"));
+        env::remove_var("DOCKMASTER_DISABLED_TOOLS");
+    }
+
+    #[test]
+    fn test_page_size_from_env() {
+        env::set_var("DOCKMASTER_TOOLS_PAGE_SIZE", "25");
+        assert_eq!(PaginationConfig::from_env().page_size, 25);
+        env::set_var("DOCKMASTER_TOOLS_PAGE_SIZE", "not-a-number");
+        assert_eq!(PaginationConfig::from_env().page_size, DEFAULT_TOOLS_PAGE_SIZE);
+        env::remove_var("DOCKMASTER_TOOLS_PAGE_SIZE");
+    }
+
+    #[test]
+    fn test_client_forwarding_from_env() {
+        env::set_var("DOCKMASTER_FORWARD_CLIENT_REQUESTS", "enabled");
+        env::set_var("DOCKMASTER_DEFAULT_CLIENT", "cursor");
+        let config = ClientForwardingConfig::from_env();
+        assert!(config.enabled);
+        assert_eq!(config.default_client.as_deref(), Some("cursor"));
+        env::remove_var("DOCKMASTER_FORWARD_CLIENT_REQUESTS");
+        env::remove_var("DOCKMASTER_DEFAULT_CLIENT");
+
+        let config = ClientForwardingConfig::from_env();
+        assert!(!config.enabled);
+        assert_eq!(config.default_client, None);
+    }
+
+    #[test]
+    fn test_approval_timeout_from_env() {
+        env::set_var("DOCKMASTER_APPROVAL_TIMEOUT_SECS", "30");
+        assert_eq!(ApprovalConfig::from_env().timeout_secs, 30);
+        env::remove_var("DOCKMASTER_APPROVAL_TIMEOUT_SECS");
+        assert_eq!(
+            ApprovalConfig::from_env().timeout_secs,
+            DEFAULT_APPROVAL_TIMEOUT_SECS
+        );
+    }
+
+    #[test]
+    fn test_discovery_from_env() {
+        let config = DiscoveryConfig::from_env();
+        assert!(!config.enabled);
+        assert_eq!(config.max_results, DEFAULT_FIND_TOOLS_LIMIT);
+
+        env::set_var("DOCKMASTER_DISCOVERY_MODE", "enabled");
+        env::set_var("DOCKMASTER_PINNED_TOOLS", "read_file, search");
+        env::set_var("DOCKMASTER_FIND_TOOLS_LIMIT", "5");
+        let config = DiscoveryConfig::from_env();
+        assert!(config.enabled);
+        assert_eq!(config.pinned_tools, vec!["read_file", "search"]);
+        assert_eq!(config.max_results, 5);
+        env::remove_var("DOCKMASTER_DISCOVERY_MODE");
+        env::remove_var("DOCKMASTER_PINNED_TOOLS");
+        env::remove_var("DOCKMASTER_FIND_TOOLS_LIMIT");
+    }
+
+    #[test]
+    fn test_result_cache_from_env() {
+        env::set_var("DOCKMASTER_RESULT_CACHE_MAX_ENTRIES", "10");
+        env::set_var("DOCKMASTER_RESULT_CACHE_MAX_BYTES", "invalid");
+        let config = ResultCacheConfig::from_env();
+        assert_eq!(config.max_entries, 10);
+        assert_eq!(config.max_bytes, DEFAULT_RESULT_CACHE_MAX_BYTES);
+        env::remove_var("DOCKMASTER_RESULT_CACHE_MAX_ENTRIES");
+        env::remove_var("DOCKMASTER_RESULT_CACHE_MAX_BYTES");
+    }
+
+    #[test]
+    fn test_output_limits_from_env() {
+        env::set_var("DOCKMASTER_MAX_OUTPUT_BYTES", "1000");
+        env::set_var("DOCKMASTER_SERVER_MAX_OUTPUT_BYTES", "files=5000, github=invalid");
+        let config = OutputLimitConfig::from_env();
+        assert_eq!(config.max_bytes_for(None), 1000);
+        assert_eq!(config.max_bytes_for(Some("github")), 1000);
+        assert_eq!(config.max_bytes_for(Some("files")), 5000);
+        assert_eq!(config.chunk_bytes, DEFAULT_RESULT_CHUNK_BYTES);
+        assert_eq!(config.stored_max_bytes, DEFAULT_STORED_RESULTS_MAX_BYTES);
+        env::remove_var("DOCKMASTER_MAX_OUTPUT_BYTES");
+        env::remove_var("DOCKMASTER_SERVER_MAX_OUTPUT_BYTES");
+    }
+
+    #[test]
+    fn test_secret_key_source_from_env() {
+        env::set_var("DOCKMASTER_SECRET_KEY_FILE", "/tmp/dockmaster.key");
+        assert_eq!(
+            SecretStorageConfig::from_env().key_source,
+            SecretKeySource::File(Some(PathBuf::from("/tmp/dockmaster.key")))
+        );
+        env::set_var("DOCKMASTER_SECRET_KEY_SOURCE", "keyring");
+        assert_eq!(
+            SecretStorageConfig::from_env().key_source,
+            SecretKeySource::Keyring
+        );
+        env::remove_var("DOCKMASTER_SECRET_KEY_SOURCE");
+        env::remove_var("DOCKMASTER_SECRET_KEY_FILE");
+    }
+
+    #[test]
+    fn test_http_auth_from_env() {
+        assert!(HttpAuthConfig::from_env().enabled);
+        env::set_var("DOCKMASTER_HTTP_AUTH", "disabled");
+        env::set_var("DOCKMASTER_DISCOVERY_FILE", "/tmp/discovery.json");
+        let config = HttpAuthConfig::from_env();
+        assert!(!config.enabled);
+        assert_eq!(
+            config.discovery_file,
+            Some(PathBuf::from("/tmp/discovery.json"))
+        );
+        env::remove_var("DOCKMASTER_HTTP_AUTH");
+        env::remove_var("DOCKMASTER_DISCOVERY_FILE");
+    }
+}
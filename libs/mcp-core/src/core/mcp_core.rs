@@ -1,15 +1,22 @@
-use std::{collections::HashMap, path::PathBuf, sync::Arc, time::Duration};
+use std::{collections::HashMap, net::SocketAddr, path::PathBuf, sync::Arc, time::Duration};
 
-use axum::Router;
+use axum::{middleware, Router};
 use log::{error, info, warn};
 use rmcp::transport::sse_server::SseServerConfig;
 use tokio::sync::RwLock;
 use tokio_util::sync::CancellationToken;
 
+use crate::config::HttpAuthConfig;
 use crate::core::mcp_core_database_ext::McpCoreDatabaseExt;
 use crate::core::mcp_core_proxy_ext::McpCoreProxyExt;
 use crate::database::db_manager::DBManager;
+use crate::mcp_server_implementation::http_auth::{
+    discovery_file_path, load_or_create_token, require_token, write_discovery, Discovery,
+    HttpAuth,
+};
+use crate::mcp_installers::ProxyAuth;
 use crate::mcp_server_implementation::mcp_server::McpServer;
+use crate::mcp_server_implementation::profile_routes::profile_router;
 use crate::registry::server_registry::ServerRegistry;
 
 use crate::mcp_state::mcp_state::MCPState;
@@ -43,6 +50,8 @@ pub struct MCPCore {
     pub app_name: String,
     /// SSE server cancellation token
     pub sse_server_cancel_token: CancellationToken,
+    /// Token required by the HTTP server
+    pub http_auth: HttpAuthConfig,
 }
 
 impl MCPCore {
@@ -101,9 +110,31 @@ impl MCPCore {
             port,
             app_name,
             sse_server_cancel_token: CancellationToken::new(),
+            http_auth: HttpAuthConfig::from_env(),
         }
     }
 
+    /// Token the HTTP server requires, generated on first use, `None` when it requires none
+    pub fn auth_token(&self) -> Result<Option<String>, String> {
+        if !self.http_auth.enabled {
+            return Ok(None);
+        }
+        load_or_create_token(&discovery_file_path(&self.http_auth)?).map(Some)
+    }
+
+    /// Discovery file the proxy server is installed with, `None` when the HTTP server requires
+    /// no token.
+    ///
+    /// The token is created first, so the proxy finds it in the file before the server starts.
+    pub fn proxy_auth(&self) -> Result<Option<ProxyAuth>, String> {
+        if self.auth_token()?.is_none() {
+            return Ok(None);
+        }
+        Ok(Some(ProxyAuth {
+            discovery_file: discovery_file_path(&self.http_auth)?,
+        }))
+    }
+
     /// Initializes the MCP server by starting the HTTP server and background services
     ///
     /// This function starts:
@@ -148,19 +179,9 @@ impl MCPCore {
             .map_err(|e| InitError::InitMcpServer(e.to_string()))?;
         info!("Server started on {}", actual_addr);
         
-        // Now create SSE server with the actual bound address
-        let (sse_server, router) = SseServer::new(SseServerConfig {
-            bind: actual_addr,
-            sse_path: "/sse".to_string(),
-            post_path: "/post".to_string(),
-            ct: self.sse_server_cancel_token.clone(),
-            sse_keep_alive: Some(Duration::from_secs(30)),
-        });
-
-        let mcp_core = Arc::new(self.clone());
-        sse_server.with_service(move || McpServer::new(mcp_core.clone()));
-
-        let mcp_http_router = Router::new().merge(router);
+        let mcp_http_router = self
+            .http_router(actual_addr)
+            .map_err(InitError::StartHttpServer)?;
         let cancellation_token = self.sse_server_cancel_token.clone();
         let mcp_http_server =
             axum::serve(listener, mcp_http_router).with_graceful_shutdown(async move {
@@ -176,6 +197,42 @@ impl MCPCore {
         Ok(())
     }
 
+    /// Routes of the HTTP server listening on `addr`: the SSE endpoint and the profile ones.
+    ///
+    /// Unless authentication is disabled, the routes require the token of the install and the
+    /// discovery file is written with `addr` so local clients find the server.
+    pub fn http_router(&self, addr: SocketAddr) -> Result<Router, String> {
+        let (sse_server, router) = SseServer::new(SseServerConfig {
+            bind: addr,
+            sse_path: "/sse".to_string(),
+            post_path: "/post".to_string(),
+            ct: self.sse_server_cancel_token.clone(),
+            sse_keep_alive: Some(Duration::from_secs(30)),
+        });
+
+        let mcp_core = Arc::new(self.clone());
+        let profiles_router =
+            profile_router(mcp_core.clone(), addr, self.sse_server_cancel_token.clone());
+        sse_server.with_service(move || McpServer::new(mcp_core.clone()));
+
+        let mcp_http_router = Router::new().merge(router).merge(profiles_router);
+        let Some(token) = self.auth_token()? else {
+            warn!("HTTP server authentication is disabled");
+            return Ok(mcp_http_router);
+        };
+        let discovery = Discovery {
+            url: format!("http://{addr}"),
+            token: token.clone(),
+        };
+        let discovery_file = discovery_file_path(&self.http_auth)?;
+        write_discovery(&discovery_file, &discovery)?;
+        info!("Discovery file written to {}", discovery_file.display());
+        Ok(mcp_http_router.layer(middleware::from_fn_with_state(
+            HttpAuth::new(token),
+            require_token,
+        )))
+    }
+
     pub async fn uninit(&self) {
         self.sse_server_cancel_token.cancel();
         // Kill all MCP Server processes
@@ -0,0 +1,245 @@
+use std::collections::HashSet;
+
+use async_trait::async_trait;
+use log::info;
+use rmcp::model::{CallToolResult, Content};
+use serde_json::{json, Map, Value};
+
+use crate::mcp_server_implementation::notifications::broadcast_tools_list_changed;
+use crate::mcp_server_implementation::tools::get_tool_names;
+use crate::models::types::{CompositeStep, CompositeTool, ToolExecutionRequest, ToolTarget};
+use crate::utils::json_template::{render_template, select_items, validate_template};
+use crate::utils::validate_name;
+
+use super::mcp_core::MCPCore;
+use super::mcp_core_proxy_ext::McpCoreProxyExt;
+
+/// User-defined tools that chain calls of upstream tools
+#[async_trait]
+pub trait McpCoreCompositeExt {
+    async fn list_composite_tools(&self) -> Result<Vec<CompositeTool>, String>;
+    async fn get_composite_tool(&self, name: &str) -> Result<CompositeTool, String>;
+    /// Create or replace a composite tool
+    async fn save_composite_tool(&self, tool: CompositeTool) -> Result<(), String>;
+    async fn delete_composite_tool(&self, name: &str) -> Result<(), String>;
+    /// Run the steps of a composite tool, a failing step ends the run with an error result
+    async fn execute_composite_tool(
+        &self,
+        name: &str,
+        arguments: Option<Map<String, Value>>,
+    ) -> Result<CallToolResult, String>;
+}
+
+#[async_trait]
+impl McpCoreCompositeExt for MCPCore {
+    async fn list_composite_tools(&self) -> Result<Vec<CompositeTool>, String> {
+        self.tool_registry.read().await.get_composite_tools()
+    }
+
+    async fn get_composite_tool(&self, name: &str) -> Result<CompositeTool, String> {
+        self.tool_registry
+            .read()
+            .await
+            .get_composite_tool(name)
+            .map_err(|_| format!("Composite tool '{name}' not found"))
+    }
+
+    async fn save_composite_tool(&self, tool: CompositeTool) -> Result<(), String> {
+        validate_name("tool", &tool.name)?;
+        if get_tool_names().contains(&tool.name) {
+            return Err(format!("'{}' is the name of a built-in tool", tool.name));
+        }
+        validate_steps(&tool)?;
+        {
+            let registry = self.tool_registry.read().await;
+            let servers = registry.get_all_servers()?;
+            // Stopped servers and disabled tools take their names back later, so every stored
+            // tool counts
+            for server_id in servers.keys() {
+                let clash = registry
+                    .get_server_tools(server_id)?
+                    .into_iter()
+                    .find(|stored| stored.exposed_name() == tool.name);
+                if let Some(stored) = clash {
+                    return Err(format!(
+                        "Tool name '{}' is already used by tool '{}' of server {}",
+                        tool.name, stored.name, server_id
+                    ));
+                }
+            }
+            let unknown = tool
+                .steps
+                .iter()
+                .find(|step| !servers.contains_key(&step.server_id));
+            if let Some(step) = unknown {
+                return Err(format!("Server '{}' not found", step.server_id));
+            }
+            registry.save_composite_tool(&tool)?;
+        }
+        info!("Saved composite tool '{}'", tool.name);
+
+        broadcast_tools_list_changed().await;
+        Ok(())
+    }
+
+    async fn delete_composite_tool(&self, name: &str) -> Result<(), String> {
+        self.tool_registry.read().await.delete_composite_tool(name)?;
+        info!("Deleted composite tool '{name}'");
+        broadcast_tools_list_changed().await;
+        Ok(())
+    }
+
+    async fn execute_composite_tool(
+        &self,
+        name: &str,
+        arguments: Option<Map<String, Value>>,
+    ) -> Result<CallToolResult, String> {
+        let tool = self.get_composite_tool(name).await?;
+        let (text, is_error) = match run_pipeline(self, &tool, arguments).await {
+            Ok(Value::String(output)) => (output, false),
+            Ok(output) => (
+                serde_json::to_string_pretty(&output)
+                    .map_err(|e| format!("Failed to serialize composite tool output: {e}"))?,
+                false,
+            ),
+            Err(e) => (e, true),
+        };
+        Ok(CallToolResult {
+            content: vec![Content::text(text)],
+            is_error: Some(is_error),
+        })
+    }
+}
+
+/// Steps need distinct ids and valid JSONPath expressions
+fn validate_steps(tool: &CompositeTool) -> Result<(), String> {
+    if tool.steps.is_empty() {
+        return Err("A composite tool needs at least one step".to_string());
+    }
+    let mut ids = HashSet::new();
+    for step in &tool.steps {
+        if step.id.is_empty() || !ids.insert(step.id.as_str()) {
+            return Err(format!("Step id '{}' is empty or used twice", step.id));
+        }
+        validate_template(&Value::Object(step.arguments.clone()))
+            .and_then(|_| match &step.for_each {
+                Some(path) => validate_template(&Value::String(path.clone())),
+                None => Ok(()),
+            })
+            .map_err(|e| format!("Step '{}': {e}", step.id))?;
+    }
+    match &tool.output {
+        Some(output) => validate_template(output).map_err(|e| format!("Output: {e}")),
+        None => Ok(()),
+    }
+}
+
+/// Run the steps in order and render the output from their results
+async fn run_pipeline(
+    mcp_core: &MCPCore,
+    tool: &CompositeTool,
+    arguments: Option<Map<String, Value>>,
+) -> Result<Value, String> {
+    let mut context = json!({
+        "input": arguments.unwrap_or_default(),
+        "steps": {},
+    });
+    let mut last_result = Value::Null;
+
+    for step in &tool.steps {
+        info!("Composite tool '{}': running step '{}'", tool.name, step.id);
+        let target = step_target(mcp_core, step).await?;
+        let result = match &step.for_each {
+            Some(path) => {
+                let items = select_items(path, &context)
+                    .map_err(|e| format!("Step '{}': {e}", step.id))?;
+                let mut results = Vec::with_capacity(items.len());
+                for item in items {
+                    context["item"] = item;
+                    results.push(call_step(mcp_core, step, &target, &context).await?);
+                }
+                context
+                    .as_object_mut()
+                    .and_then(|context| context.remove("item"));
+                Value::Array(results)
+            }
+            None => call_step(mcp_core, step, &target, &context).await?,
+        };
+        context["steps"][&step.id] = result.clone();
+        last_result = result;
+    }
+
+    match &tool.output {
+        Some(output) => render_template(output, &context).map_err(|e| format!("Output: {e}")),
+        None => Ok(last_result),
+    }
+}
+
+/// Server and upstream name of the tool a step calls
+async fn step_target(mcp_core: &MCPCore, step: &CompositeStep) -> Result<ToolTarget, String> {
+    let mcp_state = mcp_core.mcp_state.read().await;
+    let server_tools = mcp_state.server_tools.read().await;
+    server_tools
+        .get(&step.server_id)
+        .and_then(|tools| tools.iter().find(|tool| tool.matches_id(&step.tool_id)))
+        .map(|tool| ToolTarget {
+            server_id: tool.server_id.clone(),
+            tool_name: tool.name.clone(),
+        })
+        .ok_or_else(|| {
+            format!(
+                "Step '{}': tool '{}' not found on server {}",
+                step.id, step.tool_id, step.server_id
+            )
+        })
+}
+
+/// Call the tool of a step with its rendered arguments and return the result as JSON
+async fn call_step(
+    mcp_core: &MCPCore,
+    step: &CompositeStep,
+    target: &ToolTarget,
+    context: &Value,
+) -> Result<Value, String> {
+    let parameters = match render_template(&Value::Object(step.arguments.clone()), context) {
+        Ok(Value::Object(parameters)) => parameters,
+        Ok(_) => Map::new(),
+        Err(e) => return Err(format!("Step '{}': {e}", step.id)),
+    };
+    let response = mcp_core
+        .execute_proxy_tool(ToolExecutionRequest {
+            target: target.clone(),
+            parameters: Some(parameters),
+            progress_token: None,
+        })
+        .await
+        .map_err(|e| format!("Step '{}': {e}", step.id))?;
+    let result: CallToolResult = serde_json::from_value(response.result.unwrap_or_default())
+        .map_err(|e| format!("Step '{}': invalid tool result: {e}", step.id))?;
+
+    let value = tool_result_value(&result);
+    if result.is_error == Some(true) {
+        return Err(format!("Step '{}' failed: {value}", step.id));
+    }
+    Ok(value)
+}
+
+/// JSON value of a tool result that later steps can select from.
+///
+/// Text content holding JSON is parsed, other text is kept as a string and non-text content
+/// as its MCP representation. A single content is the value itself, several become a list.
+pub fn tool_result_value(result: &CallToolResult) -> Value {
+    let mut values: Vec<Value> = result
+        .content
+        .iter()
+        .map(|content| match content.as_text() {
+            Some(text) => serde_json::from_str(&text.text)
+                .unwrap_or_else(|_| Value::String(text.text.clone())),
+            None => serde_json::to_value(content).unwrap_or_default(),
+        })
+        .collect();
+    match values.len() {
+        1 => values.remove(0),
+        _ => Value::Array(values),
+    }
+}
@@ -9,9 +9,11 @@ use crate::{
 
 pub trait McpCoreInstallersExt {
     fn is_claude_installed(&self) -> Result<bool, String>;
-    fn install_claude(&self) -> Result<(), String>;
+    /// Add the proxy to Claude's config, serving `profile` when given
+    fn install_claude(&self, profile: Option<&str>) -> Result<(), String>;
     fn is_cursor_installed(&self) -> Result<bool, String>;
-    fn install_cursor(&self) -> Result<(), String>;
+    /// Add the proxy to Cursor's config, serving `profile` when given
+    fn install_cursor(&self, profile: Option<&str>) -> Result<(), String>;
     fn get_claude_config(&self) -> Result<String, String>;
     fn get_cursor_config(&self) -> Result<String, String>;
     fn get_generic_config(&self) -> Result<String, String>;
@@ -33,11 +35,17 @@ impl McpCoreInstallersExt for MCPCore {
             Err(err) => Err(err.to_string()),
         }
     }
-    fn install_claude(&self) -> Result<(), String> {
+    fn install_claude(&self, profile: Option<&str>) -> Result<(), String> {
         let Some(proxy_server_binary_path) = self.proxy_server_binary_path.to_str() else {
             return Err("failed to convert path to string".to_string());
         };
-        match install_claude(&self.app_name, proxy_server_binary_path) {
+        let auth = self.proxy_auth()?;
+        match install_claude(
+            &self.app_name,
+            proxy_server_binary_path,
+            profile,
+            auth.as_ref(),
+        ) {
             Ok(_) => Ok(()),
             Err(err) => Err(err.to_string()),
         }
@@ -48,11 +56,17 @@ impl McpCoreInstallersExt for MCPCore {
             Err(err) => Err(err.to_string()),
         }
     }
-    fn install_cursor(&self) -> Result<(), String> {
+    fn install_cursor(&self, profile: Option<&str>) -> Result<(), String> {
         let Some(proxy_server_binary_path) = self.proxy_server_binary_path.to_str() else {
             return Err("failed to convert path to string".to_string());
         };
-        match install_cursor(&self.app_name, proxy_server_binary_path) {
+        let auth = self.proxy_auth()?;
+        match install_cursor(
+            &self.app_name,
+            proxy_server_binary_path,
+            profile,
+            auth.as_ref(),
+        ) {
             Ok(_) => Ok(()),
             Err(err) => Err(err.to_string()),
         }
@@ -61,7 +75,8 @@ impl McpCoreInstallersExt for MCPCore {
         let Some(proxy_server_binary_path) = self.proxy_server_binary_path.to_str() else {
             return Err("failed to convert path to string".to_string());
         };
-        match get_claude_config(&self.app_name, proxy_server_binary_path) {
+        let auth = self.proxy_auth()?;
+        match get_claude_config(&self.app_name, proxy_server_binary_path, auth.as_ref()) {
             Ok(config) => Ok(config),
             Err(err) => Err(err.to_string()),
         }
@@ -70,7 +85,8 @@ impl McpCoreInstallersExt for MCPCore {
         let Some(proxy_server_binary_path) = self.proxy_server_binary_path.to_str() else {
             return Err("failed to convert path to string".to_string());
         };
-        match get_cursor_config(&self.app_name, proxy_server_binary_path) {
+        let auth = self.proxy_auth()?;
+        match get_cursor_config(&self.app_name, proxy_server_binary_path, auth.as_ref()) {
             Ok(config) => Ok(config),
             Err(err) => Err(err.to_string()),
         }
@@ -80,6 +96,7 @@ impl McpCoreInstallersExt for MCPCore {
         let Some(proxy_server_binary_path) = self.proxy_server_binary_path.to_str() else {
             return Err("failed to convert path to string".to_string());
         };
-        Ok(get_generic_config(proxy_server_binary_path))
+        let auth = self.proxy_auth()?;
+        Ok(get_generic_config(proxy_server_binary_path, auth.as_ref()))
     }
 }
@@ -0,0 +1,62 @@
+use async_trait::async_trait;
+use log::info;
+
+use crate::mcp_server_implementation::notifications::broadcast_tools_list_changed;
+use crate::models::types::Profile;
+use crate::utils::validate_name;
+
+use super::mcp_core::MCPCore;
+
+/// Named subsets of the servers and tools, each served at `/sse/{name}`
+#[async_trait]
+pub trait McpCoreProfilesExt {
+    async fn list_profiles(&self) -> Result<Vec<Profile>, String>;
+    async fn get_profile(&self, name: &str) -> Result<Profile, String>;
+    /// Create or replace a profile
+    async fn save_profile(&self, profile: Profile) -> Result<(), String>;
+    async fn delete_profile(&self, name: &str) -> Result<(), String>;
+}
+
+#[async_trait]
+impl McpCoreProfilesExt for MCPCore {
+    async fn list_profiles(&self) -> Result<Vec<Profile>, String> {
+        self.tool_registry.read().await.get_profiles()
+    }
+
+    async fn get_profile(&self, name: &str) -> Result<Profile, String> {
+        self.tool_registry
+            .read()
+            .await
+            .get_profile(name)
+            .map_err(|_| format!("Profile '{name}' not found"))
+    }
+
+    async fn save_profile(&self, profile: Profile) -> Result<(), String> {
+        validate_name("profile", &profile.name)?;
+        {
+            let registry = self.tool_registry.read().await;
+            let servers = registry.get_all_servers()?;
+            let unknown = profile
+                .servers
+                .iter()
+                .chain(profile.tools.iter().map(|tool| &tool.server_id))
+                .find(|server_id| !servers.contains_key(*server_id));
+            if let Some(server_id) = unknown {
+                return Err(format!("Server '{server_id}' not found"));
+            }
+            registry.save_profile(&profile)?;
+        }
+        info!("Saved profile '{}'", profile.name);
+
+        // Sessions of the profile see a different tool list
+        broadcast_tools_list_changed().await;
+        Ok(())
+    }
+
+    async fn delete_profile(&self, name: &str) -> Result<(), String> {
+        self.tool_registry.read().await.delete_profile(name)?;
+        info!("Deleted profile '{name}'");
+        broadcast_tools_list_changed().await;
+        Ok(())
+    }
+}
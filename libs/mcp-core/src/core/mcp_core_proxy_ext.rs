@@ -1,11 +1,15 @@
+use crate::config::ArgumentValidationConfig;
 use crate::models::types::{
-    CustomServerRegistrationRequest, DiscoverServerToolsRequest, Distribution, RuntimeServer, 
+    ArgumentPreset, CustomServerRegistrationRequest, DiscoverServerToolsRequest, Distribution, RuntimeServer, 
     ServerConfigUpdateRequest, ServerConfiguration, ServerDefinition, ServerEnvironment, ServerId, 
     ServerRegistrationRequest, ServerRegistrationResponse, ServerStatus, ServerUninstallResponse, 
     ServerUpdateRequest, ToolConfigUpdateResponse, ToolExecutionRequest, ToolExecutionResponse, 
-    ToolUninstallRequest, ToolUpdateResponse,
+    ResultCacheStats, SchemaStrictness, ToolOverrides, ToolSize, ToolUninstallRequest, ToolUpdateResponse,
 };
 use crate::types::ServerToolInfo;
+use crate::utils::argument_validation::format_violations;
+use crate::utils::redaction::{redact_server, REDACTED};
+use crate::utils::secrets::is_secret_key;
 use crate::utils::github::{
     analyze_env_var_context, extract_env_vars_from_readme, fetch_github_file, parse_github_url, GitHubRepo,
 };
@@ -27,16 +31,23 @@ pub trait McpCoreProxyExt {
         &self,
         tool: ServerRegistrationRequest,
     ) -> Result<ServerRegistrationResponse, String>;
+    /// Installed servers, their secret environment values are masked
     async fn list_servers(&self) -> Result<Vec<RuntimeServer>, String>;
     async fn list_all_server_tools(&self) -> Result<Vec<ServerToolInfo>, String>;
     async fn list_server_tools(
         &self,
         request: DiscoverServerToolsRequest,
     ) -> Result<Vec<ServerToolInfo>, String>;
+    /// Execute a tool of a server after applying its presets to the arguments and validating them
     async fn execute_proxy_tool(
         &self,
         request: ToolExecutionRequest,
     ) -> Result<ToolExecutionResponse, String>;
+    /// Execute a tool whose arguments already went through `ServerToolInfo::prepare_arguments`
+    async fn execute_prepared_proxy_tool(
+        &self,
+        request: ToolExecutionRequest,
+    ) -> Result<ToolExecutionResponse, String>;
     async fn update_server_status(
         &self,
         request: ServerUpdateRequest,
@@ -50,6 +61,36 @@ pub trait McpCoreProxyExt {
         request: ToolUninstallRequest,
     ) -> Result<ServerUninstallResponse, String>;
     async fn restart_server_command(&self, tool_id: String) -> Result<ToolUpdateResponse, String>;
+    /// Enable or disable a single tool of a server
+    async fn set_tool_active(
+        &self,
+        server_id: String,
+        tool_id: String,
+        active: bool,
+    ) -> Result<ToolUpdateResponse, String>;
+    /// Expose a tool under another name or descriptions, `None` restores the upstream ones
+    async fn set_tool_overrides(
+        &self,
+        server_id: String,
+        tool_id: String,
+        overrides: Option<ToolOverrides>,
+    ) -> Result<ToolUpdateResponse, String>;
+    /// Replace the argument presets of a tool, an empty map removes them
+    async fn set_tool_argument_presets(
+        &self,
+        server_id: String,
+        tool_id: String,
+        presets: HashMap<String, ArgumentPreset>,
+    ) -> Result<ToolUpdateResponse, String>;
+    /// Cache the results of a tool for `ttl` seconds, `None` disables caching
+    async fn set_tool_cache_ttl(
+        &self,
+        server_id: String,
+        tool_id: String,
+        ttl: Option<u64>,
+    ) -> Result<ToolUpdateResponse, String>;
+    async fn result_cache_stats(&self) -> ResultCacheStats;
+    async fn clear_result_cache(&self);
     async fn init_mcp_server(&self) -> Result<()>;
     async fn kill_all_processes(&self) -> Result<()>;
     /// Import a server from a GitHub repository URL
@@ -166,7 +207,9 @@ impl McpCoreProxyExt for MCPCore {
         let tool_map = registry.get_all_servers()?;
         let mut tools = Vec::new();
 
-        for (id, tool_struct) in tool_map {
+        for (id, mut tool_struct) in tool_map {
+            // The list reaches the UI and MCP clients, secrets stay in the process
+            redact_server(&mut tool_struct);
             let status = {
                 let mcp_clients = mcp_state.mcp_clients.read().await;
                 if let Some(mcp_client) = mcp_clients.get(&id) {
@@ -176,9 +219,17 @@ impl McpCoreProxyExt for MCPCore {
                 }
             };
 
-            let tool_count = {
+            let (tool_count, tool_size) = {
                 let server_tools = mcp_state.server_tools.read().await;
-                server_tools.get(&id).map_or(0, |tools| tools.len())
+                server_tools.get(&id).map_or((0, ToolSize::default()), |tools| {
+                    let tool_size = tools
+                        .iter()
+                        .filter(|tool| tool.is_active)
+                        .filter_map(|tool| tool.clone().to_tool(SchemaStrictness::Off).ok())
+                        .map(|tool| ToolSize::of(&tool))
+                        .sum();
+                    (tools.len(), tool_size)
+                })
             };
 
             tools.push(RuntimeServer {
@@ -186,6 +237,7 @@ impl McpCoreProxyExt for MCPCore {
                 id: ServerId::new(id),
                 status,
                 tool_count,
+                tool_size,
             });
         }
 
@@ -202,11 +254,10 @@ impl McpCoreProxyExt for MCPCore {
             return Ok(Vec::new());
         }
 
-        let server_tools = mcp_state.server_tools.read().await;
-        let mut all_tools = Vec::new();
-
-        for tools in (*server_tools).values() {
-            all_tools.extend(tools.iter().cloned());
+        // A name exposed by several servers is listed once, for the server it is routed to
+        let mut all_tools = mcp_state.routed_tools().await;
+        for tool in &mut all_tools {
+            tool.size = tool.clone().to_tool(SchemaStrictness::Off).ok().map(|tool| ToolSize::of(&tool));
         }
         Ok(all_tools)
     }
@@ -223,47 +274,112 @@ impl McpCoreProxyExt for MCPCore {
     /// Execute a tool from an MCP server
     async fn execute_proxy_tool(
         &self,
-        request: ToolExecutionRequest,
+        mut request: ToolExecutionRequest,
     ) -> Result<ToolExecutionResponse, String> {
-        let mcp_state = self.mcp_state.read().await;
-        // Extract server_id and tool_id from the proxy_id
-        let parts: Vec<&str> = request.tool_id.split(':').collect();
-        println!("parts: {:?}", parts);
-        if parts.len() != 2 {
-            return Err("Invalid tool_id format. Expected 'server_id:tool_id'".to_string());
+        let tool = {
+            let mcp_state = self.mcp_state.read().await;
+            let server_tools = mcp_state.server_tools.read().await;
+            server_tools
+                .get(&request.target.server_id)
+                .and_then(|tools| {
+                    tools
+                        .iter()
+                        .find(|tool| tool.name == request.target.tool_name)
+                })
+                .cloned()
+        };
+        if let Some(tool) = tool {
+            request.parameters = tool
+                .prepare_arguments(request.parameters, &ArgumentValidationConfig::from_env())
+                .map_err(|violations| {
+                    format!(
+                        "Invalid arguments for tool '{}':\n{}",
+                        request.target.tool_name,
+                        format_violations(&violations)
+                    )
+                })?;
         }
+        self.execute_prepared_proxy_tool(request).await
+    }
 
-        let server_id = parts[0];
-        let tool_id = parts[1];
+    async fn execute_prepared_proxy_tool(
+        &self,
+        request: ToolExecutionRequest,
+    ) -> Result<ToolExecutionResponse, String> {
+        // Keep only the handles, the state lock must not be held while the upstream call runs
+        let mcp_state = self.mcp_state.read().await.clone();
+        let server_id = request.target.server_id.as_str();
+        let tool_name = request.target.tool_name.as_str();
 
-        // Execute the tool on the server
-        let mcp_clients = mcp_state.mcp_clients.read().await;
-        let mcp_client = mcp_clients.get(server_id);
-        if mcp_client.is_none() {
+        if !mcp_state.mcp_clients.read().await.contains_key(server_id) {
             return Err(format!("Server with ID '{server_id}' not found"));
         }
-        let mcp_client = mcp_client.unwrap();
+
+        let tool = mcp_state
+            .server_tools
+            .read()
+            .await
+            .get(server_id)
+            .and_then(|tools| tools.iter().find(|tool| tool.name == tool_name))
+            .cloned();
+        if tool.as_ref().is_some_and(|tool| !tool.is_active) {
+            return Err(format!(
+                "Tool '{tool_name}' of server '{server_id}' is disabled"
+            ));
+        }
+        let parameters = request.parameters.clone();
+
+        let cache_ttl = tool.as_ref().and_then(|tool| tool.result_cache_ttl());
+        if cache_ttl.is_some() {
+            let cached = mcp_state
+                .result_cache
+                .lock()
+                .await
+                .get(server_id, tool_name, &parameters);
+            if let Some(result) = cached {
+                info!("Serving cached result of tool '{tool_name}' of server '{server_id}'");
+                return Ok(ToolExecutionResponse {
+                    success: true,
+                    result: Some(result),
+                    error: None,
+                });
+            }
+        }
 
         // Check if server is stopped
         // if matches!(mcp_client.server_status, ServerStatus::Stopped) {
         //     return Err(format!("Server '{}' is stopped", server_id));
         // }
 
-        let result = match mcp_client
-            .client
-            .call_tool(rmcp::model::CallToolRequestParam {
-                name: tool_id.to_string().into(),
-                arguments: request.parameters.clone(),
-            })
+        let result = match mcp_state
+            .execute_tool(
+                server_id,
+                tool_name,
+                parameters.clone(),
+                request.progress_token.clone(),
+            )
             .await
         {
             Ok(result) => result,
             Err(e) => return Err(format!("Tool execution error: {e}")),
         };
+        let is_error = result.is_error == Some(true);
+        let result = serde_json::to_value(result).unwrap();
+
+        // Errors are not cached, the next call may succeed
+        if let (Some(ttl), false) = (cache_ttl, is_error) {
+            mcp_state.result_cache.lock().await.insert(
+                server_id,
+                tool_name,
+                &parameters,
+                result.clone(),
+                ttl,
+            );
+        }
 
         Ok(ToolExecutionResponse {
             success: true,
-            result: Some(serde_json::to_value(result).unwrap()),
+            result: Some(result),
             error: None,
         })
     }
@@ -419,10 +535,16 @@ impl McpCoreProxyExt for MCPCore {
             if let Some(env_map) = &mut configuration.env {
                 // Update each environment variable from the config HashMap
                 for (key, value) in &request.config {
+                    // Masked values come back from the listed servers unchanged
+                    if value == REDACTED && env_map.contains_key(key) {
+                        continue;
+                    }
                     info!(
-                        "Setting environment variable for tool {}: {}={}",
-                        request.server_id, key, value
+                        "Setting environment variable for tool {}: {}",
+                        request.server_id, key
                     );
+                    // A variable stays secret once flagged
+                    let secret = env_map.get(key).is_some_and(|env| env.secret);
                     // Convert to ToolEnvironment
                     env_map.insert(
                         key.clone(),
@@ -430,6 +552,7 @@ impl McpCoreProxyExt for MCPCore {
                             description: "".to_string(),
                             default: Some(value.clone()),
                             required: false,
+                            secret,
                         },
                     );
                 }
@@ -529,6 +652,134 @@ impl McpCoreProxyExt for MCPCore {
         }
     }
 
+    /// Enable or disable a single tool of a server
+    async fn set_tool_active(
+        &self,
+        server_id: String,
+        tool_id: String,
+        active: bool,
+    ) -> Result<ToolUpdateResponse, String> {
+        let mcp_state = self.mcp_state.read().await;
+        match mcp_state.set_tool_active(&server_id, &tool_id, active).await {
+            Ok(tool) => Ok(ToolUpdateResponse {
+                success: true,
+                message: format!(
+                    "Tool '{}' of server '{server_id}' {}",
+                    tool.name,
+                    if active { "enabled" } else { "disabled" }
+                ),
+            }),
+            Err(e) => {
+                error!("Failed to update tool {tool_id} of server {server_id}: {e}");
+                Ok(ToolUpdateResponse {
+                    success: false,
+                    message: e,
+                })
+            }
+        }
+    }
+
+    async fn set_tool_overrides(
+        &self,
+        server_id: String,
+        tool_id: String,
+        overrides: Option<ToolOverrides>,
+    ) -> Result<ToolUpdateResponse, String> {
+        let mcp_state = self.mcp_state.read().await;
+        match mcp_state
+            .set_tool_overrides(&server_id, &tool_id, overrides)
+            .await
+        {
+            Ok(tool) => Ok(ToolUpdateResponse {
+                success: true,
+                message: format!(
+                    "Tool '{}' of server '{server_id}' is exposed as '{}'",
+                    tool.name,
+                    tool.exposed_name()
+                ),
+            }),
+            Err(e) => {
+                error!("Failed to update overrides of tool {tool_id} of server {server_id}: {e}");
+                Ok(ToolUpdateResponse {
+                    success: false,
+                    message: e,
+                })
+            }
+        }
+    }
+
+    async fn set_tool_argument_presets(
+        &self,
+        server_id: String,
+        tool_id: String,
+        presets: HashMap<String, ArgumentPreset>,
+    ) -> Result<ToolUpdateResponse, String> {
+        let mcp_state = self.mcp_state.read().await;
+        match mcp_state
+            .set_tool_argument_presets(&server_id, &tool_id, presets)
+            .await
+        {
+            Ok(tool) => Ok(ToolUpdateResponse {
+                success: true,
+                message: format!(
+                    "Tool '{}' of server '{server_id}' has {} argument presets",
+                    tool.name,
+                    tool.argument_presets.len()
+                ),
+            }),
+            Err(e) => {
+                error!("Failed to update argument presets of tool {tool_id} of server {server_id}: {e}");
+                Ok(ToolUpdateResponse {
+                    success: false,
+                    message: e,
+                })
+            }
+        }
+    }
+
+    async fn set_tool_cache_ttl(
+        &self,
+        server_id: String,
+        tool_id: String,
+        ttl: Option<u64>,
+    ) -> Result<ToolUpdateResponse, String> {
+        let mcp_state = self.mcp_state.read().await;
+        match mcp_state.set_tool_cache_ttl(&server_id, &tool_id, ttl).await {
+            Ok(tool) => Ok(ToolUpdateResponse {
+                success: true,
+                message: match ttl {
+                    Some(ttl) => format!(
+                        "Results of tool '{}' of server '{server_id}' are cached for {ttl}s",
+                        tool.name
+                    ),
+                    None => format!(
+                        "Results of tool '{}' of server '{server_id}' are not cached",
+                        tool.name
+                    ),
+                },
+            }),
+            Err(e) => {
+                error!("Failed to update cache TTL of tool {tool_id} of server {server_id}: {e}");
+                Ok(ToolUpdateResponse {
+                    success: false,
+                    message: e,
+                })
+            }
+        }
+    }
+
+    async fn result_cache_stats(&self) -> ResultCacheStats {
+        let mcp_state = self.mcp_state.read().await;
+        let stats = mcp_state.result_cache.lock().await.stats();
+        stats
+    }
+
+    async fn clear_result_cache(&self) {
+        let mcp_state = self.mcp_state.read().await;
+        mcp_state.result_cache.lock().await.clear();
+        info!("Cleared the result cache");
+    }
+
     /// Initialize and start background mcp services
     async fn init_mcp_server(&self) -> Result<()> {
         info!("Starting background initialization of MCP services");
@@ -715,6 +966,7 @@ impl McpCoreProxyExt for MCPCore {
                     description: format!("Extracted from README.md: {var_name}"),
                     default: Some("".to_string()), // Empty default value
                     required: true,
+                    secret: is_secret_key(&var_name),
                 },
             );
         }
@@ -810,6 +1062,7 @@ impl McpCoreProxyExt for MCPCore {
                     description: format!("Extracted from README.md: {var_name}"),
                     default: Some("".to_string()), // Empty default value
                     required: true,
+                    secret: is_secret_key(&var_name),
                 },
             );
         }
@@ -912,6 +1165,7 @@ impl McpCoreProxyExt for MCPCore {
                     description: format!("Custom environment variable: {}", key),
                     default: Some(resolved_value.clone()),
                     required: false,
+                    secret: is_secret_key(key),
                 });
             }
         }
@@ -0,0 +1,110 @@
+use async_trait::async_trait;
+use log::{error, info};
+
+use crate::models::types::Secret;
+use crate::utils::validate_name;
+
+use super::mcp_core::MCPCore;
+
+/// Credential vault, server env values reference its secrets as `${secret:name}`
+#[async_trait]
+pub trait McpCoreSecretsExt {
+    /// Secrets with the servers using them, without their values
+    async fn list_secrets(&self) -> Result<Vec<Secret>, String>;
+    /// Create or rotate a secret, the enabled servers using it are restarted.
+    ///
+    /// Returns the IDs of the servers using the secret.
+    async fn save_secret(
+        &self,
+        name: &str,
+        description: &str,
+        value: &str,
+    ) -> Result<Vec<String>, String>;
+    /// Delete a secret no server uses
+    async fn delete_secret(&self, name: &str) -> Result<(), String>;
+    /// Plain values of the secrets a server is given: its secret env values and the vault
+    /// secrets they reference, to mask them in what the server outputs
+    async fn server_secret_values(&self, server_id: &str) -> Result<Vec<String>, String>;
+}
+
+impl MCPCore {
+    /// IDs of the servers whose env references the secret `name`, sorted, and whether they are
+    /// enabled
+    async fn secret_dependents(&self, name: &str) -> Result<Vec<(String, bool)>, String> {
+        let servers = self.tool_registry.read().await.get_all_servers()?;
+        let mut dependents: Vec<(String, bool)> = servers
+            .into_iter()
+            .filter(|(_, server)| server.uses_secret(name))
+            .map(|(id, server)| (id, server.enabled))
+            .collect();
+        dependents.sort();
+        Ok(dependents)
+    }
+}
+
+#[async_trait]
+impl McpCoreSecretsExt for MCPCore {
+    async fn list_secrets(&self) -> Result<Vec<Secret>, String> {
+        let registry = self.tool_registry.read().await;
+        let servers = registry.get_all_servers()?;
+        let mut secrets = registry.get_secrets()?;
+        for secret in &mut secrets {
+            secret.servers = servers
+                .iter()
+                .filter(|(_, server)| server.uses_secret(&secret.name))
+                .map(|(id, _)| id.clone())
+                .collect();
+            secret.servers.sort();
+        }
+        Ok(secrets)
+    }
+
+    async fn save_secret(
+        &self,
+        name: &str,
+        description: &str,
+        value: &str,
+    ) -> Result<Vec<String>, String> {
+        validate_name("secret", name)?;
+        self.tool_registry
+            .read()
+            .await
+            .save_secret(name, description, value)?;
+        info!("Saved secret '{name}'");
+
+        // Running servers still hold the previous value
+        let dependents = self.secret_dependents(name).await?;
+        for (server_id, enabled) in &dependents {
+            if !enabled {
+                continue;
+            }
+            info!("Restarting server {server_id} to apply secret '{name}'");
+            if let Err(e) = self.mcp_state.read().await.restart_server(server_id).await {
+                error!("Failed to restart server {server_id} after saving secret '{name}': {e}");
+            }
+        }
+        Ok(dependents
+            .into_iter()
+            .map(|(server_id, _)| server_id)
+            .collect())
+    }
+
+    async fn delete_secret(&self, name: &str) -> Result<(), String> {
+        let dependents = self.secret_dependents(name).await?;
+        if !dependents.is_empty() {
+            let servers: Vec<String> = dependents.into_iter().map(|(id, _)| id).collect();
+            return Err(format!("Secret '{name}' is used by {}", servers.join(", ")));
+        }
+        self.tool_registry.read().await.delete_secret(name)?;
+        info!("Deleted secret '{name}'");
+        Ok(())
+    }
+
+    async fn server_secret_values(&self, server_id: &str) -> Result<Vec<String>, String> {
+        self.mcp_state
+            .read()
+            .await
+            .server_secret_values(server_id)
+            .await
+    }
+}
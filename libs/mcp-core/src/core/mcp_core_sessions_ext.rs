@@ -0,0 +1,99 @@
+use async_trait::async_trait;
+use log::info;
+
+use crate::mcp_server_implementation::approvals::APPROVALS;
+use crate::mcp_server_implementation::notifications::broadcast_tools_list_changed;
+use crate::mcp_server_implementation::session_manager::SESSION_MANAGER;
+use crate::models::types::{ClientPolicy, PendingApproval, SessionInfo};
+
+use super::mcp_core::MCPCore;
+
+/// Connected clients and the policies applied to them through their profile
+#[async_trait]
+pub trait McpCoreSessionsExt {
+    /// Clients connected to the SSE endpoints and their tool call counts
+    async fn list_sessions(&self) -> Result<Vec<SessionInfo>, String>;
+    async fn list_client_policies(&self) -> Result<Vec<ClientPolicy>, String>;
+    /// Policy applied to the connections using a profile: the profile's own, or else the `*`
+    /// policy, which alone applies to connections without a profile
+    async fn client_policy(&self, profile: Option<&str>) -> Result<Option<ClientPolicy>, String>;
+    /// Create or replace the client policy of a profile
+    async fn save_client_policy(&self, policy: ClientPolicy) -> Result<(), String>;
+    async fn delete_client_policy(&self, profile: &str) -> Result<(), String>;
+    /// Tool calls waiting for the user to approve them
+    async fn list_pending_approvals(&self) -> Result<Vec<PendingApproval>, String>;
+    async fn resolve_approval(&self, id: &str, approved: bool) -> Result<(), String>;
+}
+
+#[async_trait]
+impl McpCoreSessionsExt for MCPCore {
+    async fn list_sessions(&self) -> Result<Vec<SessionInfo>, String> {
+        Ok(SESSION_MANAGER.list_sessions().await)
+    }
+
+    async fn list_client_policies(&self) -> Result<Vec<ClientPolicy>, String> {
+        self.tool_registry.read().await.get_client_policies()
+    }
+
+    async fn client_policy(&self, profile: Option<&str>) -> Result<Option<ClientPolicy>, String> {
+        let registry = self.tool_registry.read().await;
+        let policy = match profile {
+            Some(profile) => registry.get_client_policy(profile)?,
+            None => None,
+        };
+        match policy {
+            Some(policy) => Ok(Some(policy)),
+            None => registry.get_client_policy(ClientPolicy::ANY_PROFILE),
+        }
+    }
+
+    async fn save_client_policy(&self, policy: ClientPolicy) -> Result<(), String> {
+        if policy.profile.trim().is_empty() {
+            return Err("Profile is required".to_string());
+        }
+        if policy.max_calls_per_minute == Some(0) {
+            return Err("The rate limit must allow at least one call per minute".to_string());
+        }
+        {
+            let registry = self.tool_registry.read().await;
+            if policy.profile != ClientPolicy::ANY_PROFILE {
+                registry
+                    .get_profile(&policy.profile)
+                    .map_err(|_| format!("Profile '{}' not found", policy.profile))?;
+            }
+            let servers = registry.get_all_servers()?;
+            let unknown = policy
+                .allowed_servers
+                .iter()
+                .flatten()
+                .find(|server_id| !servers.contains_key(*server_id));
+            if let Some(server_id) = unknown {
+                return Err(format!("Server '{server_id}' not found"));
+            }
+            registry.save_client_policy(&policy)?;
+        }
+        info!("Saved client policy of profile '{}'", policy.profile);
+
+        // Allowed servers decide which tools the client sees
+        broadcast_tools_list_changed().await;
+        Ok(())
+    }
+
+    async fn delete_client_policy(&self, profile: &str) -> Result<(), String> {
+        self.tool_registry
+            .read()
+            .await
+            .delete_client_policy(profile)?;
+        info!("Deleted client policy of profile '{profile}'");
+        broadcast_tools_list_changed().await;
+        Ok(())
+    }
+
+    async fn list_pending_approvals(&self) -> Result<Vec<PendingApproval>, String> {
+        Ok(APPROVALS.pending().await)
+    }
+
+    async fn resolve_approval(&self, id: &str, approved: bool) -> Result<(), String> {
+        APPROVALS.resolve(id, approved).await
+    }
+}
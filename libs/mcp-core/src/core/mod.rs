@@ -1,5 +1,9 @@
 pub mod mcp_core;
+pub mod mcp_core_composite_ext;
 pub mod mcp_core_database_ext;
 pub mod mcp_core_installers_ext;
+pub mod mcp_core_profiles_ext;
 pub mod mcp_core_proxy_ext;
+pub mod mcp_core_secrets_ext;
+pub mod mcp_core_sessions_ext;
 pub mod mcp_core_runtimes_ext;
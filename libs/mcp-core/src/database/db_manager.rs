@@ -1,24 +1,32 @@
 use diesel::prelude::*;
 use diesel::r2d2::{self, ConnectionManager, Pool};
 use diesel_migrations::{embed_migrations, EmbeddedMigrations, MigrationHarness};
-use log::info;
+use log::{error, info};
+use once_cell::sync::OnceCell;
 use std::collections::HashMap;
 use std::fs;
 use std::path::PathBuf;
 use std::sync::Arc;
 
+use crate::config::SecretStorageConfig;
 use crate::models::tool_db::{
-    DBAppSetting, DBServer, DBServerEnv, DBServerTool, NewAppSetting, NewServer, NewServerEnv,
-    NewServerTool, UpdateServer, UpdateServerTool,
+    DBAppSetting, DBClientPolicy, DBCompositeTool, DBProfile, DBSecret, DBServer, DBServerEnv,
+    DBServerTool, NewAppSetting, NewClientPolicy, NewCompositeTool, NewProfile, NewSecret,
+    NewServer, NewServerEnv, NewServerTool, UpdateServer, UpdateServerTool,
 };
 use crate::models::types::{
-    Distribution, InputSchema, ServerConfiguration, ServerDefinition, ServerEnvironment,
-    ServerToolInfo,
+    ClientPolicy, CompositeTool, Distribution, InputSchema, Profile, SchemaStrictness, Secret,
+    ServerConfiguration, ServerDefinition, ServerEnvironment, ServerToolInfo,
 };
 use crate::schema::app_settings::dsl as settings_dsl;
+use crate::schema::client_policies::dsl as policies_dsl;
+use crate::schema::composite_tools::dsl as composite_dsl;
+use crate::schema::profiles::dsl as profiles_dsl;
+use crate::schema::secrets::dsl as secrets_dsl;
 use crate::schema::server_env::dsl as env_dsl;
 use crate::schema::server_tools::dsl as server_tools_dsl;
 use crate::schema::servers::dsl as tools_dsl;
+use crate::utils::secrets::{is_encrypted, is_secret_key, needs_encryption, SecretCipher};
 
 pub const MIGRATIONS: EmbeddedMigrations = embed_migrations!("migrations/sqlite");
 
@@ -28,6 +36,11 @@ type SqlitePool = Pool<ConnectionManager<SqliteConnection>>;
 /// Database manager for persisting application state
 pub struct DBManager {
     pool: Arc<SqlitePool>,
+    /// Directory of the database, holds the secret key file unless configured otherwise
+    data_dir: PathBuf,
+    secret_storage: SecretStorageConfig,
+    /// Loaded on first use, so installs without secrets never need a key
+    secret_cipher: Arc<OnceCell<SecretCipher>>,
 }
 
 impl DBManager {
@@ -79,6 +92,9 @@ impl DBManager {
 
         let db_manager = Self {
             pool: Arc::new(pool),
+            data_dir: db_path.parent().map(PathBuf::from).unwrap_or_default(),
+            secret_storage: SecretStorageConfig::from_env(),
+            secret_cipher: Arc::new(OnceCell::new()),
         };
 
         info!("database initialized at: {db_path:?}");
@@ -142,10 +158,75 @@ impl DBManager {
             .execute(&mut conn)
             .map_err(|e| format!("Failed to enable foreign keys: {e}"))?;
 
+        // Secrets saved before they were encrypted, a missing key must not prevent startup
+        match self.encrypt_plaintext_secrets() {
+            Ok(0) => {}
+            Ok(count) => info!("Encrypted {count} secret environment values"),
+            Err(e) => error!("Failed to encrypt secret environment values: {e}"),
+        }
+
         info!("Migrations applied successfully");
         Ok(())
     }
 
+    /// Cipher of the secret environment values, its key is loaded or created on first use
+    fn secret_cipher(&self) -> Result<&SecretCipher, String> {
+        self.secret_cipher
+            .get_or_try_init(|| SecretCipher::load(&self.secret_storage.key_source, &self.data_dir))
+    }
+
+    /// Encrypt a secret environment value before storing it, see [`needs_encryption`]
+    fn encrypt_secret(&self, value: &str) -> Result<String, String> {
+        if !needs_encryption(value) {
+            return Ok(value.to_string());
+        }
+        self.secret_cipher()?.encrypt(value)
+    }
+
+    /// Decrypt a secret environment value read from the database
+    pub fn decrypt_secret(&self, value: &str) -> Result<String, String> {
+        if !is_encrypted(value) {
+            return Ok(value.to_string());
+        }
+        self.secret_cipher()?.decrypt(value)
+    }
+
+    /// Encrypt the secret environment values still stored in plaintext, returns how many were
+    pub fn encrypt_plaintext_secrets(&self) -> Result<usize, String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let rows: Vec<DBServerEnv> = env_dsl::server_env
+            .filter(env_dsl::env_secret.eq(true))
+            .load::<DBServerEnv>(&mut conn)
+            .map_err(|e| format!("Failed to query secret env vars: {e}"))?;
+        let mut encrypted = Vec::new();
+        for row in rows {
+            if needs_encryption(&row.env_value) {
+                let value = self.encrypt_secret(&row.env_value)?;
+                encrypted.push((row.server_id, row.env_key, value));
+            }
+        }
+
+        conn.transaction::<_, diesel::result::Error, _>(|conn| {
+            for (server_id, env_key, value) in &encrypted {
+                diesel::update(
+                    env_dsl::server_env
+                        .filter(env_dsl::server_id.eq(server_id))
+                        .filter(env_dsl::env_key.eq(env_key)),
+                )
+                .set(env_dsl::env_value.eq(value))
+                .execute(conn)?;
+            }
+            Ok(())
+        })
+        .map_err(|e| format!("Failed to encrypt secret env vars: {e}"))?;
+
+        Ok(encrypted.len())
+    }
+
     /// Get a server by ID
     pub fn get_server(&self, tool_id_str: &str) -> Result<ServerDefinition, String> {
         let mut conn = self
@@ -174,6 +255,7 @@ impl DBManager {
                     description: row.env_description,
                     default: Some(row.env_value),
                     required: row.env_required,
+                    secret: row.env_secret,
                 },
             );
         }
@@ -247,6 +329,7 @@ impl DBManager {
                     description: row.env_description,
                     default: Some(row.env_value),
                     required: row.env_required,
+                    secret: row.env_secret,
                 },
             );
         }
@@ -385,6 +468,13 @@ impl DBManager {
             .map_err(|e| format!("Failed to save tool: {e}"))?;
 
         // Now handle environment variables in tool_env
+        // Keys already stored keep the secret flag the caller passes, even when turned off
+        let stored_keys: Vec<String> = env_dsl::server_env
+            .filter(env_dsl::server_id.eq(server_id_str))
+            .select(env_dsl::env_key)
+            .load(&mut conn)
+            .map_err(|e| format!("Failed to load env keys: {e}"))?;
+
         // 1) Delete old environment variables
         diesel::delete(env_dsl::server_env.filter(env_dsl::server_id.eq(server_id_str)))
             .execute(&mut conn)
@@ -396,16 +486,25 @@ impl DBManager {
                 let new_env_rows: Vec<NewServerEnv> = env
                     .iter()
                     .map(|(k, v)| {
-                        let default_value = v.default.clone().unwrap_or_default();
-                        NewServerEnv {
+                        // Only new keys are guessed to be secrets from their name
+                        let env_secret = v.secret || (!stored_keys.contains(k) && is_secret_key(k));
+                        let mut default_value = v.default.clone().unwrap_or_default();
+                        if env_secret {
+                            default_value = self.encrypt_secret(&default_value)?;
+                        } else {
+                            // A key no longer marked secret is stored in plaintext again
+                            default_value = self.decrypt_secret(&default_value)?;
+                        }
+                        Ok(NewServerEnv {
                             server_id: server_id_str.to_string(),
                             env_key: k.to_string(),
                             env_value: default_value,
                             env_description: v.description.clone(),
                             env_required: v.required,
-                        }
+                            env_secret,
+                        })
                     })
-                    .collect();
+                    .collect::<Result<_, String>>()?;
 
                 if !new_env_rows.is_empty() {
                     diesel::insert_into(env_dsl::server_env)
@@ -456,6 +555,12 @@ impl DBManager {
             // Delete server tools
             diesel::delete(server_tools_dsl::server_tools).execute(conn)?;
 
+            // Delete profiles, client policies, composite tools and secrets
+            diesel::delete(profiles_dsl::profiles).execute(conn)?;
+            diesel::delete(policies_dsl::client_policies).execute(conn)?;
+            diesel::delete(composite_dsl::composite_tools).execute(conn)?;
+            diesel::delete(secrets_dsl::secrets).execute(conn)?;
+
             Ok(())
         })
         .map_err(|e| format!("Transaction failed: {e}"))?;
@@ -504,6 +609,33 @@ impl DBManager {
             None
         };
 
+        let annotations_json = tool
+            .annotations
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| format!("Failed to serialize tool annotations: {e}"))?;
+        let overrides_json = tool
+            .overrides
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| format!("Failed to serialize tool overrides: {e}"))?;
+        let argument_presets_json = if tool.argument_presets.is_empty() {
+            None
+        } else {
+            Some(
+                serde_json::to_string(&tool.argument_presets)
+                    .map_err(|e| format!("Failed to serialize argument presets: {e}"))?,
+            )
+        };
+
+        let cache_ttl = tool
+            .cache_ttl
+            .map(i32::try_from)
+            .transpose()
+            .map_err(|e| format!("Invalid cache TTL: {e}"))?;
+
         // Create the new server tool record
         let new_tool = NewServerTool {
             id: tool.id.clone(),
@@ -513,6 +645,11 @@ impl DBManager {
             server_id: tool.server_id.clone(),
             proxy_id: tool.proxy_id.clone(),
             is_active: tool.is_active,
+            title: tool.title.clone(),
+            annotations: annotations_json.clone(),
+            overrides: overrides_json.clone(),
+            argument_presets: argument_presets_json.clone(),
+            cache_ttl,
         };
 
         // Insert or update the server tool
@@ -526,6 +663,11 @@ impl DBManager {
                 input_schema: Some(input_schema_json),
                 proxy_id: Some(tool.proxy_id.clone()),
                 is_active: Some(tool.is_active),
+                title: Some(tool.title.clone()),
+                annotations: Some(annotations_json),
+                overrides: Some(overrides_json),
+                argument_presets: Some(argument_presets_json),
+                cache_ttl: Some(cache_ttl),
             })
             .execute(&mut conn)
             .map_err(|e| format!("Failed to save server tool: {e}"))?;
@@ -550,25 +692,7 @@ impl DBManager {
             .first::<DBServerTool>(&mut conn)
             .map_err(|e| format!("Failed to get server tool {tool_id}: {e}"))?;
 
-        // Parse the input_schema from JSON if it exists
-        let input_schema = if let Some(ref schema_json) = db_tool.input_schema {
-            Some(
-                serde_json::from_str::<InputSchema>(schema_json)
-                    .map_err(|e| format!("Failed to parse input schema: {e}"))?,
-            )
-        } else {
-            None
-        };
-
-        Ok(ServerToolInfo {
-            id: db_tool.id,
-            name: db_tool.name,
-            description: db_tool.description,
-            input_schema,
-            server_id: db_tool.server_id,
-            proxy_id: db_tool.proxy_id,
-            is_active: db_tool.is_active,
-        })
+        server_tool_from_db(db_tool)
     }
 
     /// Get all ServerToolInfo for a server
@@ -583,30 +707,7 @@ impl DBManager {
             .load::<DBServerTool>(&mut conn)
             .map_err(|e| format!("Failed to get server tools for {server_id}: {e}"))?;
 
-        let mut tools = Vec::new();
-        for db_tool in db_tools {
-            // Parse the input_schema from JSON if it exists
-            let input_schema = if let Some(ref schema_json) = db_tool.input_schema {
-                Some(
-                    serde_json::from_str::<InputSchema>(schema_json)
-                        .map_err(|e| format!("Failed to parse input schema: {e}"))?,
-                )
-            } else {
-                None
-            };
-
-            tools.push(ServerToolInfo {
-                id: db_tool.id,
-                name: db_tool.name,
-                description: db_tool.description,
-                input_schema,
-                server_id: db_tool.server_id,
-                proxy_id: db_tool.proxy_id,
-                is_active: db_tool.is_active,
-            });
-        }
-
-        Ok(tools)
+        db_tools.into_iter().map(server_tool_from_db).collect()
     }
 
     /// Delete a ServerToolInfo by ID and server_id
@@ -661,4 +762,444 @@ impl DBManager {
 
         Ok(())
     }
+
+    /// Get a profile by name
+    pub fn get_profile(&self, name: &str) -> Result<Profile, String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let db_profile: DBProfile = profiles_dsl::profiles
+            .filter(profiles_dsl::name.eq(name))
+            .first(&mut conn)
+            .map_err(|e| format!("Failed to get profile {name}: {e}"))?;
+
+        profile_from_db(db_profile)
+    }
+
+    /// Get all profiles, ordered by name
+    pub fn get_profiles(&self) -> Result<Vec<Profile>, String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let db_profiles: Vec<DBProfile> = profiles_dsl::profiles
+            .order(profiles_dsl::name)
+            .load(&mut conn)
+            .map_err(|e| format!("Failed to get profiles: {e}"))?;
+
+        db_profiles.into_iter().map(profile_from_db).collect()
+    }
+
+    /// Save or update a profile
+    pub fn save_profile(&self, profile: &Profile) -> Result<(), String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let servers = serde_json::to_string(&profile.servers)
+            .map_err(|e| format!("Failed to serialize profile servers: {e}"))?;
+        let tools = serde_json::to_string(&profile.tools)
+            .map_err(|e| format!("Failed to serialize profile tools: {e}"))?;
+        let token_budget = profile
+            .token_budget
+            .map(i32::try_from)
+            .transpose()
+            .map_err(|e| format!("Invalid token budget: {e}"))?;
+        let new_profile = NewProfile {
+            name: &profile.name,
+            description: &profile.description,
+            servers: &servers,
+            tools: &tools,
+            token_budget,
+            schema_strictness: profile
+                .schema_strictness
+                .as_ref()
+                .map(SchemaStrictness::as_str),
+        };
+
+        diesel::insert_into(profiles_dsl::profiles)
+            .values(&new_profile)
+            .on_conflict(profiles_dsl::name)
+            .do_update()
+            .set(&new_profile)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save profile: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Delete a profile by name
+    pub fn delete_profile(&self, name: &str) -> Result<(), String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        diesel::delete(profiles_dsl::profiles.filter(profiles_dsl::name.eq(name)))
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to delete profile: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Get the client policy of a profile
+    pub fn get_client_policy(&self, profile: &str) -> Result<Option<ClientPolicy>, String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let db_policy: Option<DBClientPolicy> = policies_dsl::client_policies
+            .filter(policies_dsl::profile.eq(profile))
+            .first(&mut conn)
+            .optional()
+            .map_err(|e| format!("Failed to get client policy of profile {profile}: {e}"))?;
+
+        db_policy.map(client_policy_from_db).transpose()
+    }
+
+    /// Get all client policies, ordered by profile
+    pub fn get_client_policies(&self) -> Result<Vec<ClientPolicy>, String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let db_policies: Vec<DBClientPolicy> = policies_dsl::client_policies
+            .order(policies_dsl::profile)
+            .load(&mut conn)
+            .map_err(|e| format!("Failed to get client policies: {e}"))?;
+
+        db_policies.into_iter().map(client_policy_from_db).collect()
+    }
+
+    /// Save or update the client policy of a profile
+    pub fn save_client_policy(&self, policy: &ClientPolicy) -> Result<(), String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let allowed_servers = policy
+            .allowed_servers
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| format!("Failed to serialize allowed servers: {e}"))?;
+        let max_calls_per_minute = policy
+            .max_calls_per_minute
+            .map(i32::try_from)
+            .transpose()
+            .map_err(|e| format!("Invalid rate limit: {e}"))?;
+        let new_policy = NewClientPolicy {
+            profile: &policy.profile,
+            allowed_servers: allowed_servers.as_deref(),
+            max_calls_per_minute,
+            require_approval: policy.require_approval,
+            schema_strictness: policy
+                .schema_strictness
+                .as_ref()
+                .map(SchemaStrictness::as_str),
+        };
+
+        diesel::insert_into(policies_dsl::client_policies)
+            .values(&new_policy)
+            .on_conflict(policies_dsl::profile)
+            .do_update()
+            .set(&new_policy)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save client policy: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Delete the client policy of a profile
+    pub fn delete_client_policy(&self, profile: &str) -> Result<(), String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        diesel::delete(policies_dsl::client_policies.filter(policies_dsl::profile.eq(profile)))
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to delete client policy: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Get a composite tool by name
+    pub fn get_composite_tool(&self, name: &str) -> Result<CompositeTool, String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let db_tool: DBCompositeTool = composite_dsl::composite_tools
+            .filter(composite_dsl::name.eq(name))
+            .first(&mut conn)
+            .map_err(|e| format!("Failed to get composite tool {name}: {e}"))?;
+
+        composite_tool_from_db(db_tool)
+    }
+
+    /// Get all composite tools, ordered by name
+    pub fn get_composite_tools(&self) -> Result<Vec<CompositeTool>, String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let db_tools: Vec<DBCompositeTool> = composite_dsl::composite_tools
+            .order(composite_dsl::name)
+            .load(&mut conn)
+            .map_err(|e| format!("Failed to get composite tools: {e}"))?;
+
+        db_tools.into_iter().map(composite_tool_from_db).collect()
+    }
+
+    /// Save or update a composite tool
+    pub fn save_composite_tool(&self, tool: &CompositeTool) -> Result<(), String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let input_schema = serde_json::to_string(&tool.input_schema)
+            .map_err(|e| format!("Failed to serialize input schema: {e}"))?;
+        let steps = serde_json::to_string(&tool.steps)
+            .map_err(|e| format!("Failed to serialize composite tool steps: {e}"))?;
+        let output = tool
+            .output
+            .as_ref()
+            .map(serde_json::to_string)
+            .transpose()
+            .map_err(|e| format!("Failed to serialize composite tool output: {e}"))?;
+        let new_tool = NewCompositeTool {
+            name: &tool.name,
+            description: &tool.description,
+            input_schema: &input_schema,
+            steps: &steps,
+            output: output.as_deref(),
+        };
+
+        diesel::insert_into(composite_dsl::composite_tools)
+            .values(&new_tool)
+            .on_conflict(composite_dsl::name)
+            .do_update()
+            .set(&new_tool)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save composite tool: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Delete a composite tool by name
+    pub fn delete_composite_tool(&self, name: &str) -> Result<(), String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        diesel::delete(composite_dsl::composite_tools.filter(composite_dsl::name.eq(name)))
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to delete composite tool: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Get all vault secrets without their values, ordered by name
+    pub fn get_secrets(&self) -> Result<Vec<Secret>, String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let db_secrets: Vec<DBSecret> = secrets_dsl::secrets
+            .order(secrets_dsl::name)
+            .load(&mut conn)
+            .map_err(|e| format!("Failed to get secrets: {e}"))?;
+
+        Ok(db_secrets.into_iter().map(secret_from_db).collect())
+    }
+
+    /// Get the decrypted value of a vault secret
+    pub fn get_secret_value(&self, name: &str) -> Result<String, String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let value: String = secrets_dsl::secrets
+            .filter(secrets_dsl::name.eq(name))
+            .select(secrets_dsl::value)
+            .first(&mut conn)
+            .map_err(|e| format!("Failed to get secret {name}: {e}"))?;
+
+        self.decrypt_secret(&value)
+    }
+
+    /// Save or update a vault secret, its value is stored encrypted
+    pub fn save_secret(&self, name: &str, description: &str, value: &str) -> Result<(), String> {
+        let value = self.secret_cipher()?.encrypt(value)?;
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        let new_secret = NewSecret {
+            name,
+            description,
+            value: &value,
+        };
+
+        diesel::insert_into(secrets_dsl::secrets)
+            .values(&new_secret)
+            .on_conflict(secrets_dsl::name)
+            .do_update()
+            .set(&new_secret)
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to save secret: {e}"))?;
+
+        Ok(())
+    }
+
+    /// Delete a vault secret by name
+    pub fn delete_secret(&self, name: &str) -> Result<(), String> {
+        let mut conn = self
+            .pool
+            .get()
+            .map_err(|e| format!("Failed to get database connection: {e}"))?;
+
+        diesel::delete(secrets_dsl::secrets.filter(secrets_dsl::name.eq(name)))
+            .execute(&mut conn)
+            .map_err(|e| format!("Failed to delete secret: {e}"))?;
+
+        Ok(())
+    }
+}
+
+/// Convert a `server_tools` row, parsing its JSON columns
+fn server_tool_from_db(db_tool: DBServerTool) -> Result<ServerToolInfo, String> {
+    let input_schema = db_tool
+        .input_schema
+        .as_deref()
+        .map(serde_json::from_str::<InputSchema>)
+        .transpose()
+        .map_err(|e| format!("Failed to parse input schema: {e}"))?;
+    let annotations = db_tool
+        .annotations
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| format!("Failed to parse tool annotations: {e}"))?;
+    let overrides = db_tool
+        .overrides
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| format!("Failed to parse tool overrides: {e}"))?;
+    let argument_presets = db_tool
+        .argument_presets
+        .as_deref()
+        .map(serde_json::from_str)
+        .transpose()
+        .map_err(|e| format!("Failed to parse argument presets: {e}"))?
+        .unwrap_or_default();
+
+    Ok(ServerToolInfo {
+        id: db_tool.id,
+        name: db_tool.name,
+        description: db_tool.description,
+        input_schema,
+        server_id: db_tool.server_id,
+        proxy_id: db_tool.proxy_id,
+        is_active: db_tool.is_active,
+        title: db_tool.title,
+        annotations,
+        overrides,
+        argument_presets,
+        cache_ttl: db_tool
+            .cache_ttl
+            .map(u64::try_from)
+            .transpose()
+            .map_err(|e| format!("Invalid cache TTL: {e}"))?,
+        size: None,
+    })
+}
+
+/// Convert a `profiles` row, parsing its JSON columns
+fn profile_from_db(db_profile: DBProfile) -> Result<Profile, String> {
+    Ok(Profile {
+        servers: serde_json::from_str(&db_profile.servers)
+            .map_err(|e| format!("Failed to parse profile servers: {e}"))?,
+        tools: serde_json::from_str(&db_profile.tools)
+            .map_err(|e| format!("Failed to parse profile tools: {e}"))?,
+        token_budget: db_profile
+            .token_budget
+            .map(u32::try_from)
+            .transpose()
+            .map_err(|e| format!("Invalid token budget: {e}"))?,
+        schema_strictness: db_profile
+            .schema_strictness
+            .as_deref()
+            .map(str::parse)
+            .transpose()?,
+        name: db_profile.name,
+        description: db_profile.description,
+    })
+}
+
+/// Convert a `client_policies` row, parsing its JSON columns
+fn client_policy_from_db(db_policy: DBClientPolicy) -> Result<ClientPolicy, String> {
+    Ok(ClientPolicy {
+        allowed_servers: db_policy
+            .allowed_servers
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| format!("Failed to parse allowed servers: {e}"))?,
+        max_calls_per_minute: db_policy
+            .max_calls_per_minute
+            .map(u32::try_from)
+            .transpose()
+            .map_err(|e| format!("Invalid rate limit: {e}"))?,
+        schema_strictness: db_policy
+            .schema_strictness
+            .as_deref()
+            .map(str::parse)
+            .transpose()?,
+        profile: db_policy.profile,
+        require_approval: db_policy.require_approval,
+    })
+}
+
+/// Convert a `composite_tools` row, parsing its JSON columns
+fn composite_tool_from_db(db_tool: DBCompositeTool) -> Result<CompositeTool, String> {
+    Ok(CompositeTool {
+        input_schema: serde_json::from_str(&db_tool.input_schema)
+            .map_err(|e| format!("Failed to parse input schema: {e}"))?,
+        steps: serde_json::from_str(&db_tool.steps)
+            .map_err(|e| format!("Failed to parse composite tool steps: {e}"))?,
+        output: db_tool
+            .output
+            .as_deref()
+            .map(serde_json::from_str)
+            .transpose()
+            .map_err(|e| format!("Failed to parse composite tool output: {e}"))?,
+        name: db_tool.name,
+        description: db_tool.description,
+    })
+}
+
+/// Convert a `secrets` row, leaving its value out
+fn secret_from_db(db_secret: DBSecret) -> Secret {
+    Secret {
+        name: db_secret.name,
+        description: db_secret.description,
+        servers: Vec::new(),
+    }
 }
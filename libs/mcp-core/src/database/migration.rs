@@ -2,10 +2,12 @@ use anyhow::Result;
 use diesel::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 // Removed unused imports
+use crate::config::{SecretKeySource, SecretStorageConfig};
 use crate::schema::{server_env, servers};
+use crate::utils::secrets::{is_secret_key, secret_references, SecretCipher};
 
 #[derive(Debug, Serialize, Deserialize)]
 pub struct ExportedServer {
@@ -30,6 +32,20 @@ pub struct ExportedEnvVar {
     pub value: String,
     pub description: String,
     pub required: bool,
+    /// The value is a secret, empty unless re-encrypted for the importing install
+    #[serde(default)]
+    pub secret: bool,
+}
+
+/// How the values of secret environment variables are exported
+#[derive(Debug, Clone, Default, PartialEq)]
+pub enum SecretExport {
+    /// Secret values are left empty
+    #[default]
+    Exclude,
+    /// Secret values are re-encrypted with the key of a key file, such as the `secret.key` of
+    /// the install that imports them. The key file is created if missing.
+    ReEncrypt(PathBuf),
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -62,9 +78,10 @@ struct ServerEnvRecord {
     pub env_value: String,
     pub env_description: String,
     pub env_required: bool,
+    pub env_secret: bool,
 }
 
-pub fn export_servers_to_json(db_path: &Path) -> Result<String> {
+pub fn export_servers_to_json(db_path: &Path, secrets: &SecretExport) -> Result<String> {
     use diesel::sqlite::SqliteConnection;
     
     // Connect to the database
@@ -98,21 +115,47 @@ pub fn export_servers_to_json(db_path: &Path) -> Result<String> {
             server_env::env_value,
             server_env::env_description,
             server_env::env_required,
+            server_env::env_secret,
         ))
         .load::<ServerEnvRecord>(&mut connection)?;
     
+    // Secrets are decrypted with the key of this install and re-encrypted with the target key
+    let ciphers = match secrets {
+        SecretExport::Exclude => None,
+        SecretExport::ReEncrypt(key_path) => {
+            let data_dir = db_path.parent().unwrap_or(Path::new("."));
+            let source = SecretCipher::load(&SecretStorageConfig::from_env().key_source, data_dir)
+                .map_err(anyhow::Error::msg)?;
+            let target = SecretCipher::load(&SecretKeySource::File(Some(key_path.clone())), data_dir)
+                .map_err(anyhow::Error::msg)?;
+            Some((source, target))
+        }
+    };
+
     // Group env vars by server_id
     let mut env_by_server: HashMap<String, HashMap<String, ExportedEnvVar>> = HashMap::new();
     for env in env_records {
+        let value = match (&ciphers, env.env_secret) {
+            (_, false) => env.env_value,
+            // References to the vault hold no secret, the importing install defines the secrets
+            (_, true) if !secret_references(&env.env_value).is_empty() => env.env_value,
+            (None, true) => String::new(),
+            (Some(_), true) if env.env_value.is_empty() => String::new(),
+            (Some((source, target)), true) => source
+                .decrypt(&env.env_value)
+                .and_then(|value| target.encrypt(&value))
+                .map_err(|e| anyhow::anyhow!("Failed to re-encrypt {}: {e}", env.env_key))?,
+        };
         env_by_server
             .entry(env.server_id.clone())
             .or_default()
             .insert(
                 env.env_key,
                 ExportedEnvVar {
-                    value: env.env_value,
+                    value,
                     description: env.env_description,
                     required: env.env_required,
+                    secret: env.env_secret,
                 },
             );
     }
@@ -151,8 +194,8 @@ pub fn export_servers_to_json(db_path: &Path) -> Result<String> {
     serde_json::to_string_pretty(&export_data).map_err(Into::into)
 }
 
-pub fn export_servers_to_file(db_path: &Path, output_path: &Path) -> Result<()> {
-    let json = export_servers_to_json(db_path)?;
+pub fn export_servers_to_file(db_path: &Path, output_path: &Path, secrets: &SecretExport) -> Result<()> {
+    let json = export_servers_to_json(db_path, secrets)?;
     std::fs::write(output_path, json)?;
     Ok(())
 }
@@ -190,10 +233,12 @@ pub fn import_claude_desktop_config(config_path: &Path) -> Result<Vec<ExportedSe
         // Convert env vars to our format
         let mut env_vars = HashMap::new();
         for (key, value) in server_config.env {
+            let secret = is_secret_key(&key);
             env_vars.insert(key, ExportedEnvVar {
                 value,
                 description: "Imported from Claude Desktop config".to_string(),
                 required: false,
+                secret,
             });
         }
         
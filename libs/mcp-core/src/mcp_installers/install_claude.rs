@@ -1,6 +1,7 @@
 use crate::utils::process::kill_process_by_name;
 
 use super::install_paths::get_claude_config_path;
+use super::install_paths::ProxyAuth;
 use super::{install_errors::ClaudeError, install_paths};
 use log::info;
 use serde_json::{json, Value};
@@ -39,8 +40,24 @@ pub fn is_claude_installed(app_name: &str) -> Result<(), ClaudeError> {
     }
 }
 
-pub fn install_claude(app_name: &str, binary_path: &str) -> Result<(), ClaudeError> {
-    if is_claude_installed(app_name).is_ok() {
+/// Environment of an installed entry, `None` when the entry is not installed
+fn installed_env(entry_name: &str) -> Option<Value> {
+    let content = fs::read_to_string(get_claude_config_path().ok()?).ok()?;
+    let config: Value = serde_json::from_str(&content).ok()?;
+    let entry = config.get("mcpServers")?.get(entry_name)?;
+    Some(entry.get("env").cloned().unwrap_or(Value::Null))
+}
+
+pub fn install_claude(
+    app_name: &str,
+    binary_path: &str,
+    profile: Option<&str>,
+    auth: Option<&ProxyAuth>,
+) -> Result<(), ClaudeError> {
+    let (entry_name, args) = install_paths::proxy_server_entry(app_name, profile);
+    let env = json!(install_paths::proxy_server_env(auth));
+    // Entries installed before the token, or with another token, are installed again
+    if installed_env(&entry_name).is_some_and(|installed| installed == env) {
         return Ok(());
     }
 
@@ -86,23 +103,29 @@ pub fn install_claude(app_name: &str, binary_path: &str) -> Result<(), ClaudeErr
     }
 
     // Add mcp-dockmaster configuration with the correct format
-    config["mcpServers"][app_name] = json!({
-        "args": [],
+    config["mcpServers"][&entry_name] = json!({
+        "args": args,
         "command": binary_path
     });
+    if !env.is_null() {
+        config["mcpServers"][&entry_name]["env"] = env;
+    }
 
     // Write the updated configuration
     fs::write(&config_path, serde_json::to_string_pretty(&config)?)?;
     info!("✅ MCP Dockmaster installed in CLAUDE");
     info!("Please restart Claude to apply the changes.");
     info!("config_path: {config_path}");
-    info!("content: {config}");
-    is_claude_installed(app_name)
+    is_claude_installed(&entry_name)
 }
 
-pub fn get_claude_config(app_name: &str, binary_path: &str) -> Result<String, ClaudeError> {
+pub fn get_claude_config(
+    app_name: &str,
+    binary_path: &str,
+    auth: Option<&ProxyAuth>,
+) -> Result<String, ClaudeError> {
     let config_path = get_claude_config_path()?;
-    let config = json!({
+    let mut config = json!({
         "mcpServers": {
             format!("{}", app_name): {
                 "args": [],
@@ -110,6 +133,9 @@ pub fn get_claude_config(app_name: &str, binary_path: &str) -> Result<String, Cl
             }
         }
     });
+    if let Some(env) = install_paths::proxy_server_env(auth) {
+        config["mcpServers"][app_name]["env"] = json!(env);
+    }
 
     // Format the JSON with proper indentation
     let pretty_json = serde_json::to_string_pretty(&config)?;
@@ -5,6 +5,7 @@ use log::error;
 use serde::{Deserialize, Serialize};
 
 use super::install_errors::CursorError;
+use super::install_paths::{proxy_server_entry, proxy_server_env, ProxyAuth};
 
 #[derive(Serialize, Deserialize, Clone, Debug)]
 struct CursorMcpGlobalConfig {
@@ -44,7 +45,6 @@ pub fn is_cursor_installed(app_name: &str) -> Result<bool, CursorError> {
         error!("cannot install: Failed to read Cursor MCP global config: {e}");
         CursorError::ConfigNotFound(cursor_mcp_global_config_path.to_string_lossy().to_string())
     })?;
-    println!("cursor_mcp_global_config_file: {cursor_mcp_global_config_as_str}");
     let cursor_mcp_global_config: CursorMcpGlobalConfig =
         if cursor_mcp_global_config_as_str.is_empty() {
             CursorMcpGlobalConfig { mcp_servers: None }
@@ -59,7 +59,13 @@ pub fn is_cursor_installed(app_name: &str) -> Result<bool, CursorError> {
     Ok(mcp_servers.contains_key(app_name))
 }
 
-pub fn install_cursor(app_name: &str, binary_path: &str) -> Result<(), CursorError> {
+pub fn install_cursor(
+    app_name: &str,
+    binary_path: &str,
+    profile: Option<&str>,
+    auth: Option<&ProxyAuth>,
+) -> Result<(), CursorError> {
+    let (entry_name, args) = proxy_server_entry(app_name, profile);
     let cursor_mcp_global_config_path = get_cursor_mcp_global_config_path()?;
     let cursor_mcp_global_config_as_str = if !cursor_mcp_global_config_path.exists() {
         File::create(&cursor_mcp_global_config_path).map_err(|e| {
@@ -74,7 +80,6 @@ pub fn install_cursor(app_name: &str, binary_path: &str) -> Result<(), CursorErr
         })?
     };
 
-    println!("cursor_mcp_global_config_file: {cursor_mcp_global_config_as_str}");
     let mut cursor_mcp_global_config: CursorMcpGlobalConfig =
         if cursor_mcp_global_config_as_str.is_empty() {
             CursorMcpGlobalConfig { mcp_servers: None }
@@ -87,11 +92,11 @@ pub fn install_cursor(app_name: &str, binary_path: &str) -> Result<(), CursorErr
 
     let mut servers = cursor_mcp_global_config.mcp_servers.unwrap_or_default();
     servers.insert(
-        app_name.to_string(),
+        entry_name,
         McpServer::Command(CommandMcpServer {
             command: binary_path.to_string(),
-            args: vec![],
-            env: None,
+            args,
+            env: proxy_server_env(auth),
         }),
     );
 
@@ -112,7 +117,13 @@ pub fn install_cursor(app_name: &str, binary_path: &str) -> Result<(), CursorErr
     Ok(())
 }
 
-pub fn get_cursor_config(app_name: &str, binary_path: &str) -> Result<String, CursorError> {
+pub fn get_cursor_config(
+    app_name: &str,
+    binary_path: &str,
+    auth: Option<&ProxyAuth>,
+) -> Result<String, CursorError> {
+    let env = serde_json::to_string(&proxy_server_env(auth).unwrap_or_default())
+        .map_err(|e| CursorError::InvalidJson(e.to_string()))?;
     Ok(format!(
         r#"
 {{
@@ -121,7 +132,7 @@ pub fn get_cursor_config(app_name: &str, binary_path: &str) -> Result<String, Cu
     "{app_name}": {{
       "command": "{binary_path}",
       "args": [],
-      "env": {{}}
+      "env": {env}
     }}
     ...
   }}
@@ -1,9 +1,11 @@
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::io;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 use super::install_errors::ClaudeError;
+use crate::mcp_server_implementation::http_auth::DISCOVERY_FILE_ENV;
 
 pub fn get_claude_config_path() -> Result<String, ClaudeError> {
     match env::consts::OS {
@@ -43,7 +45,44 @@ pub fn backup_file(file_path: &str) -> Result<String, io::Error> {
     Ok(backup_path)
 }
 
-pub fn get_generic_config(binary_path: &str) -> String {
+/// Name of the client config entry and arguments of the proxy for a profile.
+///
+/// Each profile gets its own entry, so a client can use several profiles side by side.
+pub fn proxy_server_entry(app_name: &str, profile: Option<&str>) -> (String, Vec<String>) {
+    match profile {
+        Some(profile) => (
+            format!("{app_name}-{profile}"),
+            vec!["--profile".to_string(), profile.to_string()],
+        ),
+        None => (app_name.to_string(), vec![]),
+    }
+}
+
+/// How the proxy server finds the HTTP server and authenticates to it.
+///
+/// The proxy reads the token from the discovery file, so it is never written to client configs
+/// or shown to the user.
+#[derive(Clone, Debug)]
+pub struct ProxyAuth {
+    /// Discovery file holding the address of the HTTP server and its token
+    pub discovery_file: PathBuf,
+}
+
+/// Environment of the proxy server entry, set when the HTTP server requires a token
+pub fn proxy_server_env(auth: Option<&ProxyAuth>) -> Option<HashMap<String, String>> {
+    auth.map(|auth| {
+        HashMap::from([(
+            DISCOVERY_FILE_ENV.to_string(),
+            auth.discovery_file.to_string_lossy().into_owned(),
+        )])
+    })
+}
+
+pub fn get_generic_config(binary_path: &str, auth: Option<&ProxyAuth>) -> String {
+    let environment = match auth {
+        Some(auth) => format!("{DISCOVERY_FILE_ENV}={}", auth.discovery_file.display()),
+        None => String::new(),
+    };
     format!(
         "
 For any MCP client, configure with:
@@ -52,6 +91,7 @@ For any MCP client, configure with:
 Server Name: mcp-dockmaster
 Command: {binary_path}
 Arguments: []
+Environment: {environment}
 ```
         "
     )
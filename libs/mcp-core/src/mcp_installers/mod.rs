@@ -10,3 +10,5 @@ pub use self::install_cursor::is_cursor_installed;
 
 pub use self::install_cursor::get_cursor_config;
 pub use self::install_paths::get_generic_config;
+pub use self::install_paths::proxy_server_env;
+pub use self::install_paths::ProxyAuth;
@@ -0,0 +1,78 @@
+use std::{collections::HashMap, time::Duration};
+
+use log::{info, warn};
+use once_cell::sync::Lazy;
+use tokio::sync::{oneshot, Mutex as TokioMutex};
+
+use crate::models::types::PendingApproval;
+
+/// Tool calls waiting for the user to approve or deny them
+#[derive(Default)]
+pub struct ApprovalManager {
+    pending: TokioMutex<HashMap<String, (PendingApproval, oneshot::Sender<bool>)>>,
+}
+
+impl ApprovalManager {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wait for the user to decide on a tool call, calls not decided within `timeout` are denied
+    pub async fn request_approval(&self, approval: PendingApproval, timeout: Duration) -> bool {
+        let (sender, receiver) = oneshot::channel();
+        let id = approval.id.clone();
+        info!(
+            "Tool '{}' of server {} called by {} is waiting for approval",
+            approval.tool_name, approval.server_id, approval.client_name
+        );
+        self.pending
+            .lock()
+            .await
+            .insert(id.clone(), (approval, sender));
+
+        match tokio::time::timeout(timeout, receiver).await {
+            Ok(Ok(approved)) => approved,
+            Ok(Err(_)) => false,
+            Err(_) => {
+                warn!("Approval request {id} timed out");
+                self.pending.lock().await.remove(&id);
+                false
+            }
+        }
+    }
+
+    /// Calls waiting for a decision, oldest first
+    pub async fn pending(&self) -> Vec<PendingApproval> {
+        let mut pending = self.pending.lock().await;
+        // Drop the requests of calls that were abandoned, e.g. because the client disconnected
+        pending.retain(|_, (_, sender)| !sender.is_closed());
+        let mut approvals: Vec<PendingApproval> = pending
+            .values()
+            .map(|(approval, _)| approval.clone())
+            .collect();
+        approvals.sort_by_key(|approval| approval.requested_at);
+        approvals
+    }
+
+    /// Approve or deny a waiting call
+    pub async fn resolve(&self, id: &str, approved: bool) -> Result<(), String> {
+        let (approval, sender) = self
+            .pending
+            .lock()
+            .await
+            .remove(id)
+            .ok_or_else(|| format!("No tool call is waiting for approval {id}"))?;
+        info!(
+            "Tool '{}' of server {} {}",
+            approval.tool_name,
+            approval.server_id,
+            if approved { "approved" } else { "denied" }
+        );
+        sender
+            .send(approved)
+            .map_err(|_| format!("The call waiting for approval {id} ended"))
+    }
+}
+
+// Global approval manager instance
+pub static APPROVALS: Lazy<ApprovalManager> = Lazy::new(ApprovalManager::new);
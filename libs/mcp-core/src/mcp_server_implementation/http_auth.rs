@@ -0,0 +1,252 @@
+use std::{
+    collections::HashSet,
+    fs,
+    io::Write,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+};
+
+use aes_gcm::aead::{rand_core::RngCore, OsRng};
+use axum::{
+    body::{Body, Bytes},
+    extract::{Request, State},
+    http::{header, Method, StatusCode},
+    middleware::Next,
+    response::{IntoResponse, Response},
+};
+use base64::{engine::general_purpose::URL_SAFE_NO_PAD, Engine};
+use futures::StreamExt;
+use log::{info, warn};
+use serde::{Deserialize, Serialize};
+
+use crate::config::HttpAuthConfig;
+use crate::utils::default_storage_path;
+
+/// Name of the discovery file in the default storage path
+pub const DISCOVERY_FILE_NAME: &str = "discovery.json";
+
+/// Environment variable the proxy reads the token from before the discovery file
+pub const AUTH_TOKEN_ENV: &str = "DOCKMASTER_AUTH_TOKEN";
+
+/// Environment variable the proxy reads the path of the discovery file from
+pub const DISCOVERY_FILE_ENV: &str = "DOCKMASTER_DISCOVERY_FILE";
+
+/// Query parameter carrying the token, for clients that cannot set headers
+pub const TOKEN_QUERY_PARAMETER: &str = "token";
+
+/// Query parameter of the endpoint the SSE stream sends clients to post their messages
+const SESSION_ID_QUERY_PARAMETER: &str = "sessionId";
+
+/// Random bytes of a token
+const TOKEN_BYTES: usize = 32;
+
+/// How local clients find the HTTP server and authenticate to it
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct Discovery {
+    /// Base URL of the HTTP server, such as `http://127.0.0.1:11011`
+    #[serde(default)]
+    pub url: String,
+    pub token: String,
+}
+
+/// Path of the discovery file, see [`HttpAuthConfig::discovery_file`]
+pub fn discovery_file_path(config: &HttpAuthConfig) -> Result<PathBuf, String> {
+    match &config.discovery_file {
+        Some(path) => Ok(path.clone()),
+        None => Ok(default_storage_path()?.join(DISCOVERY_FILE_NAME)),
+    }
+}
+
+/// Read the discovery file
+pub fn read_discovery(path: &Path) -> Result<Discovery, String> {
+    let content = fs::read_to_string(path)
+        .map_err(|e| format!("Failed to read discovery file {}: {e}", path.display()))?;
+    serde_json::from_str(&content)
+        .map_err(|e| format!("Invalid discovery file {}: {e}", path.display()))
+}
+
+/// Write the discovery file, only the current user may read it
+pub fn write_discovery(path: &Path, discovery: &Discovery) -> Result<(), String> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)
+            .map_err(|e| format!("Failed to create discovery file directory: {e}"))?;
+    }
+    let content = serde_json::to_string_pretty(discovery)
+        .map_err(|e| format!("Failed to serialize discovery file: {e}"))?;
+    let mut options = fs::OpenOptions::new();
+    options.write(true).create(true).truncate(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    let mut file = options
+        .open(path)
+        .map_err(|e| format!("Failed to open discovery file {}: {e}", path.display()))?;
+    // The mode only applies to new files, an existing file may be readable by others
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        fs::set_permissions(path, fs::Permissions::from_mode(0o600)).map_err(|e| {
+            format!(
+                "Failed to restrict discovery file {} permissions: {e}",
+                path.display()
+            )
+        })?;
+    }
+    file.write_all(content.as_bytes())
+        .map_err(|e| format!("Failed to write discovery file {}: {e}", path.display()))
+}
+
+/// Random token, URL safe so it fits in the query string
+pub fn generate_token() -> String {
+    let mut bytes = [0u8; TOKEN_BYTES];
+    OsRng.fill_bytes(&mut bytes);
+    URL_SAFE_NO_PAD.encode(bytes)
+}
+
+/// Token of the install, generated and stored in the discovery file on first run
+pub fn load_or_create_token(path: &Path) -> Result<String, String> {
+    match read_discovery(path) {
+        Ok(discovery) if !discovery.token.is_empty() => return Ok(discovery.token),
+        Ok(_) => {}
+        Err(e) if path.exists() => warn!("Replacing the discovery file: {e}"),
+        Err(_) => {}
+    }
+    info!("Creating the HTTP server token in {}", path.display());
+    let discovery = Discovery {
+        url: String::new(),
+        token: generate_token(),
+    };
+    write_discovery(path, &discovery)?;
+    Ok(discovery.token)
+}
+
+/// Value of a query parameter of a request
+fn query_parameter<'a>(request: &'a Request, name: &str) -> Option<&'a str> {
+    request.uri().query()?.split('&').find_map(|pair| {
+        pair.strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('='))
+    })
+}
+
+/// Token a request carries as a Bearer header or as the `token` query parameter
+fn request_token(request: &Request) -> Option<&str> {
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+    if let Some(authorization) = authorization {
+        return authorization.strip_prefix("Bearer ").map(str::trim);
+    }
+    query_parameter(request, TOKEN_QUERY_PARAMETER)
+}
+
+/// Compare tokens in a time that does not depend on where they differ
+fn tokens_match(candidate: &str, token: &str) -> bool {
+    candidate.len() == token.len()
+        && candidate
+            .bytes()
+            .zip(token.bytes())
+            .fold(0, |difference, (a, b)| difference | (a ^ b))
+            == 0
+}
+
+/// Token of the install and the sessions of the SSE streams opened with it
+#[derive(Clone)]
+pub struct HttpAuth {
+    token: Arc<str>,
+    sessions: Arc<Mutex<HashSet<String>>>,
+}
+
+/// Session of an SSE stream opened with the token, forgotten when the stream closes
+struct StreamSession {
+    sessions: Arc<Mutex<HashSet<String>>>,
+    session_id: Option<String>,
+}
+
+impl StreamSession {
+    /// Record the session of the `endpoint` event, the first event of the stream
+    fn record(&mut self, chunk: &Bytes) {
+        let chunk = String::from_utf8_lossy(chunk);
+        let marker = format!("?{SESSION_ID_QUERY_PARAMETER}=");
+        let Some(start) = chunk.find(&marker) else {
+            return;
+        };
+        let session_id: String = chunk[start + marker.len()..]
+            .chars()
+            .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+            .collect();
+        if !session_id.is_empty() {
+            self.sessions.lock().unwrap().insert(session_id.clone());
+            self.session_id = Some(session_id);
+        }
+    }
+}
+
+impl Drop for StreamSession {
+    fn drop(&mut self) {
+        if let Some(session_id) = &self.session_id {
+            self.sessions.lock().unwrap().remove(session_id);
+        }
+    }
+}
+
+impl HttpAuth {
+    pub fn new(token: impl Into<Arc<str>>) -> Self {
+        Self {
+            token: token.into(),
+            sessions: Arc::new(Mutex::new(HashSet::new())),
+        }
+    }
+
+    fn has_session(&self, session_id: &str) -> bool {
+        self.sessions.lock().unwrap().contains(session_id)
+    }
+
+    /// Follow the session of an SSE stream until it closes, other responses are left as they are
+    fn track_session(&self, response: Response) -> Response {
+        let is_event_stream = response
+            .headers()
+            .get(header::CONTENT_TYPE)
+            .is_some_and(|value| value.as_bytes().starts_with(b"text/event-stream"));
+        if !is_event_stream {
+            return response;
+        }
+        let (parts, body) = response.into_parts();
+        let mut session = StreamSession {
+            sessions: self.sessions.clone(),
+            session_id: None,
+        };
+        let stream = body.into_data_stream().inspect(move |chunk| {
+            if let (None, Ok(chunk)) = (&session.session_id, chunk) {
+                session.record(chunk);
+            }
+        });
+        Response::from_parts(parts, Body::from_stream(stream))
+    }
+}
+
+/// Middleware rejecting the requests without the token of the install.
+///
+/// Clients passing the token as a query parameter post their messages to the endpoint the SSE
+/// stream sends them, without the token, so posts to the session of a stream opened with the
+/// token are let through.
+pub async fn require_token(State(auth): State<HttpAuth>, request: Request, next: Next) -> Response {
+    if request_token(&request).is_some_and(|candidate| tokens_match(candidate, &auth.token)) {
+        let response = next.run(request).await;
+        return auth.track_session(response);
+    }
+    let is_session_post = request.method() == Method::POST
+        && query_parameter(&request, SESSION_ID_QUERY_PARAMETER)
+            .is_some_and(|session_id| auth.has_session(session_id));
+    if is_session_post {
+        return next.run(request).await;
+    }
+    (
+        StatusCode::UNAUTHORIZED,
+        [(header::WWW_AUTHENTICATE, "Bearer")],
+        "Missing or invalid token",
+    )
+        .into_response()
+}
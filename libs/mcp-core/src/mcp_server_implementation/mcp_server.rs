@@ -1,36 +1,353 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc, time::Duration};
 
 use rmcp::{
     model::{
-        CallToolRequestParam, CallToolResult, Content, ErrorCode, ListToolsResult,
-        PaginatedRequestParam, ServerCapabilities, ServerInfo, Tool,
+        CallToolRequestParam, CallToolResult, ErrorCode, InitializeRequestParam,
+        InitializeResult, JsonObject, ListResourcesResult, ListToolsResult, PaginatedRequestParam,
+        ReadResourceRequestParam, ReadResourceResult, ServerCapabilities, ServerInfo,
+        SetLevelRequestParam, Tool,
     },
-    service::RequestContext,
+    service::{NotificationContext, RequestContext},
     tool, Error as McpError, RoleServer, ServerHandler,
 };
 
 use crate::{
-    core::{mcp_core::MCPCore, mcp_core_proxy_ext::McpCoreProxyExt},
-    types::ToolExecutionRequest,
+    config::{
+        ApprovalConfig, ArgumentValidationConfig, DiscoveryConfig, OutputLimitConfig,
+        PaginationConfig,
+    },
+    core::{
+        mcp_core::MCPCore, mcp_core_composite_ext::McpCoreCompositeExt,
+        mcp_core_profiles_ext::McpCoreProfilesExt, mcp_core_proxy_ext::McpCoreProxyExt,
+        mcp_core_sessions_ext::McpCoreSessionsExt,
+    },
+    types::{
+        ClientPolicy, CompositeTool, PendingApproval, Profile, SchemaStrictness, ServerToolInfo,
+        ToolExecutionRequest, ToolTarget,
+    },
+    utils::{
+        argument_validation::{format_violations, ArgumentViolation},
+        unix_timestamp,
+    },
 };
 
 use super::{
-    get_configure_server_tool, get_register_server_tool, get_search_server_tool,
+    approvals::APPROVALS,
+    pagination::paginate,
+    result_store::RESULT_STORE,
+    session_manager::SESSION_MANAGER,
+    tool_budget::fit_token_budget,
     tools::{
-        get_list_installed_servers_tool, get_tool_names, get_uninstall_server_tool,
-        handle_configure_server, handle_list_installed_servers, handle_register_server,
-        handle_search_server, handle_uninstall_server,
+        get_builtin_tools, get_tool_names, handle_configure_server, handle_find_tools,
+        handle_list_installed_servers, handle_list_server_tools, handle_register_server,
+        handle_restart_server, handle_search_server, handle_server_logs, handle_server_status,
+        handle_set_server_enabled, handle_set_tool_active, handle_uninstall_server,
+        parse_call_tool_request, server_id_argument, server_not_found,
     },
 };
 
+/// Error of a call whose arguments do not satisfy the tool's input schema
+fn invalid_arguments(tool_name: &str, violations: &[ArgumentViolation]) -> McpError {
+    McpError::invalid_params(
+        format!(
+            "Invalid arguments for tool '{tool_name}':\n{}",
+            format_violations(violations)
+        ),
+        serde_json::to_value(violations).ok(),
+    )
+}
+
+/// One instance per SSE connection
 pub struct McpServer {
     mcp_core: Arc<MCPCore>,
+    /// Key of this connection in `SESSION_MANAGER`
+    session_id: String,
+    /// Profile the connection was opened for, `None` exposes every tool
+    profile: Option<String>,
 }
 
 #[tool(tool_box)]
 impl McpServer {
     pub fn new(mcp_core: Arc<MCPCore>) -> Self {
-        Self { mcp_core }
+        Self::with_profile(mcp_core, None)
+    }
+
+    /// Server exposing only the servers and tools of `profile`
+    pub fn with_profile(mcp_core: Arc<MCPCore>, profile: Option<String>) -> Self {
+        Self {
+            mcp_core,
+            session_id: uuid::Uuid::new_v4().to_string(),
+            profile,
+        }
+    }
+
+    /// Current definition of the connection's profile, it may have changed since connecting
+    async fn profile(&self) -> Result<Option<Profile>, McpError> {
+        match &self.profile {
+            Some(name) => self
+                .mcp_core
+                .get_profile(name)
+                .await
+                .map(Some)
+                .map_err(|e| McpError::invalid_request(e, None)),
+            None => Ok(None),
+        }
+    }
+
+    /// Client policy of the connection's profile, if one applies to it.
+    ///
+    /// A policy that cannot be read refuses the request rather than leaving the client
+    /// unrestricted.
+    async fn client_policy(&self) -> Result<Option<ClientPolicy>, McpError> {
+        self.mcp_core
+            .client_policy(self.profile.as_deref())
+            .await
+            .map_err(|e| McpError::internal_error(e, None))
+    }
+
+    /// Active proxied tools this connection may see, after its profile and client policy
+    async fn visible_server_tools(&self) -> Result<Vec<ServerToolInfo>, McpError> {
+        let server_tools = self.mcp_core.list_all_server_tools().await;
+        let mut server_tools = server_tools.map_err(|e| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                "Failed to list tools",
+                Some(e.into()),
+            )
+        })?;
+        if let Some(profile) = self.profile().await? {
+            server_tools.retain(|tool| profile.includes(tool));
+        }
+        if let Some(policy) = self.client_policy().await? {
+            server_tools.retain(|tool| policy.allows_server(&tool.server_id));
+        }
+        Ok(server_tools)
+    }
+
+    /// Whether this connection may see a server, after its profile and client policy
+    async fn server_visibility(&self) -> Result<impl Fn(&str) -> bool, McpError> {
+        let profile = self.profile().await?;
+        let policy = self.client_policy().await?;
+        Ok(move |server_id: &str| {
+            profile
+                .as_ref()
+                .is_none_or(|profile| profile.includes_server(server_id))
+                && policy
+                    .as_ref()
+                    .is_none_or(|policy| policy.allows_server(server_id))
+        })
+    }
+
+    /// Refuse a server this connection may not see as if it did not exist
+    async fn require_visible_server(&self, server_id: &str) -> Result<(), McpError> {
+        match self.server_visibility().await?(server_id) {
+            true => Ok(()),
+            false => Err(server_not_found(server_id)),
+        }
+    }
+
+    /// Run a composite tool this connection may see, its arguments checked before the client
+    /// policy counts the call
+    async fn call_composite_tool(
+        &self,
+        name: &str,
+        arguments: Option<JsonObject>,
+    ) -> Result<CallToolResult, McpError> {
+        let server_tools = self.visible_server_tools().await?;
+        let composite = self
+            .visible_composite_tools(&server_tools)
+            .await
+            .into_iter()
+            .find(|composite| composite.name == name)
+            .ok_or_else(|| {
+                McpError::new(
+                    ErrorCode::METHOD_NOT_FOUND,
+                    format!("Tool '{name}' not found"),
+                    None,
+                )
+            })?;
+        let arguments = composite
+            .prepare_arguments(arguments, &ArgumentValidationConfig::from_env())
+            .map_err(|violations| invalid_arguments(name, &violations))?;
+
+        let mut server_ids: Vec<&str> = composite
+            .steps
+            .iter()
+            .map(|step| step.server_id.as_str())
+            .collect();
+        server_ids.sort_unstable();
+        server_ids.dedup();
+        self.admit_tool_call(&server_ids, name, &arguments).await?;
+
+        let calls: Vec<_> = server_ids
+            .iter()
+            .map(|server_id| SESSION_MANAGER.begin_call(server_id, &self.session_id))
+            .collect();
+        let result = self.mcp_core.execute_composite_tool(name, arguments).await;
+        drop(calls);
+        result.map_err(|e| {
+            McpError::new(
+                ErrorCode::INTERNAL_ERROR,
+                "Failed to execute tool",
+                Some(e.into()),
+            )
+        })
+    }
+
+    /// Composite tools whose steps only call tools among `server_tools`
+    async fn visible_composite_tools(&self, server_tools: &[ServerToolInfo]) -> Vec<CompositeTool> {
+        let composite_tools = self
+            .mcp_core
+            .list_composite_tools()
+            .await
+            .unwrap_or_default();
+        composite_tools
+            .into_iter()
+            .filter(|composite| {
+                composite.steps.iter().all(|step| {
+                    server_tools.iter().any(|tool| {
+                        tool.server_id == step.server_id && tool.matches_id(&step.tool_id)
+                    })
+                })
+            })
+            .collect()
+    }
+
+    /// Normalization of the listed input schemas, the client's policy takes precedence over
+    /// the profile
+    async fn schema_strictness(&self) -> Result<SchemaStrictness, McpError> {
+        let policy = self.client_policy().await?;
+        if let Some(strictness) = policy.and_then(|policy| policy.schema_strictness) {
+            return Ok(strictness);
+        }
+        let profile = self.profile().await?;
+        Ok(profile
+            .and_then(|profile| profile.schema_strictness)
+            .unwrap_or_default())
+    }
+
+    /// Every tool this connection may call, in a deterministic order so cursors stay valid
+    async fn available_tools(&self) -> Result<Vec<Tool>, McpError> {
+        let mut server_tools = self.visible_server_tools().await?;
+        let strictness = self.schema_strictness().await?;
+        let composite_tools = self.visible_composite_tools(&server_tools).await;
+        server_tools.sort_by(|a, b| {
+            a.exposed_name()
+                .cmp(b.exposed_name())
+                .then(a.server_id.cmp(&b.server_id))
+        });
+        // Composite tools are called in place of proxied tools with the same name
+        server_tools.retain(|tool| {
+            !composite_tools
+                .iter()
+                .any(|composite| composite.name == tool.exposed_name())
+        });
+        let server_tools = server_tools
+            .into_iter()
+            .map(|tool| tool.to_tool(strictness))
+            .collect::<Result<Vec<_>, _>>()
+            .map_err(|e| McpError::internal_error(format!("Invalid tool definition: {e}"), None))?;
+        Ok(get_builtin_tools()
+            .into_iter()
+            .chain(
+                composite_tools
+                    .iter()
+                    .map(|composite| composite.to_tool(strictness)),
+            )
+            .chain(server_tools)
+            .collect())
+    }
+
+    /// Refuse a tool call the client policy does not allow, or that the user did not approve
+    async fn enforce_client_policy(
+        &self,
+        server_ids: &[&str],
+        tool_name: &str,
+        arguments: &Option<JsonObject>,
+    ) -> Result<(), McpError> {
+        let Some(policy) = self.client_policy().await? else {
+            return Ok(());
+        };
+
+        if let Some(server_id) = server_ids.iter().find(|id| !policy.allows_server(id)) {
+            return Err(McpError::invalid_request(
+                format!(
+                    "The client policy of profile '{}' does not allow server '{server_id}'",
+                    policy.profile
+                ),
+                None,
+            ));
+        }
+
+        // The connections of a profile share its limit
+        if let Some(max_calls_per_minute) = policy.max_calls_per_minute {
+            if !SESSION_MANAGER
+                .try_acquire_call(&policy.profile, max_calls_per_minute)
+                .await
+            {
+                return Err(McpError::invalid_request(
+                    format!(
+                        "Profile '{}' exceeded its limit of {max_calls_per_minute} tool calls per minute",
+                        policy.profile
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        if policy.require_approval {
+            let client_name = SESSION_MANAGER
+                .client_name(&self.session_id)
+                .await
+                .unwrap_or_default();
+            let approval = PendingApproval {
+                id: uuid::Uuid::new_v4().to_string(),
+                session_id: self.session_id.clone(),
+                client_name,
+                server_id: server_ids.join(", "),
+                tool_name: tool_name.to_string(),
+                arguments: arguments.clone(),
+                requested_at: unix_timestamp(),
+            };
+            let timeout = Duration::from_secs(ApprovalConfig::from_env().timeout_secs);
+            if !APPROVALS.request_approval(approval, timeout).await {
+                return Err(McpError::invalid_request(
+                    format!(
+                        "Call of tool '{tool_name}' of server '{}' was not approved",
+                        server_ids.join(", ")
+                    ),
+                    None,
+                ));
+            }
+        }
+
+        Ok(())
+    }
+
+    /// Admit a tool call the client policy allows, only admitted calls count for the session
+    async fn admit_tool_call(
+        &self,
+        server_ids: &[&str],
+        tool_name: &str,
+        arguments: &Option<JsonObject>,
+    ) -> Result<(), McpError> {
+        self.enforce_client_policy(server_ids, tool_name, arguments)
+            .await?;
+        SESSION_MANAGER.record_tool_call(&self.session_id).await;
+        Ok(())
+    }
+}
+
+impl Drop for McpServer {
+    fn drop(&mut self) {
+        // The handler is dropped when the connection closes, stop notifying it
+        if let Ok(handle) = tokio::runtime::Handle::try_current() {
+            let session_id = std::mem::take(&mut self.session_id);
+            handle.spawn(async move {
+                SESSION_MANAGER.remove_session(&session_id).await;
+                RESULT_STORE.remove_session(&session_id).await;
+            });
+        }
     }
 }
 
@@ -39,144 +356,401 @@ impl ServerHandler for McpServer {
         ServerInfo {
             instructions: Some("Dockmaster MCP Server".into()),
             capabilities: ServerCapabilities::builder()
+                .enable_logging()
                 .enable_tools()
                 .enable_tool_list_changed()
                 .enable_prompts()
+                .enable_prompts_list_changed()
                 .enable_resources()
+                .enable_resources_list_changed()
                 .build(),
             ..Default::default()
         }
     }
 
+    async fn initialize(
+        &self,
+        request: InitializeRequestParam,
+        context: RequestContext<RoleServer>,
+    ) -> Result<InitializeResult, McpError> {
+        if context.peer.peer_info().is_none() {
+            context.peer.set_peer_info(request.clone());
+        }
+        // Keep the peer so notifications and server requests can be pushed to this client
+        SESSION_MANAGER
+            .register_session(
+                self.session_id.clone(),
+                context.peer,
+                request,
+                self.profile.clone(),
+            )
+            .await;
+        Ok(self.get_info())
+    }
+
+    async fn on_roots_list_changed(&self, _context: NotificationContext<RoleServer>) {
+        let mcp_state = self.mcp_core.mcp_state.read().await;
+        mcp_state.notify_roots_list_changed().await;
+    }
+
+    async fn set_level(
+        &self,
+        request: SetLevelRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<(), McpError> {
+        SESSION_MANAGER
+            .set_log_level(&self.session_id, request.level)
+            .await
+            .map_err(|e| McpError::invalid_request(e, None))
+    }
+
     async fn list_tools(
         &self,
-        _request: Option<PaginatedRequestParam>,
+        request: Option<PaginatedRequestParam>,
         _context: RequestContext<RoleServer>,
     ) -> Result<ListToolsResult, McpError> {
-        let server_tools = self.mcp_core.list_all_server_tools().await;
-        let server_tools = server_tools.map_err(|e| {
-            McpError::new(
-                ErrorCode::INTERNAL_ERROR,
-                "Failed to list tools",
-                Some(e.into()),
-            )
-        })?;
-        let tools: Vec<Tool> = vec![
-            get_register_server_tool(),
-            get_search_server_tool(),
-            get_configure_server_tool(),
-            get_list_installed_servers_tool(),
-            get_uninstall_server_tool(),
-        ]
-        .into_iter()
-        .chain(server_tools.into_iter().map(|tool| tool.to_tool().unwrap()))
-        .collect();
-        Ok(ListToolsResult {
+        let mut tools = self.available_tools().await?;
+
+        // In discovery mode clients find the other tools with find_tools
+        let discovery = DiscoveryConfig::from_env();
+        if discovery.enabled {
+            let tool_names = get_tool_names();
+            let mounted_tools = SESSION_MANAGER.mounted_tools(&self.session_id).await;
+            tools.retain(|tool| {
+                tool.name == tool_names.find_tools
+                    || tool.name == tool_names.call_tool
+                    || discovery.pinned_tools.iter().any(|name| *name == tool.name)
+                    || mounted_tools.contains(tool.name.as_ref())
+            });
+        }
+
+        // Proxied tools are minified or dropped past the profile's budget, by profile priority
+        if let Some(profile) = self.profile().await? {
+            if let Some(token_budget) = profile.token_budget {
+                let priorities: HashMap<String, usize> = self
+                    .visible_server_tools()
+                    .await?
+                    .iter()
+                    .map(|tool| (tool.exposed_name().to_string(), profile.priority(tool)))
+                    .collect();
+                tools = fit_token_budget(tools, token_budget as usize, |tool| {
+                    priorities.get(tool.name.as_ref()).copied()
+                });
+            }
+        }
+
+        let cursor = request.as_ref().and_then(|r| r.cursor.as_deref());
+        let page = paginate(
             tools,
-            next_cursor: None,
+            |tool| tool.name.as_ref(),
+            cursor,
+            PaginationConfig::from_env().page_size,
+        )?;
+        Ok(ListToolsResult {
+            tools: page.items,
+            next_cursor: page.next_cursor,
         })
     }
 
     async fn call_tool(
         &self,
         request: CallToolRequestParam,
-        _: RequestContext<RoleServer>,
+        context: RequestContext<RoleServer>,
     ) -> Result<CallToolResult, McpError> {
         // Get the current tool names from configuration
-        let (tool_register, tool_search, tool_configure, tool_uninstall, tool_list) = get_tool_names();
-        
-        match request.name.clone().to_string().as_str() {
-            name if name == tool_register => {
+        let tool_names = get_tool_names();
+        let discovery_enabled = DiscoveryConfig::from_env().enabled;
+
+        // call_tool stands for the tool it names
+        let request = if discovery_enabled
+            && request.name == tool_names.call_tool
+            && !tool_names.is_disabled(&tool_names.call_tool)
+        {
+            parse_call_tool_request(request.arguments.unwrap_or_default())?
+        } else {
+            request
+        };
+
+        let tool_name = request.name.to_string();
+        // Other tools are resolved among those this connection may see, composite tools are
+        // called in place of proxied tools with the same name, as listed
+        let (is_composite, visible_tool) = if tool_names.contains(&tool_name) {
+            (false, None)
+        } else {
+            let server_tools = self.visible_server_tools().await?;
+            let is_composite = self
+                .visible_composite_tools(&server_tools)
+                .await
+                .iter()
+                .any(|composite| composite.name == tool_name);
+            let visible_tool = server_tools
+                .into_iter()
+                .find(|tool| !is_composite && tool.exposed_name() == tool_name);
+            (is_composite, visible_tool)
+        };
+        let server_id = visible_tool.as_ref().map(|tool| tool.server_id.clone());
+
+        // Built-ins acting on a server are held to the profile and client policy like its tools,
+        // the other built-ins are admitted as is
+        if tool_names.contains(&tool_name) && !tool_names.is_disabled(&tool_name) {
+            match server_id_argument(&request.arguments) {
+                Some(server_id) if tool_names.changes_server(&tool_name) => {
+                    self.require_visible_server(server_id).await?;
+                    self.admit_tool_call(&[server_id], &tool_name, &request.arguments)
+                        .await?;
+                }
+                Some(server_id) if tool_names.reads_server(&tool_name) => {
+                    self.require_visible_server(server_id).await?;
+                    SESSION_MANAGER.record_tool_call(&self.session_id).await;
+                }
+                _ => SESSION_MANAGER.record_tool_call(&self.session_id).await,
+            }
+        }
+
+        let result = match request.name.clone().to_string().as_str() {
+            name if tool_names.is_disabled(name) => Err(McpError::new(
+                ErrorCode::METHOD_NOT_FOUND,
+                format!("Tool '{name}' not found"),
+                None,
+            )),
+            name if discovery_enabled && name == tool_names.find_tools => {
+                let tools: Vec<Tool> = self
+                    .available_tools()
+                    .await?
+                    .into_iter()
+                    .filter(|tool| {
+                        tool.name != tool_names.find_tools && tool.name != tool_names.call_tool
+                    })
+                    .collect();
+                let (result, mount) =
+                    handle_find_tools(tools, request.arguments.unwrap_or_default()).await?;
+                if SESSION_MANAGER.mount_tools(&self.session_id, mount).await {
+                    if let Err(e) = context.peer.notify_tool_list_changed().await {
+                        log::debug!(
+                            "Failed to notify session {} of mounted tools: {e}",
+                            self.session_id
+                        );
+                    }
+                }
+                Ok(result)
+            }
+            name if name == tool_names.register_server => {
                 handle_register_server(
                     self.mcp_core.clone(),
                     request.arguments.clone().unwrap_or_default(),
                 )
                 .await
             }
-            name if name == tool_search => handle_search_server(request.arguments.unwrap_or_default()).await,
-            name if name == tool_configure => {
+            name if name == tool_names.search_server => handle_search_server(request.arguments.unwrap_or_default()).await,
+            name if name == tool_names.configure_server => {
                 handle_configure_server(
                     self.mcp_core.clone(),
                     request.arguments.unwrap_or_default(),
                 )
                 .await
             }
-            name if name == tool_uninstall => {
+            name if name == tool_names.uninstall_server => {
                 handle_uninstall_server(
                     self.mcp_core.clone(),
                     request.arguments.unwrap_or_default(),
                 )
                 .await
             }
-            name if name == tool_list => {
-                handle_list_installed_servers(self.mcp_core.clone()).await
+            name if name == tool_names.list_installed_servers => {
+                let is_visible = self.server_visibility().await?;
+                handle_list_installed_servers(self.mcp_core.clone(), is_visible).await
             }
-            _ => {
-                // For non-built-in tools, find the appropriate server that has this tool
-                let mcp_state = self.mcp_core.mcp_state.read().await;
-                let server_tools = mcp_state.server_tools.read().await;
-
-                // Find which server has the requested tool
-                let mut server_id = None;
+            name if name == tool_names.set_tool_active => {
+                handle_set_tool_active(
+                    self.mcp_core.clone(),
+                    request.arguments.unwrap_or_default(),
+                )
+                .await
+            }
+            name if name == tool_names.server_status => {
+                handle_server_status(
+                    self.mcp_core.clone(),
+                    request.arguments.unwrap_or_default(),
+                )
+                .await
+            }
+            name if name == tool_names.restart_server => {
+                handle_restart_server(
+                    self.mcp_core.clone(),
+                    request.arguments.unwrap_or_default(),
+                )
+                .await
+            }
+            name if name == tool_names.enable_server => {
+                handle_set_server_enabled(
+                    self.mcp_core.clone(),
+                    request.arguments.unwrap_or_default(),
+                    true,
+                )
+                .await
+            }
+            name if name == tool_names.disable_server => {
+                handle_set_server_enabled(
+                    self.mcp_core.clone(),
+                    request.arguments.unwrap_or_default(),
+                    false,
+                )
+                .await
+            }
+            name if name == tool_names.server_logs => {
+                handle_server_logs(
+                    self.mcp_core.clone(),
+                    request.arguments.unwrap_or_default(),
+                )
+                .await
+            }
+            name if name == tool_names.list_server_tools => {
+                // A profile may include only some tools of the server
+                let profile = self.profile().await?;
+                handle_list_server_tools(
+                    self.mcp_core.clone(),
+                    request.arguments.unwrap_or_default(),
+                    |tool| profile.as_ref().is_none_or(|profile| profile.includes(tool)),
+                )
+                .await
+            }
+            _ if is_composite => {
+                self.call_composite_tool(&request.name, request.arguments)
+                    .await
+            }
+            _ => match visible_tool {
+                Some(tool) => {
+                    // Refuse invalid arguments before they count against the rate limit or
+                    // wait for approval
+                    let arguments = tool
+                        .prepare_arguments(request.arguments, &ArgumentValidationConfig::from_env())
+                        .map_err(|violations| invalid_arguments(&request.name, &violations))?;
+                    let target = ToolTarget {
+                        server_id: tool.server_id,
+                        tool_name: tool.name,
+                    };
+                    self.admit_tool_call(&[&target.server_id], &target.tool_name, &arguments)
+                        .await?;
 
-                for (sid, tools) in &*server_tools {
-                    for tool in tools {
-                        if tool.name == request.name {
-                            server_id = Some(sid.clone());
-                            break;
-                        }
-                    }
+                    // Relay the progress of the upstream call to this client
+                    let progress = context.meta.get_progress_token().map(|progress_token| {
+                        SESSION_MANAGER.register_progress(context.peer.clone(), progress_token)
+                    });
+                    let server_id = target.server_id.clone();
+                    let request = ToolExecutionRequest {
+                        target,
+                        parameters: arguments,
+                        progress_token: progress
+                            .as_ref()
+                            .map(|progress| progress.upstream_token().clone()),
+                    };
 
-                    if server_id.is_some() {
-                        break;
-                    }
-                }
+                    // Sampling and roots requests the server sends meanwhile go to this client
+                    let call = SESSION_MANAGER.begin_call(&server_id, &self.session_id);
+                    let result = self.mcp_core.execute_prepared_proxy_tool(request).await;
+                    drop(call);
+                    drop(progress);
 
-                // Drop the locks before proceeding
-                drop(server_tools);
-                drop(mcp_state);
-
-                match server_id {
-                    Some(server_id) => {
-                        let request = ToolExecutionRequest {
-                            tool_id: format!("{}:{}", server_id, request.name),
-                            parameters: request.arguments,
-                        };
-
-                        match self.mcp_core.execute_proxy_tool(request).await {
-                            Ok(response) => {
-                                if response.success {
-                                    Ok(CallToolResult {
-                                        content: vec![Content::text(
-                                            serde_json::to_string(&response.result)
-                                                .unwrap_or_default(),
-                                        )],
-                                        is_error: Some(false),
-                                    })
-                                } else {
-                                    Err(McpError::new(
+                    match result {
+                        Ok(response) => {
+                            if response.success {
+                                // Hand back the upstream result as is, including its
+                                // non-text content and `is_error`
+                                serde_json::from_value::<CallToolResult>(
+                                    response.result.unwrap_or_default(),
+                                )
+                                .map_err(|e| {
+                                    McpError::new(
                                         ErrorCode::INTERNAL_ERROR,
-                                        "Failed to execute tool",
-                                        Some(serde_json::Value::String(
-                                            response.error.unwrap_or("Unknown error".to_string()),
-                                        )),
-                                    ))
-                                }
+                                        "Invalid tool result",
+                                        Some(serde_json::Value::String(e.to_string())),
+                                    )
+                                })
+                            } else {
+                                Err(McpError::new(
+                                    ErrorCode::INTERNAL_ERROR,
+                                    "Failed to execute tool",
+                                    Some(serde_json::Value::String(
+                                        response.error.unwrap_or("Unknown error".to_string()),
+                                    )),
+                                ))
                             }
-                            Err(e) => Err(McpError::new(
-                                ErrorCode::INTERNAL_ERROR,
-                                "Failed to execute tool",
-                                Some(e.into()),
-                            )),
                         }
+                        Err(e) => Err(McpError::new(
+                            ErrorCode::INTERNAL_ERROR,
+                            "Failed to execute tool",
+                            Some(e.into()),
+                        )),
                     }
-                    None => Err(McpError::new(
-                        ErrorCode::METHOD_NOT_FOUND,
-                        format!("Tool '{}' not found", request.name),
-                        None,
-                    )),
                 }
-            }
-        }
+                None => {
+                    // Tools outside the connection's profile do not exist for it
+                    let profile = self.profile().await?;
+                    let is_visible = self.server_visibility().await?;
+                    let inactive_tool = {
+                        let mcp_state = self.mcp_core.mcp_state.read().await;
+                        mcp_state
+                            .find_inactive_tool(&request.name)
+                            .await
+                            .filter(|tool| {
+                                is_visible(&tool.server_id)
+                                    && profile.as_ref().is_none_or(|p| p.includes(tool))
+                            })
+                    };
+                    match inactive_tool {
+                        Some(tool) => Err(McpError::invalid_request(
+                            format!(
+                                "Tool '{}' of server '{}' is disabled",
+                                request.name, tool.server_id
+                            ),
+                            None,
+                        )),
+                        None => Err(McpError::new(
+                            ErrorCode::METHOD_NOT_FOUND,
+                            format!("Tool '{}' not found", request.name),
+                            None,
+                        )),
+                    }
+                }
+            },
+        };
+
+        // Oversized output is cut short, the client reads the rest as a resource
+        let output_limits = OutputLimitConfig::from_env();
+        Ok(RESULT_STORE
+            .limit_output(
+                &self.session_id,
+                &tool_name,
+                result?,
+                output_limits.max_bytes_for(server_id.as_deref()),
+                output_limits.chunk_bytes,
+            )
+            .await)
+    }
+
+    async fn list_resources(
+        &self,
+        _request: Option<PaginatedRequestParam>,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ListResourcesResult, McpError> {
+        Ok(ListResourcesResult {
+            resources: RESULT_STORE.list(&self.session_id).await,
+            next_cursor: None,
+        })
+    }
+
+    async fn read_resource(
+        &self,
+        request: ReadResourceRequestParam,
+        _context: RequestContext<RoleServer>,
+    ) -> Result<ReadResourceResult, McpError> {
+        RESULT_STORE
+            .read(
+                &self.session_id,
+                &request.uri,
+                OutputLimitConfig::from_env().chunk_bytes,
+            )
+            .await
+            .map_err(|e| McpError::resource_not_found(e, None))
     }
 }
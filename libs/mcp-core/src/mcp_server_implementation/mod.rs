@@ -1,7 +1,14 @@
+pub mod approvals;
+pub mod http_auth;
 pub mod mcp_server;
 pub mod notifications;
+pub mod pagination;
+pub mod profile_routes;
 pub mod registry_cache;
+pub mod result_store;
 pub mod session_manager;
+pub mod tool_budget;
+pub mod tool_search;
 pub mod tools;
 
 pub use self::session_manager::SESSION_MANAGER;
@@ -1,17 +1,87 @@
-use super::session_manager::SESSION_MANAGER;
-use serde_json::json;
+use std::future::Future;
 
-pub async fn broadcast_tools_list_changed() {
-    let notification = json!({
-        "jsonrpc": "2.0",
-        "method": "notifications/tools/list_changed",
-        "params": {}
-    });
+use rmcp::model::LoggingMessageNotificationParam;
+use rmcp::{Peer, RoleServer, ServiceError};
 
-    let message = serde_json::to_string(&notification).unwrap_or_default();
-    let failed = SESSION_MANAGER.broadcast_message(&message).await;
+use super::session_manager::SESSION_MANAGER;
+
+/// Send a notification to the given sessions, dropping the sessions whose transport is gone
+async fn notify_sessions<F, Fut>(
+    notification: &str,
+    peers: Vec<(String, Peer<RoleServer>)>,
+    send: F,
+) where
+    F: Fn(Peer<RoleServer>) -> Fut,
+    Fut: Future<Output = Result<(), ServiceError>>,
+{
+    let mut failed = Vec::new();
+    for (session_id, peer) in peers {
+        if let Err(e) = send(peer).await {
+            log::debug!("Failed to send {notification} to session {session_id}: {e}");
+            failed.push(session_id);
+        }
+    }
 
     if !failed.is_empty() {
-        log::warn!("Failed to send tools list changed notification to some sessions: {failed:?}");
+        log::warn!("Failed to send {notification} notification to some sessions: {failed:?}");
+        for session_id in &failed {
+            SESSION_MANAGER.remove_session(session_id).await;
+        }
     }
 }
+
+pub async fn broadcast_tools_list_changed() {
+    notify_sessions("tools list changed", SESSION_MANAGER.peers().await, |peer| async move {
+        peer.notify_tool_list_changed().await
+    })
+    .await
+}
+
+pub async fn broadcast_resources_list_changed() {
+    notify_sessions("resources list changed", SESSION_MANAGER.peers().await, |peer| async move {
+        peer.notify_resource_list_changed().await
+    })
+    .await
+}
+
+pub async fn broadcast_prompts_list_changed() {
+    notify_sessions("prompts list changed", SESSION_MANAGER.peers().await, |peer| async move {
+        peer.notify_prompt_list_changed().await
+    })
+    .await
+}
+
+/// Tell a single session its resources changed, such as when one of its results was stored
+pub async fn notify_resources_list_changed(session_id: &str) {
+    let peers = SESSION_MANAGER
+        .get_peer(session_id)
+        .await
+        .map(|peer| (session_id.to_string(), peer))
+        .into_iter()
+        .collect();
+    notify_sessions("resources list changed", peers, |peer| async move {
+        peer.notify_resource_list_changed().await
+    })
+    .await
+}
+
+/// A server started or stopped, so every aggregated list may have changed
+pub async fn broadcast_server_lists_changed() {
+    broadcast_tools_list_changed().await;
+    broadcast_resources_list_changed().await;
+    broadcast_prompts_list_changed().await;
+}
+
+/// Forward a log message to the sessions whose `logging/setLevel` lets it through and that
+/// `accepts`, given the profile of the session
+pub async fn broadcast_log_message(
+    params: LoggingMessageNotificationParam,
+    accepts: impl Fn(Option<&str>) -> bool,
+) {
+    let peers = SESSION_MANAGER.peers_for_log_level(params.level, accepts).await;
+    notify_sessions("log message", peers, |peer| {
+        let params = params.clone();
+        async move { peer.notify_logging_message(params).await }
+    })
+    .await
+}
@@ -0,0 +1,84 @@
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+use rmcp::Error as McpError;
+
+/// A single page of a list response and the cursor of the following page, if any
+#[derive(Debug)]
+pub struct Page<T> {
+    pub items: Vec<T>,
+    pub next_cursor: Option<String>,
+}
+
+/// Fingerprint of an ordered item list, a cursor is only valid for the list it was issued for
+fn fingerprint<T>(items: &[T], key: impl Fn(&T) -> &str) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    items.len().hash(&mut hasher);
+    for item in items {
+        key(item).hash(&mut hasher);
+    }
+    hasher.finish()
+}
+
+fn encode_cursor(fingerprint: u64, offset: usize) -> String {
+    format!("{fingerprint:016x}{offset:x}")
+}
+
+fn decode_cursor(cursor: &str) -> Option<(u64, usize)> {
+    if cursor.len() <= 16 || !cursor.is_ascii() {
+        return None;
+    }
+    let (fingerprint, offset) = cursor.split_at(16);
+    Some((
+        u64::from_str_radix(fingerprint, 16).ok()?,
+        usize::from_str_radix(offset, 16).ok()?,
+    ))
+}
+
+/// Slice an ordered list into pages addressed by opaque cursors.
+///
+/// Cursors stay valid as long as the list keeps the same items in the same order; once it
+/// changes, old cursors are rejected with `invalid_params` so the client restarts the listing.
+/// A `page_size` of 0 returns everything from the cursor onwards in a single page.
+pub fn paginate<T>(
+    items: Vec<T>,
+    key: impl Fn(&T) -> &str,
+    cursor: Option<&str>,
+    page_size: usize,
+) -> Result<Page<T>, McpError> {
+    let list_fingerprint = fingerprint(&items, key);
+
+    let offset = match cursor {
+        None => 0,
+        Some(cursor) => match decode_cursor(cursor) {
+            Some((cursor_fingerprint, offset))
+                if cursor_fingerprint == list_fingerprint && offset <= items.len() =>
+            {
+                offset
+            }
+            Some(_) => {
+                return Err(McpError::invalid_params(
+                    "Cursor is no longer valid because the list changed, request the first page again",
+                    Some(serde_json::Value::String(cursor.to_string())),
+                ))
+            }
+            None => {
+                return Err(McpError::invalid_params(
+                    "Invalid cursor",
+                    Some(serde_json::Value::String(cursor.to_string())),
+                ))
+            }
+        },
+    };
+
+    let total = items.len();
+    let end = if page_size == 0 {
+        total
+    } else {
+        offset.saturating_add(page_size).min(total)
+    };
+    let next_cursor = (end < total).then(|| encode_cursor(list_fingerprint, end));
+    let items = items.into_iter().skip(offset).take(end - offset).collect();
+
+    Ok(Page { items, next_cursor })
+}
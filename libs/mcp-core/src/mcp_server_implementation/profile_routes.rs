@@ -0,0 +1,84 @@
+use std::{collections::HashMap, net::SocketAddr, sync::Arc, time::Duration};
+
+use axum::{
+    extract::{Path, Request, State},
+    http::StatusCode,
+    response::{IntoResponse, Response},
+    routing::any,
+    Router,
+};
+use log::info;
+use rmcp::transport::{sse_server::SseServerConfig, SseServer};
+use tokio::sync::Mutex;
+use tokio_util::sync::CancellationToken;
+use tower::ServiceExt;
+
+use crate::core::{mcp_core::MCPCore, mcp_core_profiles_ext::McpCoreProfilesExt};
+
+use super::mcp_server::McpServer;
+
+/// SSE endpoints of the profiles, created the first time a client connects to a profile
+struct ProfileEndpoints {
+    mcp_core: Arc<MCPCore>,
+    bind: SocketAddr,
+    ct: CancellationToken,
+    routers: Mutex<HashMap<String, Router>>,
+}
+
+impl ProfileEndpoints {
+    /// Router serving `/sse/{profile}` and `/post/{profile}`
+    async fn router(&self, profile: &str) -> Result<Router, String> {
+        // Checked on every request so deleted profiles stop accepting connections
+        self.mcp_core.get_profile(profile).await?;
+
+        let mut routers = self.routers.lock().await;
+        if let Some(router) = routers.get(profile) {
+            return Ok(router.clone());
+        }
+
+        info!("Serving profile '{profile}' at /sse/{profile}");
+        let (sse_server, router) = SseServer::new(SseServerConfig {
+            bind: self.bind,
+            sse_path: format!("/sse/{profile}"),
+            post_path: format!("/post/{profile}"),
+            ct: self.ct.child_token(),
+            sse_keep_alive: Some(Duration::from_secs(30)),
+        });
+        let mcp_core = self.mcp_core.clone();
+        let profile_name = profile.to_string();
+        sse_server.with_service(move || {
+            McpServer::with_profile(mcp_core.clone(), Some(profile_name.clone()))
+        });
+
+        routers.insert(profile.to_string(), router.clone());
+        Ok(router)
+    }
+}
+
+async fn profile_endpoint(
+    State(endpoints): State<Arc<ProfileEndpoints>>,
+    Path(profile): Path<String>,
+    request: Request,
+) -> Response {
+    match endpoints.router(&profile).await {
+        Ok(router) => match router.oneshot(request).await {
+            Ok(response) => response,
+            Err(never) => match never {},
+        },
+        Err(e) => (StatusCode::NOT_FOUND, e).into_response(),
+    }
+}
+
+/// Routes serving each profile at `/sse/{profile}`, with its messages posted to `/post/{profile}`
+pub fn profile_router(mcp_core: Arc<MCPCore>, bind: SocketAddr, ct: CancellationToken) -> Router {
+    let endpoints = Arc::new(ProfileEndpoints {
+        mcp_core,
+        bind,
+        ct,
+        routers: Mutex::new(HashMap::new()),
+    });
+    Router::new()
+        .route("/sse/{profile}", any(profile_endpoint))
+        .route("/post/{profile}", any(profile_endpoint))
+        .with_state(endpoints)
+}
@@ -0,0 +1,211 @@
+use std::collections::VecDeque;
+
+use log::info;
+use once_cell::sync::Lazy;
+use rmcp::model::{
+    Annotated, CallToolResult, Content, RawResource, ReadResourceResult, Resource, ResourceContents,
+};
+use tokio::sync::Mutex as TokioMutex;
+use uuid::Uuid;
+
+use super::notifications::notify_resources_list_changed;
+use crate::config::OutputLimitConfig;
+
+/// URI prefix of the resources holding the full output of truncated tool results
+pub const RESULT_URI_PREFIX: &str = "dockmaster://results/";
+
+/// Spilled results kept at most, the oldest are dropped first
+const MAX_STORED_RESULTS: usize = 50;
+
+struct StoredResult {
+    id: String,
+    /// Only the session that made the call may read the result
+    session_id: String,
+    tool_name: String,
+    text: String,
+}
+
+/// Full output of the tool results that were truncated for exceeding the output limit
+pub struct ResultStore {
+    results: TokioMutex<VecDeque<StoredResult>>,
+    /// Total size of the stored outputs, the oldest are dropped first
+    max_bytes: usize,
+}
+
+/// Split a text in chunks of at most `chunk_bytes`, on character boundaries
+pub fn chunks(text: &str, chunk_bytes: usize) -> Vec<&str> {
+    let mut chunks = Vec::new();
+    let mut rest = text;
+    while !rest.is_empty() {
+        let mut end = chunk_bytes.min(rest.len());
+        while !rest.is_char_boundary(end) {
+            end -= 1;
+        }
+        // A character wider than a chunk is a chunk of its own
+        if end == 0 {
+            end = rest.chars().next().map_or(rest.len(), char::len_utf8);
+        }
+        let (chunk, tail) = rest.split_at(end);
+        chunks.push(chunk);
+        rest = tail;
+    }
+    chunks
+}
+
+/// Id and chunk index of a `dockmaster://results/{id}?chunk={n}` URI, the chunk defaults to 0
+fn parse_result_uri(uri: &str) -> Option<(&str, usize)> {
+    let path = uri.strip_prefix(RESULT_URI_PREFIX)?;
+    match path.split_once('?') {
+        Some((id, query)) => Some((id, query.strip_prefix("chunk=")?.parse().ok()?)),
+        None => Some((path, 0)),
+    }
+}
+
+/// Text of a tool result, non-text content as its JSON
+fn result_text(result: &CallToolResult) -> String {
+    result
+        .content
+        .iter()
+        .map(|content| match content.as_text() {
+            Some(text) => text.text.clone(),
+            None => serde_json::to_string(content).unwrap_or_default(),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+impl ResultStore {
+    pub fn new(max_bytes: usize) -> Self {
+        Self {
+            results: TokioMutex::new(VecDeque::new()),
+            max_bytes,
+        }
+    }
+
+    /// Keep the full output of a tool call and return the URI it is read at.
+    ///
+    /// Older results are dropped to stay within the count and size limits, the new result is
+    /// kept even if it exceeds the size limit on its own.
+    pub async fn store(&self, session_id: &str, tool_name: &str, text: String) -> String {
+        let id = Uuid::new_v4().to_string();
+        let mut results = self.results.lock().await;
+        results.push_back(StoredResult {
+            id: id.clone(),
+            session_id: session_id.to_string(),
+            tool_name: tool_name.to_string(),
+            text,
+        });
+        let mut total_bytes: usize = results.iter().map(|result| result.text.len()).sum();
+        while results.len() > MAX_STORED_RESULTS
+            || (results.len() > 1 && total_bytes > self.max_bytes)
+        {
+            if let Some(dropped) = results.pop_front() {
+                total_bytes -= dropped.text.len();
+            }
+        }
+        format!("{RESULT_URI_PREFIX}{id}")
+    }
+
+    /// Resources of the results a session spilled
+    pub async fn list(&self, session_id: &str) -> Vec<Resource> {
+        self.results
+            .lock()
+            .await
+            .iter()
+            .filter(|result| result.session_id == session_id)
+            .map(|result| {
+                Annotated::new(
+                    RawResource {
+                        uri: format!("{RESULT_URI_PREFIX}{}", result.id),
+                        name: format!("Output of {}", result.tool_name),
+                        description: Some("Full output of a truncated tool result".to_string()),
+                        mime_type: Some("text/plain".to_string()),
+                        size: u32::try_from(result.text.len()).ok(),
+                    },
+                    None,
+                )
+            })
+            .collect()
+    }
+
+    /// One chunk of a spilled result, `uri` selects it with `?chunk={n}`
+    pub async fn read(
+        &self,
+        session_id: &str,
+        uri: &str,
+        chunk_bytes: usize,
+    ) -> Result<ReadResourceResult, String> {
+        let (id, chunk) = parse_result_uri(uri).ok_or_else(|| format!("Invalid URI '{uri}'"))?;
+        let results = self.results.lock().await;
+        let result = results
+            .iter()
+            .find(|result| result.id == id && result.session_id == session_id)
+            .ok_or_else(|| format!("Resource '{uri}' not found"))?;
+        let chunks = chunks(&result.text, chunk_bytes);
+        let text = chunks.get(chunk).ok_or_else(|| {
+            format!(
+                "Chunk {chunk} out of range, '{uri}' has {} chunks",
+                chunks.len()
+            )
+        })?;
+        Ok(ReadResourceResult {
+            contents: vec![ResourceContents::TextResourceContents {
+                uri: uri.to_string(),
+                mime_type: Some("text/plain".to_string()),
+                text: text.to_string(),
+            }],
+        })
+    }
+
+    /// Truncate a result whose content exceeds `max_bytes` as JSON, 0 disables the limit.
+    ///
+    /// The truncated result keeps the start of the output followed by a marker naming the
+    /// resource the full output is stored as.
+    pub async fn limit_output(
+        &self,
+        session_id: &str,
+        tool_name: &str,
+        result: CallToolResult,
+        max_bytes: usize,
+        chunk_bytes: usize,
+    ) -> CallToolResult {
+        let size = serde_json::to_string(&result.content).map_or(0, |json| json.len());
+        if max_bytes == 0 || size <= max_bytes {
+            return result;
+        }
+
+        let text = result_text(&result);
+        let chunk_count = chunks(&text, chunk_bytes).len();
+        let uri = self.store(session_id, tool_name, text.clone()).await;
+        info!("Output of tool '{tool_name}' ({size} bytes) exceeds {max_bytes} bytes, stored as {uri}");
+        notify_resources_list_changed(session_id).await;
+
+        let marker = format!(
+            "[Output truncated: {size} bytes exceed the limit of {max_bytes}. The full output is \
+             the resource {uri}, read it in {chunk_count} chunks from {uri}?chunk=0 to \
+             {uri}?chunk={}]",
+            chunk_count.saturating_sub(1)
+        );
+        // The preview fills what the marker leaves of the limit, escaping aside
+        let overhead = serde_json::to_string(&[Content::text(""), Content::text(marker.clone())])
+            .map_or(marker.len(), |json| json.len());
+        let preview = chunks(&text, max_bytes.saturating_sub(overhead).max(1))
+            .first()
+            .map_or(String::new(), |preview| preview.to_string());
+        CallToolResult {
+            content: vec![Content::text(preview), Content::text(marker)],
+            is_error: result.is_error,
+        }
+    }
+
+    /// Forget the results of a session that disconnected
+    pub async fn remove_session(&self, session_id: &str) {
+        self.results
+            .lock()
+            .await
+            .retain(|result| result.session_id != session_id);
+    }
+}
+
+pub static RESULT_STORE: Lazy<ResultStore> =
+    Lazy::new(|| ResultStore::new(OutputLimitConfig::from_env().stored_max_bytes));
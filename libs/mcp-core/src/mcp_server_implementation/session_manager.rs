@@ -1,39 +1,148 @@
-use log::error;
 use once_cell::sync::Lazy;
-use std::collections::HashMap;
-use std::sync::Arc;
-use tokio::io::{self, AsyncWriteExt};
+use rmcp::model::{InitializeRequestParam, LoggingLevel, NumberOrString, ProgressToken};
+use rmcp::{Peer, RoleServer};
+use log::info;
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
 use tokio::sync::Mutex as TokioMutex;
 
-pub struct SessionChannels {
-    pub command: Arc<TokioMutex<io::WriteHalf<io::SimplexStream>>>,
-    pub notification: Arc<TokioMutex<io::WriteHalf<io::SimplexStream>>>,
+use crate::models::types::SessionInfo;
+use crate::utils::unix_timestamp;
+
+/// Level applied to sessions that never sent `logging/setLevel`
+pub const DEFAULT_LOG_LEVEL: LoggingLevel = LoggingLevel::Info;
+
+/// Period `ClientPolicy::max_calls_per_minute` is counted over
+const RATE_LIMIT_WINDOW: Duration = Duration::from_secs(60);
+
+/// A downstream client connected to the SSE endpoint
+pub struct Session {
+    pub peer: Peer<RoleServer>,
+    /// What the client sent in `initialize`: its name, version and capabilities
+    pub client: InitializeRequestParam,
+    /// Minimum level of the upstream log messages forwarded to this client
+    pub log_level: LoggingLevel,
+    /// Profile the client connected to, `None` for `/sse`
+    pub profile: Option<String>,
+    /// Seconds since the Unix epoch
+    pub connected_at: u64,
+    pub tool_calls: u64,
+    /// Tools `find_tools` mounted for this client, listed next to the pinned ones in discovery mode
+    pub mounted_tools: HashSet<String>,
+}
+
+/// Downstream request an upstream progress token reports to
+struct ProgressRoute {
+    peer: Peer<RoleServer>,
+    progress_token: ProgressToken,
+}
+
+/// Progress route of an upstream call, removed when dropped so a call whose future is dropped
+/// does not leave it behind
+#[must_use]
+pub struct ProgressRegistration<'a> {
+    manager: &'a SSESessionManager,
+    upstream_token: ProgressToken,
+}
+
+impl ProgressRegistration<'_> {
+    /// Token to send upstream
+    pub fn upstream_token(&self) -> &ProgressToken {
+        &self.upstream_token
+    }
+}
+
+/// Tool call of a session on a server, in flight until dropped
+#[must_use]
+pub struct ActiveCall<'a> {
+    manager: &'a SSESessionManager,
+    server_id: String,
+    session_id: String,
+}
+
+impl Drop for ActiveCall<'_> {
+    fn drop(&mut self) {
+        let mut active_calls = self.manager.active_calls.lock().unwrap();
+        if let Some(callers) = active_calls.get_mut(&self.server_id) {
+            if let Some(position) = callers.iter().rposition(|caller| *caller == self.session_id) {
+                callers.remove(position);
+            }
+            if callers.is_empty() {
+                active_calls.remove(&self.server_id);
+            }
+        }
+    }
+}
+
+impl Drop for ProgressRegistration<'_> {
+    fn drop(&mut self) {
+        let mut progress_routes = self.manager.progress_routes.lock().unwrap();
+        progress_routes.remove(&self.upstream_token);
+    }
 }
 
 #[derive(Default)]
 pub struct SSESessionManager {
-    pub(crate) sessions: TokioMutex<HashMap<String, SessionChannels>>,
+    pub(crate) sessions: TokioMutex<HashMap<String, Session>>,
+    progress_routes: Mutex<HashMap<ProgressToken, ProgressRoute>>,
+    /// Server id -> sessions with a tool call in flight on it, most recent last
+    active_calls: Mutex<HashMap<String, Vec<String>>>,
+    /// Client name -> start of its tool calls in the last minute, for rate limits
+    call_windows: TokioMutex<HashMap<String, VecDeque<Instant>>>,
+}
+
+/// Severity rank of a logging level, higher is more severe
+pub fn log_level_severity(level: LoggingLevel) -> u8 {
+    match level {
+        LoggingLevel::Debug => 0,
+        LoggingLevel::Info => 1,
+        LoggingLevel::Notice => 2,
+        LoggingLevel::Warning => 3,
+        LoggingLevel::Error => 4,
+        LoggingLevel::Critical => 5,
+        LoggingLevel::Alert => 6,
+        LoggingLevel::Emergency => 7,
+    }
 }
 
 impl SSESessionManager {
     pub fn new() -> Self {
         Self {
             sessions: TokioMutex::new(HashMap::new()),
+            progress_routes: Mutex::new(HashMap::new()),
+            active_calls: Mutex::new(HashMap::new()),
+            call_windows: TokioMutex::new(HashMap::new()),
         }
     }
 
     pub async fn register_session(
         &self,
         session_id: String,
-        command_writer: Arc<TokioMutex<io::WriteHalf<io::SimplexStream>>>,
-        notification_writer: Arc<TokioMutex<io::WriteHalf<io::SimplexStream>>>,
+        peer: Peer<RoleServer>,
+        client: InitializeRequestParam,
+        profile: Option<String>,
     ) {
+        info!(
+            "Client {} {} connected{}",
+            client.client_info.name,
+            client.client_info.version,
+            profile
+                .as_deref()
+                .map(|profile| format!(" to profile {profile}"))
+                .unwrap_or_default()
+        );
         let mut sessions = self.sessions.lock().await;
         sessions.insert(
             session_id,
-            SessionChannels {
-                command: command_writer,
-                notification: notification_writer,
+            Session {
+                peer,
+                client,
+                log_level: DEFAULT_LOG_LEVEL,
+                profile,
+                connected_at: unix_timestamp(),
+                tool_calls: 0,
+                mounted_tools: HashSet::new(),
             },
         );
     }
@@ -43,48 +152,209 @@ impl SSESessionManager {
         sessions.remove(session_id);
     }
 
-    pub async fn broadcast_message(&self, message: &str) -> Vec<String> {
-        let mut failed_sessions = Vec::new();
+    pub async fn session_count(&self) -> usize {
+        self.sessions.lock().await.len()
+    }
+
+    /// Snapshot of the connected peers, so callers can send without holding the lock
+    pub async fn peers(&self) -> Vec<(String, Peer<RoleServer>)> {
         let sessions = self.sessions.lock().await;
+        sessions
+            .iter()
+            .map(|(session_id, session)| (session_id.clone(), session.peer.clone()))
+            .collect()
+    }
 
-        for (session_id, channels) in sessions.iter() {
-            let mut writer = channels.notification.lock().await;
-            let message_bytes = message.as_bytes();
+    pub async fn get_peer(&self, session_id: &str) -> Option<Peer<RoleServer>> {
+        let sessions = self.sessions.lock().await;
+        sessions.get(session_id).map(|session| session.peer.clone())
+    }
 
-            if let Err(e) = async {
-                writer.write_all(message_bytes).await?;
-                writer.write_u8(b'\n').await?;
-                writer.flush().await?;
-                Ok::<_, std::io::Error>(())
-            }
-            .await
-            {
-                error!("Failed to broadcast to session {session_id}: {e}");
-                failed_sessions.push(session_id.clone());
-            }
+    /// Name the client of a session sent in `initialize`
+    pub async fn client_name(&self, session_id: &str) -> Option<String> {
+        let sessions = self.sessions.lock().await;
+        sessions
+            .get(session_id)
+            .map(|session| session.client.client_info.name.clone())
+    }
+
+    /// Connected clients, oldest connection first
+    pub async fn list_sessions(&self) -> Vec<SessionInfo> {
+        let sessions = self.sessions.lock().await;
+        let mut session_infos: Vec<SessionInfo> = sessions
+            .iter()
+            .map(|(session_id, session)| SessionInfo {
+                session_id: session_id.clone(),
+                client_name: session.client.client_info.name.clone(),
+                client_version: session.client.client_info.version.clone(),
+                profile: session.profile.clone(),
+                connected_at: session.connected_at,
+                tool_calls: session.tool_calls,
+            })
+            .collect();
+        session_infos.sort_by_key(|session| session.connected_at);
+        session_infos
+    }
+
+    pub async fn record_tool_call(&self, session_id: &str) {
+        let mut sessions = self.sessions.lock().await;
+        if let Some(session) = sessions.get_mut(session_id) {
+            session.tool_calls += 1;
         }
+    }
 
-        failed_sessions
+    /// Add tools to the ones listed to a session, returns whether any of them was new
+    pub async fn mount_tools(&self, session_id: &str, tool_names: Vec<String>) -> bool {
+        let mut sessions = self.sessions.lock().await;
+        let Some(session) = sessions.get_mut(session_id) else {
+            return false;
+        };
+        let mounted = session.mounted_tools.len();
+        session.mounted_tools.extend(tool_names);
+        session.mounted_tools.len() > mounted
     }
 
-    pub async fn send_message(&self, session_id: &str, message: &str) -> Result<(), String> {
+    pub async fn mounted_tools(&self, session_id: &str) -> HashSet<String> {
         let sessions = self.sessions.lock().await;
-        if let Some(channels) = sessions.get(session_id) {
-            let mut writer = channels.command.lock().await;
-            let message_bytes = message.as_bytes();
-
-            async {
-                writer.write_all(message_bytes).await?;
-                writer.write_u8(b'\n').await?;
-                writer.flush().await?;
-                Ok::<_, std::io::Error>(())
-            }
-            .await
-            .map_err(|e| format!("Failed to send message to session {session_id}: {e}"))
-        } else {
-            Err(format!("Session {session_id} not found"))
+        sessions
+            .get(session_id)
+            .map(|session| session.mounted_tools.clone())
+            .unwrap_or_default()
+    }
+
+    /// Count a tool call of the connections of a profile against their per-minute limit.
+    ///
+    /// Returns false, without counting the call, when the profile already reached the limit.
+    pub async fn try_acquire_call(&self, profile: &str, max_calls_per_minute: u32) -> bool {
+        let mut call_windows = self.call_windows.lock().await;
+        let window = call_windows.entry(profile.to_string()).or_default();
+        let now = Instant::now();
+        while window
+            .front()
+            .is_some_and(|start| now.duration_since(*start) >= RATE_LIMIT_WINDOW)
+        {
+            window.pop_front();
+        }
+        if window.len() >= max_calls_per_minute as usize {
+            return false;
+        }
+        window.push_back(now);
+        true
+    }
+
+    pub async fn set_log_level(&self, session_id: &str, level: LoggingLevel) -> Result<(), String> {
+        let mut sessions = self.sessions.lock().await;
+        let session = sessions
+            .get_mut(session_id)
+            .ok_or_else(|| format!("Session {session_id} not found"))?;
+        session.log_level = level;
+        Ok(())
+    }
+
+    /// Peers of the sessions that want log messages of the given level.
+    ///
+    /// `accepts` is given the profile of each session, so messages only reach the sessions that
+    /// may see where they come from.
+    pub async fn peers_for_log_level(
+        &self,
+        level: LoggingLevel,
+        accepts: impl Fn(Option<&str>) -> bool,
+    ) -> Vec<(String, Peer<RoleServer>)> {
+        let sessions = self.sessions.lock().await;
+        sessions
+            .iter()
+            .filter(|(_, session)| {
+                log_level_severity(level) >= log_level_severity(session.log_level)
+                    && accepts(session.profile.as_deref())
+            })
+            .map(|(session_id, session)| (session_id.clone(), session.peer.clone()))
+            .collect()
+    }
+
+    /// Record that a session is calling a tool of a server, as long as the returned call lasts
+    pub fn begin_call(&self, server_id: &str, session_id: &str) -> ActiveCall<'_> {
+        let mut active_calls = self.active_calls.lock().unwrap();
+        active_calls
+            .entry(server_id.to_string())
+            .or_default()
+            .push(session_id.to_string());
+        ActiveCall {
+            manager: self,
+            server_id: server_id.to_string(),
+            session_id: session_id.to_string(),
         }
     }
+
+    /// Session a request sent by an upstream server should be forwarded to.
+    ///
+    /// That is the session with the most recent tool call in flight on the server, or else a
+    /// session of `default_client` that `accepts`, given its profile, lets see the server. Returns the peer and what the client sent in `initialize`.
+    pub async fn session_for_server_request(
+        &self,
+        server_id: &str,
+        default_client: Option<&str>,
+        accepts: impl Fn(Option<&str>) -> bool,
+    ) -> Option<(Peer<RoleServer>, InitializeRequestParam)> {
+        let callers = self
+            .active_calls
+            .lock()
+            .unwrap()
+            .get(server_id)
+            .cloned()
+            .unwrap_or_default();
+        let sessions = self.sessions.lock().await;
+
+        callers
+            .iter()
+            .rev()
+            .find_map(|session_id| sessions.get(session_id))
+            .or_else(|| {
+                let default_client = default_client?;
+                sessions.values().find(|session| {
+                    session.client.client_info.name == default_client
+                        && accepts(session.profile.as_deref())
+                })
+            })
+            .map(|session| (session.peer.clone(), session.client.clone()))
+    }
+
+    /// Route progress of an upstream call back to the downstream request that asked for it.
+    ///
+    /// Downstream tokens are only unique per client, so the upstream call gets a fresh token
+    /// that is translated back when progress arrives. The route lasts as long as the returned
+    /// registration.
+    pub fn register_progress(
+        &self,
+        peer: Peer<RoleServer>,
+        progress_token: ProgressToken,
+    ) -> ProgressRegistration<'_> {
+        let upstream_token = ProgressToken(NumberOrString::String(
+            uuid::Uuid::new_v4().to_string().into(),
+        ));
+        let mut progress_routes = self.progress_routes.lock().unwrap();
+        progress_routes.insert(
+            upstream_token.clone(),
+            ProgressRoute {
+                peer,
+                progress_token,
+            },
+        );
+        ProgressRegistration {
+            manager: self,
+            upstream_token,
+        }
+    }
+
+    /// Downstream peer and token for an upstream progress token
+    pub fn progress_route(
+        &self,
+        upstream_token: &ProgressToken,
+    ) -> Option<(Peer<RoleServer>, ProgressToken)> {
+        let progress_routes = self.progress_routes.lock().unwrap();
+        progress_routes
+            .get(upstream_token)
+            .map(|route| (route.peer.clone(), route.progress_token.clone()))
+    }
 }
 
 // Global session manager instance
@@ -0,0 +1,128 @@
+use std::sync::Arc;
+
+use log::warn;
+use rmcp::model::{JsonObject, Tool};
+use serde_json::Value;
+
+use crate::types::ToolSize;
+
+/// Schema keywords that only document a schema, minified tools go without them
+const DOCUMENTATION_KEYWORDS: [&str; 4] = ["description", "title", "examples", "$comment"];
+
+/// Keywords whose value is a map of names to schemas
+const SCHEMA_MAP_KEYWORDS: [&str; 4] = ["properties", "patternProperties", "definitions", "$defs"];
+
+/// Keywords whose value is data rather than a schema
+const DATA_KEYWORDS: [&str; 3] = ["default", "enum", "const"];
+
+/// First sentence or line of a text
+fn first_sentence(text: &str) -> &str {
+    let text = text.trim();
+    let end = [text.find(". ").map(|i| i + 1), text.find('\n')]
+        .into_iter()
+        .flatten()
+        .min()
+        .unwrap_or(text.len());
+    text[..end].trim_end()
+}
+
+/// Remove the documentation keywords of a schema and of its subschemas
+fn minify_schema(schema: &mut JsonObject) {
+    schema.retain(|key, _| !DOCUMENTATION_KEYWORDS.contains(&key.as_str()));
+    for (key, value) in schema.iter_mut() {
+        if DATA_KEYWORDS.contains(&key.as_str()) {
+            continue;
+        }
+        match value {
+            Value::Object(schemas) if SCHEMA_MAP_KEYWORDS.contains(&key.as_str()) => {
+                for schema in schemas.values_mut() {
+                    if let Value::Object(schema) = schema {
+                        minify_schema(schema);
+                    }
+                }
+            }
+            Value::Object(schema) => minify_schema(schema),
+            Value::Array(schemas) => {
+                for schema in schemas {
+                    if let Value::Object(schema) = schema {
+                        minify_schema(schema);
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
+
+/// The tool with its description cut to the first sentence and its input schema undocumented
+pub fn minify_tool(tool: &Tool) -> Tool {
+    let mut input_schema = tool.input_schema.as_ref().clone();
+    minify_schema(&mut input_schema);
+    Tool {
+        name: tool.name.clone(),
+        description: tool
+            .description
+            .as_deref()
+            .map(|description| first_sentence(description).to_string().into()),
+        input_schema: Arc::new(input_schema),
+        annotations: tool.annotations.clone(),
+    }
+}
+
+/// Fit tool definitions into `budget` estimated tokens, see [`ToolSize`].
+///
+/// `priority` ranks the tools that may be cut, lower ranks are kept longer, tools ranked `None`
+/// are kept as they are. The lowest-ranked tools are minified first and dropped if the budget is
+/// still exceeded. The remaining tools keep their order.
+pub fn fit_token_budget(
+    mut tools: Vec<Tool>,
+    budget: usize,
+    priority: impl Fn(&Tool) -> Option<usize>,
+) -> Vec<Tool> {
+    let mut sizes: Vec<usize> = tools.iter().map(|tool| ToolSize::of(tool).tokens).collect();
+    let mut total: usize = sizes.iter().sum();
+    if total <= budget {
+        return tools;
+    }
+
+    let mut cuttable: Vec<(usize, usize)> = tools
+        .iter()
+        .enumerate()
+        .filter_map(|(i, tool)| Some((priority(tool)?, i)))
+        .collect();
+    // Lowest priority first, later tools first among equals
+    cuttable.sort_by(|a, b| b.cmp(a));
+
+    for &(_, i) in &cuttable {
+        if total <= budget {
+            break;
+        }
+        let minified = minify_tool(&tools[i]);
+        let size = ToolSize::of(&minified).tokens;
+        total = total - sizes[i] + size;
+        sizes[i] = size;
+        tools[i] = minified;
+    }
+
+    let mut dropped = vec![false; tools.len()];
+    for &(_, i) in &cuttable {
+        if total <= budget {
+            break;
+        }
+        total -= sizes[i];
+        dropped[i] = true;
+    }
+    let dropped_count = dropped.iter().filter(|dropped| **dropped).count();
+    if dropped_count > 0 || total > budget {
+        warn!(
+            "Tool definitions exceed the budget of {budget} tokens: dropped {dropped_count} tools, {total} tokens left"
+        );
+    }
+
+    tools
+        .into_iter()
+        .zip(dropped)
+        .filter(|(_, dropped)| !dropped)
+        .map(|(tool, _)| tool)
+        .collect()
+}
@@ -0,0 +1,52 @@
+use std::borrow::Cow;
+
+use probly_search::score::bm25;
+use probly_search::Index;
+use rmcp::model::Tool;
+
+/// Field weights: name, description
+const WEIGHTS: [f64; 2] = [3.0, 1.0];
+
+/// Lowercase words of a text, names like `create_issue` are split into `create` and `issue`
+fn tokenizer(s: &str) -> Vec<Cow<'static, str>> {
+    s.to_lowercase()
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(|word| Cow::Owned(word.to_string()))
+        .collect()
+}
+
+/// Rank tools against a query by name and description with BM25, best match first.
+///
+/// Tools that match none of the query words are left out, at most `limit` tools are returned.
+pub fn rank_tools(tools: Vec<Tool>, query: &str, limit: usize) -> Vec<(Tool, f64)> {
+    type FA = for<'b> fn(&'b Tool) -> Vec<&'b str>;
+    let name_extractor: FA = |tool| vec![tool.name.as_ref()];
+    let description_extractor: FA = |tool| vec![tool.description.as_deref().unwrap_or_default()];
+    let extractors: [FA; 2] = [name_extractor, description_extractor];
+
+    let mut index = Index::<u32>::new(WEIGHTS.len());
+    for (i, tool) in tools.iter().enumerate() {
+        index.add_document(&extractors, tokenizer, i as u32, tool);
+    }
+
+    let mut scorer = bm25::new();
+    let mut results: Vec<(u32, f64)> = index
+        .query(query, &mut scorer, tokenizer, &WEIGHTS)
+        .into_iter()
+        .map(|result| (result.key, result.score))
+        .collect();
+    // Ties keep the order of `tools` so results are stable
+    results.sort_by(|a, b| {
+        b.1.partial_cmp(&a.1)
+            .unwrap_or(std::cmp::Ordering::Equal)
+            .then(a.0.cmp(&b.0))
+    });
+
+    let mut tools: Vec<Option<Tool>> = tools.into_iter().map(Some).collect();
+    results
+        .into_iter()
+        .take(limit)
+        .filter_map(|(key, score)| Some((tools.get_mut(key as usize)?.take()?, score)))
+        .collect()
+}
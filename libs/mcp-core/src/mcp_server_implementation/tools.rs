@@ -2,21 +2,28 @@ use std::{borrow::Cow, sync::Arc};
 
 use log::{error, info};
 use rmcp::{
-    model::{CallToolResult, Content, Tool},
+    model::{CallToolRequestParam, CallToolResult, Content, Tool},
     Error as McpError,
 };
 use serde::Deserialize;
 use serde_json::{json, Map, Value};
 
 use crate::{
-    config::ToolConfig,
-    core::{mcp_core::MCPCore, mcp_core_proxy_ext::McpCoreProxyExt},
-    mcp_server_implementation::registry_cache::fetch_tool_from_registry,
+    config::{DiscoveryConfig, ToolConfig},
+    core::{
+        mcp_core::MCPCore, mcp_core_proxy_ext::McpCoreProxyExt,
+        mcp_core_secrets_ext::McpCoreSecretsExt,
+    },
+    mcp_server_implementation::{
+        registry_cache::fetch_tool_from_registry, tool_search::rank_tools,
+    },
+    mcp_state::server_logs::read_server_log,
     registry::registry_search::{RegistrySearch, SearchError},
     types::{
         Distribution, ServerConfigUpdateRequest, ServerConfiguration, ServerRegistrationRequest,
-        ToolUninstallRequest,
+        ServerToolInfo, ServerUpdateRequest, ToolUninstallRequest,
     },
+    utils::redaction::redact_values,
 };
 
 #[derive(Deserialize, Debug)]
@@ -47,25 +54,343 @@ struct ServerSearchRequest {
     query: String,
 }
 
+#[derive(Deserialize, Debug)]
+struct SetToolActiveRequest {
+    server_id: String,
+    tool_id: String,
+    active: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct ServerIdRequest {
+    server_id: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct ServerLogsRequest {
+    server_id: String,
+    #[serde(default = "default_server_log_lines")]
+    lines: usize,
+}
+
+#[derive(Deserialize, Debug)]
+struct FindToolsRequest {
+    query: String,
+    limit: Option<usize>,
+    #[serde(default)]
+    mount: bool,
+}
+
+#[derive(Deserialize, Debug)]
+struct CallToolRequest {
+    name: String,
+    arguments: Option<Map<String, Value>>,
+}
+
+/// Log lines `server_logs` returns when the client does not ask for a number
+const DEFAULT_SERVER_LOG_LINES: usize = 100;
+
+fn default_server_log_lines() -> usize {
+    DEFAULT_SERVER_LOG_LINES
+}
+
 /// Base tool names (without namespace)
 const BASE_TOOL_REGISTER_SERVER: &str = "register_server";
 const BASE_TOOL_SEARCH_SERVER: &str = "search_server";
 const BASE_TOOL_CONFIGURE_SERVER: &str = "configure_server";
 const BASE_TOOL_UNINSTALL_SERVER: &str = "uninstall_server";
 const BASE_TOOL_LIST_INSTALLED_SERVERS: &str = "list_installed_servers";
+const BASE_TOOL_SET_TOOL_ACTIVE: &str = "set_tool_active";
+const BASE_TOOL_SERVER_STATUS: &str = "server_status";
+const BASE_TOOL_RESTART_SERVER: &str = "restart_server";
+const BASE_TOOL_ENABLE_SERVER: &str = "enable_server";
+const BASE_TOOL_DISABLE_SERVER: &str = "disable_server";
+const BASE_TOOL_SERVER_LOGS: &str = "server_logs";
+const BASE_TOOL_LIST_SERVER_TOOLS: &str = "list_server_tools";
+const BASE_TOOL_FIND_TOOLS: &str = "find_tools";
+const BASE_TOOL_CALL_TOOL: &str = "call_tool";
+
+/// Namespaced names of the built-in tools
+pub struct ToolNames {
+    pub register_server: String,
+    pub search_server: String,
+    pub configure_server: String,
+    pub uninstall_server: String,
+    pub list_installed_servers: String,
+    pub set_tool_active: String,
+    pub server_status: String,
+    pub restart_server: String,
+    pub enable_server: String,
+    pub disable_server: String,
+    pub server_logs: String,
+    pub list_server_tools: String,
+    pub find_tools: String,
+    pub call_tool: String,
+    /// Namespaced names of the built-in tools turned off in `ToolConfig`
+    pub disabled: Vec<String>,
+}
 
 /// Get namespaced tool names using configuration
-pub fn get_tool_names() -> (String, String, String, String, String) {
+pub fn get_tool_names() -> ToolNames {
+    let config = ToolConfig::from_env();
+    ToolNames {
+        register_server: config.tool_name(BASE_TOOL_REGISTER_SERVER),
+        search_server: config.tool_name(BASE_TOOL_SEARCH_SERVER),
+        configure_server: config.tool_name(BASE_TOOL_CONFIGURE_SERVER),
+        uninstall_server: config.tool_name(BASE_TOOL_UNINSTALL_SERVER),
+        list_installed_servers: config.tool_name(BASE_TOOL_LIST_INSTALLED_SERVERS),
+        set_tool_active: config.tool_name(BASE_TOOL_SET_TOOL_ACTIVE),
+        server_status: config.tool_name(BASE_TOOL_SERVER_STATUS),
+        restart_server: config.tool_name(BASE_TOOL_RESTART_SERVER),
+        enable_server: config.tool_name(BASE_TOOL_ENABLE_SERVER),
+        disable_server: config.tool_name(BASE_TOOL_DISABLE_SERVER),
+        server_logs: config.tool_name(BASE_TOOL_SERVER_LOGS),
+        list_server_tools: config.tool_name(BASE_TOOL_LIST_SERVER_TOOLS),
+        find_tools: config.tool_name(BASE_TOOL_FIND_TOOLS),
+        call_tool: config.tool_name(BASE_TOOL_CALL_TOOL),
+        disabled: config
+            .disabled_tools
+            .iter()
+            .map(|base_name| config.tool_name(base_name))
+            .collect(),
+    }
+}
+
+impl ToolNames {
+    /// Whether `name` is taken by a built-in tool, enabled or not
+    pub fn contains(&self, name: &str) -> bool {
+        [
+            &self.register_server,
+            &self.search_server,
+            &self.configure_server,
+            &self.uninstall_server,
+            &self.list_installed_servers,
+            &self.set_tool_active,
+            &self.server_status,
+            &self.restart_server,
+            &self.enable_server,
+            &self.disable_server,
+            &self.server_logs,
+            &self.list_server_tools,
+            &self.find_tools,
+            &self.call_tool,
+        ]
+        .into_iter()
+        .any(|tool_name| tool_name == name)
+    }
+
+    /// Whether `name` is a built-in tool changing the server named by its `server_id` argument
+    pub fn changes_server(&self, name: &str) -> bool {
+        [
+            &self.configure_server,
+            &self.uninstall_server,
+            &self.set_tool_active,
+            &self.restart_server,
+            &self.enable_server,
+            &self.disable_server,
+        ]
+        .into_iter()
+        .any(|tool_name| tool_name == name)
+    }
+
+    /// Whether `name` is a built-in tool reading the server named by its `server_id` argument
+    pub fn reads_server(&self, name: &str) -> bool {
+        [
+            &self.server_status,
+            &self.server_logs,
+            &self.list_server_tools,
+        ]
+        .into_iter()
+        .any(|tool_name| tool_name == name)
+    }
+
+    /// Whether `name` is a built-in tool turned off in `ToolConfig`
+    pub fn is_disabled(&self, name: &str) -> bool {
+        self.disabled.iter().any(|tool_name| tool_name == name)
+    }
+}
+
+/// Built-in tools offered to clients, without the ones turned off in `ToolConfig`.
+///
+/// `find_tools` and `call_tool` are only offered in discovery mode.
+pub fn get_builtin_tools() -> Vec<Tool> {
+    let tool_names = get_tool_names();
+    let discovery_tools = if DiscoveryConfig::from_env().enabled {
+        vec![get_find_tools_tool(), get_call_tool_tool()]
+    } else {
+        Vec::new()
+    };
+    discovery_tools
+        .into_iter()
+        .chain([
+            get_register_server_tool(),
+            get_search_server_tool(),
+            get_configure_server_tool(),
+            get_list_installed_servers_tool(),
+            get_uninstall_server_tool(),
+            get_set_tool_active_tool(),
+            get_server_status_tool(),
+            get_restart_server_tool(),
+            get_enable_server_tool(),
+            get_disable_server_tool(),
+            get_server_logs_tool(),
+            get_list_server_tools_tool(),
+        ])
+        .filter(|tool| !tool_names.is_disabled(&tool.name))
+        .collect()
+}
+
+/// Get the find_tools tool definition
+pub fn get_find_tools_tool() -> Tool {
+    let config = ToolConfig::from_env();
+    let tool_name = config.tool_name(BASE_TOOL_FIND_TOOLS);
+    Tool {
+        name: Cow::Owned(tool_name),
+        description: Some(Cow::Owned(
+            "Find the installed tools matching a description of the task, with their input schemas"
+                .to_string(),
+        )),
+        input_schema: Arc::new(serde_json::Map::from_iter([
+            ("type".to_string(), json!("object")),
+            (
+                "properties".to_string(),
+                json!({
+                    "query": {
+                        "type": "string",
+                        "description": "Words describing what the tool should do"
+                    },
+                    "limit": {
+                        "type": "integer",
+                        "description": "Maximum number of tools to return"
+                    },
+                    "mount": {
+                        "type": "boolean",
+                        "description": "Add the tools found to the tool list so they can be called directly",
+                        "default": false
+                    }
+                }),
+            ),
+            ("required".to_string(), json!(["query"])),
+        ])),
+        annotations: None,
+    }
+}
+
+/// Get the call_tool tool definition
+pub fn get_call_tool_tool() -> Tool {
     let config = ToolConfig::from_env();
-    (
-        config.tool_name(BASE_TOOL_REGISTER_SERVER),
-        config.tool_name(BASE_TOOL_SEARCH_SERVER),
-        config.tool_name(BASE_TOOL_CONFIGURE_SERVER),
-        config.tool_name(BASE_TOOL_UNINSTALL_SERVER),
-        config.tool_name(BASE_TOOL_LIST_INSTALLED_SERVERS),
+    let tool_name = config.tool_name(BASE_TOOL_CALL_TOOL);
+    Tool {
+        name: Cow::Owned(tool_name),
+        description: Some(Cow::Owned(
+            "Call a tool returned by find_tools by its name".to_string(),
+        )),
+        input_schema: Arc::new(serde_json::Map::from_iter([
+            ("type".to_string(), json!("object")),
+            (
+                "properties".to_string(),
+                json!({
+                    "name": {
+                        "type": "string",
+                        "description": "Name of the tool"
+                    },
+                    "arguments": {
+                        "type": "object",
+                        "description": "Arguments matching the input schema of the tool"
+                    }
+                }),
+            ),
+            ("required".to_string(), json!(["name"])),
+        ])),
+        annotations: None,
+    }
+}
+
+/// Definition of a built-in tool whose only argument is the ID of a server
+fn server_id_tool(base_name: &str, description: &str) -> Tool {
+    let config = ToolConfig::from_env();
+    Tool {
+        name: Cow::Owned(config.tool_name(base_name)),
+        description: Some(Cow::Owned(description.to_string())),
+        input_schema: Arc::new(serde_json::Map::from_iter([
+            ("type".to_string(), json!("object")),
+            (
+                "properties".to_string(),
+                json!({
+                    "server_id": {
+                        "type": "string",
+                        "description": "ID of the installed server"
+                    }
+                }),
+            ),
+            ("required".to_string(), json!(["server_id"])),
+        ])),
+        annotations: None,
+    }
+}
+
+/// Get the server_status tool definition
+pub fn get_server_status_tool() -> Tool {
+    server_id_tool(
+        BASE_TOOL_SERVER_STATUS,
+        "Get the status of an installed server, including the error it failed with",
+    )
+}
+
+/// Get the restart_server tool definition
+pub fn get_restart_server_tool() -> Tool {
+    server_id_tool(BASE_TOOL_RESTART_SERVER, "Restart an installed server")
+}
+
+/// Get the enable_server tool definition
+pub fn get_enable_server_tool() -> Tool {
+    server_id_tool(BASE_TOOL_ENABLE_SERVER, "Enable and start an installed server")
+}
+
+/// Get the disable_server tool definition
+pub fn get_disable_server_tool() -> Tool {
+    server_id_tool(BASE_TOOL_DISABLE_SERVER, "Disable and stop an installed server")
+}
+
+/// Get the list_server_tools tool definition
+pub fn get_list_server_tools_tool() -> Tool {
+    server_id_tool(
+        BASE_TOOL_LIST_SERVER_TOOLS,
+        "List the known tools of an installed server, including the disabled ones and those the server does not provide right now",
     )
 }
 
+/// Get the server_logs tool definition
+pub fn get_server_logs_tool() -> Tool {
+    let config = ToolConfig::from_env();
+    let tool_name = config.tool_name(BASE_TOOL_SERVER_LOGS);
+    Tool {
+        name: Cow::Owned(tool_name),
+        description: Some(Cow::Owned(
+            "Read the most recent log lines of an installed server".to_string(),
+        )),
+        input_schema: Arc::new(serde_json::Map::from_iter([
+            ("type".to_string(), json!("object")),
+            (
+                "properties".to_string(),
+                json!({
+                    "server_id": {
+                        "type": "string",
+                        "description": "ID of the installed server"
+                    },
+                    "lines": {
+                        "type": "integer",
+                        "description": "Number of lines to return",
+                        "default": DEFAULT_SERVER_LOG_LINES
+                    }
+                }),
+            ),
+            ("required".to_string(), json!(["server_id"])),
+        ])),
+        annotations: None,
+    }
+}
+
 /// Get the list installed servers tool definition
 pub fn get_list_installed_servers_tool() -> Tool {
     let config = ToolConfig::from_env();
@@ -188,6 +513,43 @@ pub fn get_uninstall_server_tool() -> Tool {
     }
 }
 
+/// Get the set_tool_active tool definition
+pub fn get_set_tool_active_tool() -> Tool {
+    let config = ToolConfig::from_env();
+    let tool_name = config.tool_name(BASE_TOOL_SET_TOOL_ACTIVE);
+    Tool {
+        name: Cow::Owned(tool_name),
+        description: Some(Cow::Owned(
+            "Enable or disable a single tool of an installed server".to_string(),
+        )),
+        input_schema: Arc::new(serde_json::Map::from_iter([
+            ("type".to_string(), json!("object")),
+            (
+                "properties".to_string(),
+                json!({
+                    "server_id": {
+                        "type": "string",
+                        "description": "ID of the server that provides the tool"
+                    },
+                    "tool_id": {
+                        "type": "string",
+                        "description": "ID or name of the tool"
+                    },
+                    "active": {
+                        "type": "boolean",
+                        "description": "Whether the tool is exposed to clients"
+                    }
+                }),
+            ),
+            (
+                "required".to_string(),
+                json!(["server_id", "tool_id", "active"]),
+            ),
+        ])),
+        annotations: None,
+    }
+}
+
 pub async fn handle_register_server(
     mcp_core: Arc<MCPCore>,
     params: Map<String, Value>,
@@ -406,12 +768,29 @@ pub async fn handle_search_server(args: Map<String, Value>) -> Result<CallToolRe
     })
 }
 
-/// Handle list_installed_servers tool
+/// Error for a server that does not exist, or that the client may not see
+pub fn server_not_found(server_id: &str) -> McpError {
+    McpError::invalid_params(
+        format!("Server {server_id} not found"),
+        Some(Value::String(server_id.to_string())),
+    )
+}
+
+/// `server_id` argument of a built-in tool acting on a server
+pub fn server_id_argument(args: &Option<Map<String, Value>>) -> Option<&str> {
+    args.as_ref()?.get("server_id")?.as_str()
+}
+
+/// Handle list_installed_servers tool, listing the servers `is_visible` lets through
 pub async fn handle_list_installed_servers(
     mcp_core: Arc<MCPCore>,
+    is_visible: impl Fn(&str) -> bool,
 ) -> Result<CallToolResult, McpError> {
     // Get the installed servers from MCPCore
-    let result = mcp_core.list_servers().await;
+    let result = mcp_core.list_servers().await.map(|mut servers| {
+        servers.retain(|server| is_visible(server.id.as_str()));
+        servers
+    });
 
     // Return the installed servers as JSON
     Ok(CallToolResult {
@@ -419,3 +798,241 @@ pub async fn handle_list_installed_servers(
         is_error: Some(false),
     })
 }
+
+/// Handle set_tool_active tool
+pub async fn handle_set_tool_active(
+    mcp_core: Arc<MCPCore>,
+    args: Map<String, Value>,
+) -> Result<CallToolResult, McpError> {
+    let request: SetToolActiveRequest = serde_json::from_value(Value::Object(args))
+        .map_err(|e| McpError::invalid_params(format!("Invalid arguments: {e}"), None))?;
+
+    let response = mcp_core
+        .set_tool_active(request.server_id, request.tool_id, request.active)
+        .await
+        .map_err(|e| McpError::internal_error(format!("Error updating tool: {e}"), None))?;
+
+    Ok(CallToolResult {
+        content: vec![Content::text(response.message)],
+        is_error: Some(!response.success),
+    })
+}
+
+/// Handle server_status tool
+pub async fn handle_server_status(
+    mcp_core: Arc<MCPCore>,
+    args: Map<String, Value>,
+) -> Result<CallToolResult, McpError> {
+    let request: ServerIdRequest = serde_json::from_value(Value::Object(args))
+        .map_err(|e| McpError::invalid_params(format!("Invalid arguments: {e}"), None))?;
+
+    let servers = mcp_core
+        .list_servers()
+        .await
+        .map_err(|e| McpError::internal_error(format!("Error listing servers: {e}"), None))?;
+    let server = servers
+        .into_iter()
+        .find(|server| server.id.as_str() == request.server_id)
+        .ok_or_else(|| server_not_found(&request.server_id))?;
+
+    Ok(CallToolResult {
+        content: vec![Content::text(serde_json::to_string(&server).unwrap())],
+        is_error: Some(false),
+    })
+}
+
+/// Handle restart_server tool
+pub async fn handle_restart_server(
+    mcp_core: Arc<MCPCore>,
+    args: Map<String, Value>,
+) -> Result<CallToolResult, McpError> {
+    let request: ServerIdRequest = serde_json::from_value(Value::Object(args))
+        .map_err(|e| McpError::invalid_params(format!("Invalid arguments: {e}"), None))?;
+
+    let response = mcp_core
+        .restart_server_command(request.server_id)
+        .await
+        .map_err(|e| McpError::internal_error(format!("Error restarting server: {e}"), None))?;
+
+    Ok(CallToolResult {
+        content: vec![Content::text(response.message)],
+        is_error: Some(!response.success),
+    })
+}
+
+/// Handle enable_server and disable_server tools
+pub async fn handle_set_server_enabled(
+    mcp_core: Arc<MCPCore>,
+    args: Map<String, Value>,
+    enabled: bool,
+) -> Result<CallToolResult, McpError> {
+    let request: ServerIdRequest = serde_json::from_value(Value::Object(args))
+        .map_err(|e| McpError::invalid_params(format!("Invalid arguments: {e}"), None))?;
+
+    let response = mcp_core
+        .update_server_status(ServerUpdateRequest {
+            server_id: request.server_id,
+            enabled,
+        })
+        .await
+        .map_err(|e| McpError::internal_error(format!("Error updating server: {e}"), None))?;
+
+    Ok(CallToolResult {
+        content: vec![Content::text(response.message)],
+        is_error: Some(!response.success),
+    })
+}
+
+/// Handle server_logs tool
+pub async fn handle_server_logs(
+    mcp_core: Arc<MCPCore>,
+    args: Map<String, Value>,
+) -> Result<CallToolResult, McpError> {
+    let request: ServerLogsRequest = serde_json::from_value(Value::Object(args))
+        .map_err(|e| McpError::invalid_params(format!("Invalid arguments: {e}"), None))?;
+
+    let exists = mcp_core
+        .tool_registry
+        .read()
+        .await
+        .get_server(&request.server_id)
+        .is_ok();
+    if !exists {
+        return Err(server_not_found(&request.server_id));
+    }
+
+    let lines = read_server_log(&request.server_id, request.lines)
+        .await
+        .map_err(|e| McpError::internal_error(e, None))?;
+    // Servers often echo their API keys in their logs
+    let secrets = mcp_core
+        .server_secret_values(&request.server_id)
+        .await
+        .map_err(|e| McpError::internal_error(e, None))?;
+    let text = if lines.is_empty() {
+        format!("Server {} has not logged anything yet", request.server_id)
+    } else {
+        lines
+            .iter()
+            .map(|line| redact_values(line, &secrets))
+            .collect::<Vec<_>>()
+            .join("\n")
+    };
+
+    Ok(CallToolResult {
+        content: vec![Content::text(text)],
+        is_error: Some(false),
+    })
+}
+
+/// Handle list_server_tools tool, listing the tools `is_visible` lets through
+pub async fn handle_list_server_tools(
+    mcp_core: Arc<MCPCore>,
+    args: Map<String, Value>,
+    is_visible: impl Fn(&ServerToolInfo) -> bool,
+) -> Result<CallToolResult, McpError> {
+    let request: ServerIdRequest = serde_json::from_value(Value::Object(args))
+        .map_err(|e| McpError::invalid_params(format!("Invalid arguments: {e}"), None))?;
+
+    // Read the stored tools, rediscovering is left to the server's list_changed notifications
+    let tools = {
+        let registry = mcp_core.tool_registry.read().await;
+        if registry.get_server(&request.server_id).is_err() {
+            return Err(server_not_found(&request.server_id));
+        }
+        registry
+            .get_server_tools(&request.server_id)
+            .map_err(|e| McpError::internal_error(format!("Error listing tools: {e}"), None))?
+    };
+    let provided: Vec<String> = {
+        let mcp_state = mcp_core.mcp_state.read().await;
+        let server_tools = mcp_state.server_tools.read().await;
+        server_tools
+            .get(&request.server_id)
+            .map(|tools| tools.iter().map(|tool| tool.id.clone()).collect())
+            .unwrap_or_default()
+    };
+    let tools: Vec<Value> = tools
+        .iter()
+        .filter(|tool| is_visible(tool))
+        .map(|tool| {
+            json!({
+                "id": tool.id,
+                "name": tool.exposed_name(),
+                "description": tool.description,
+                "is_active": tool.is_active,
+                "available": provided.contains(&tool.id),
+            })
+        })
+        .collect();
+
+    Ok(CallToolResult {
+        content: vec![Content::text(serde_json::to_string(&tools).unwrap())],
+        is_error: Some(false),
+    })
+}
+
+/// Handle find_tools tool, ranking the tools the client may call.
+///
+/// Returns the result and the names of the tools found when the client asked to mount them.
+pub async fn handle_find_tools(
+    tools: Vec<Tool>,
+    args: Map<String, Value>,
+) -> Result<(CallToolResult, Vec<String>), McpError> {
+    let request: FindToolsRequest = serde_json::from_value(Value::Object(args))
+        .map_err(|e| McpError::invalid_params(format!("Invalid arguments: {e}"), None))?;
+    if request.query.trim().is_empty() {
+        return Err(McpError::invalid_params(
+            "Search query cannot be empty".to_string(),
+            None,
+        ));
+    }
+
+    let limit = request
+        .limit
+        .unwrap_or_else(|| DiscoveryConfig::from_env().max_results);
+    let results = rank_tools(tools, &request.query, limit);
+    let mount = if request.mount {
+        results.iter().map(|(tool, _)| tool.name.to_string()).collect()
+    } else {
+        Vec::new()
+    };
+    let formatted_results: Vec<Value> = results
+        .into_iter()
+        .map(|(tool, score)| {
+            json!({
+                "name": tool.name,
+                "description": tool.description,
+                "input_schema": tool.input_schema,
+                "score": score,
+            })
+        })
+        .collect();
+
+    Ok((
+        CallToolResult {
+            content: vec![Content::text(
+                serde_json::to_string(&formatted_results).unwrap(),
+            )],
+            is_error: Some(false),
+        },
+        mount,
+    ))
+}
+
+/// Request of the tool a call_tool call names
+pub fn parse_call_tool_request(args: Map<String, Value>) -> Result<CallToolRequestParam, McpError> {
+    let request: CallToolRequest = serde_json::from_value(Value::Object(args))
+        .map_err(|e| McpError::invalid_params(format!("Invalid arguments: {e}"), None))?;
+    let tool_names = get_tool_names();
+    if request.name == tool_names.call_tool || request.name == tool_names.find_tools {
+        return Err(McpError::invalid_params(
+            format!("Tool '{}' cannot be called through call_tool", request.name),
+            None,
+        ));
+    }
+    Ok(CallToolRequestParam {
+        name: request.name.into(),
+        arguments: request.arguments,
+    })
+}
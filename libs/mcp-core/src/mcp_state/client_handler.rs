@@ -0,0 +1,237 @@
+use std::collections::HashMap;
+
+use log::{debug, info, warn};
+use rmcp::model::{
+    ClientCapabilities, ClientInfo, CreateMessageRequestParam, CreateMessageResult, ErrorCode,
+    ListRootsResult, LoggingMessageNotificationParam, ProgressNotificationParam,
+};
+use rmcp::service::{NotificationContext, RequestContext};
+use rmcp::{ClientHandler, Error as McpError, Peer, RoleClient, RoleServer, ServiceError};
+
+use crate::config::ClientForwardingConfig;
+use crate::models::types::{ClientPolicy, Profile};
+use crate::utils::redaction::{redact_json_values, redact_values};
+
+use crate::mcp_server_implementation::notifications::{
+    broadcast_log_message, broadcast_prompts_list_changed, broadcast_resources_list_changed,
+};
+use crate::mcp_server_implementation::session_manager::SESSION_MANAGER;
+
+use super::mcp_state::MCPState;
+use super::server_logs::append_server_log;
+
+/// Client side of the connection to an upstream server.
+///
+/// Reacts to the notifications the upstream server sends, so changes it announces are picked up
+/// and relayed to the downstream sessions.
+pub struct DockmasterClientHandler {
+    server_id: String,
+    mcp_state: MCPState,
+    info: ClientInfo,
+}
+
+impl DockmasterClientHandler {
+    pub fn new(server_id: String, mcp_state: MCPState, info: ClientInfo) -> Self {
+        Self {
+            server_id,
+            mcp_state,
+            info,
+        }
+    }
+
+    /// Downstream client a request of this server is forwarded to, if it supports the capability
+    async fn downstream_peer(
+        &self,
+        capability: &str,
+        supports: impl Fn(&ClientCapabilities) -> bool,
+    ) -> Result<Peer<RoleServer>, McpError> {
+        let config = ClientForwardingConfig::from_env();
+        if !config.enabled {
+            return Err(McpError::new(
+                ErrorCode::METHOD_NOT_FOUND,
+                format!("Forwarding {capability} requests to clients is disabled"),
+                None,
+            ));
+        }
+        // The default client only gets requests of servers its profile and policy let it see
+        let accepts = self
+            .server_audience()
+            .await
+            .map_err(|e| McpError::internal_error(e, None))?;
+        let session = SESSION_MANAGER
+            .session_for_server_request(&self.server_id, config.default_client.as_deref(), accepts)
+            .await;
+        match session {
+            Some((peer, client)) if supports(&client.capabilities) => Ok(peer),
+            Some((_, client)) => Err(McpError::new(
+                ErrorCode::METHOD_NOT_FOUND,
+                format!(
+                    "Client '{}' does not support {capability}",
+                    client.client_info.name
+                ),
+                None,
+            )),
+            None => Err(McpError::new(
+                ErrorCode::METHOD_NOT_FOUND,
+                format!("No connected client can handle {capability} for this server"),
+                None,
+            )),
+        }
+    }
+
+    /// Whether a session, given its profile, may see this server
+    async fn server_audience(&self) -> Result<impl Fn(Option<&str>) -> bool, String> {
+        let registry = self.mcp_state.tool_registry.read().await;
+        let profiles: HashMap<String, Profile> = registry
+            .get_profiles()?
+            .into_iter()
+            .map(|profile| (profile.name.clone(), profile))
+            .collect();
+        let policies: HashMap<String, ClientPolicy> = registry
+            .get_client_policies()?
+            .into_iter()
+            .map(|policy| (policy.profile.clone(), policy))
+            .collect();
+        let server_id = self.server_id.clone();
+        Ok(move |profile: Option<&str>| {
+            let in_profile = profile.is_none_or(|name| {
+                profiles
+                    .get(name)
+                    .is_some_and(|profile| profile.includes_server(&server_id))
+            });
+            let policy = profile
+                .and_then(|name| policies.get(name))
+                .or_else(|| policies.get(ClientPolicy::ANY_PROFILE));
+            in_profile && policy.is_none_or(|policy| policy.allows_server(&server_id))
+        })
+    }
+}
+
+/// Keep errors the downstream client returned, wrap transport failures
+fn forwarding_error(server_id: &str, error: ServiceError) -> McpError {
+    match error {
+        ServiceError::McpError(error) => error,
+        error => McpError::internal_error(
+            format!("Failed to forward request of server {server_id} to the client: {error}"),
+            None,
+        ),
+    }
+}
+
+impl ClientHandler for DockmasterClientHandler {
+    fn get_info(&self) -> ClientInfo {
+        self.info.clone()
+    }
+
+    async fn create_message(
+        &self,
+        params: CreateMessageRequestParam,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<CreateMessageResult, McpError> {
+        info!("Forwarding sampling request of server {}", self.server_id);
+        let peer = self
+            .downstream_peer("sampling", |capabilities| capabilities.sampling.is_some())
+            .await?;
+        peer.create_message(params)
+            .await
+            .map_err(|e| forwarding_error(&self.server_id, e))
+    }
+
+    async fn list_roots(
+        &self,
+        _context: RequestContext<RoleClient>,
+    ) -> Result<ListRootsResult, McpError> {
+        let peer = self
+            .downstream_peer("roots", |capabilities| capabilities.roots.is_some())
+            .await?;
+        peer.list_roots()
+            .await
+            .map_err(|e| forwarding_error(&self.server_id, e))
+    }
+
+    async fn on_tool_list_changed(&self, _context: NotificationContext<RoleClient>) {
+        info!(
+            "Server {} reported a tools list change, rediscovering its tools",
+            self.server_id
+        );
+        // Rediscovery persists the tools and notifies the downstream sessions
+        if let Err(e) = self.mcp_state.discover_server_tools(&self.server_id).await {
+            warn!(
+                "Failed to rediscover tools for server {}: {e}",
+                self.server_id
+            );
+        }
+    }
+
+    async fn on_resource_list_changed(&self, _context: NotificationContext<RoleClient>) {
+        info!("Server {} reported a resources list change", self.server_id);
+        broadcast_resources_list_changed().await;
+    }
+
+    async fn on_prompt_list_changed(&self, _context: NotificationContext<RoleClient>) {
+        info!("Server {} reported a prompts list change", self.server_id);
+        broadcast_prompts_list_changed().await;
+    }
+
+    async fn on_progress(
+        &self,
+        mut params: ProgressNotificationParam,
+        _context: NotificationContext<RoleClient>,
+    ) {
+        let Some((peer, progress_token)) =
+            SESSION_MANAGER.progress_route(&params.progress_token)
+        else {
+            debug!(
+                "Dropping progress from server {} for unknown token {:?}",
+                self.server_id, params.progress_token
+            );
+            return;
+        };
+        params.progress_token = progress_token;
+        if let Err(e) = peer.notify_progress(params).await {
+            debug!("Failed to relay progress from server {}: {e}", self.server_id);
+        }
+    }
+
+    async fn on_logging_message(
+        &self,
+        mut params: LoggingMessageNotificationParam,
+        _context: NotificationContext<RoleClient>,
+    ) {
+        // The server may log the credentials it was given
+        let secrets = match self.mcp_state.server_secret_values(&self.server_id).await {
+            Ok(secrets) => secrets,
+            Err(e) => {
+                warn!(
+                    "Dropping log message of server {}, its secrets cannot be read: {e}",
+                    self.server_id
+                );
+                return;
+            }
+        };
+        params.data = redact_json_values(params.data, &secrets);
+        params.logger = params.logger.map(|logger| redact_values(&logger, &secrets));
+        let line = format!(
+            "[{}] {}: {}",
+            format!("{:?}", params.level).to_lowercase(),
+            params.logger.as_deref().unwrap_or(&self.server_id),
+            params.data
+        );
+        if let Err(e) = append_server_log(&self.server_id, &line).await {
+            warn!("Failed to write log of server {}: {e}", self.server_id);
+        }
+
+        // Tell the downstream client which server the message comes from
+        params.logger = Some(match params.logger {
+            Some(logger) => format!("{}/{logger}", self.server_id),
+            None => self.server_id.clone(),
+        });
+        match self.server_audience().await {
+            Ok(accepts) => broadcast_log_message(params, accepts).await,
+            Err(e) => warn!(
+                "Not forwarding log message of server {}: {e}",
+                self.server_id
+            ),
+        }
+    }
+}
@@ -1,18 +1,32 @@
+use crate::config::{ClientForwardingConfig, ResultCacheConfig};
+use crate::mcp_server_implementation::notifications::{
+    broadcast_server_lists_changed, broadcast_tools_list_changed,
+};
+use crate::mcp_server_implementation::tools::get_tool_names;
+use crate::mcp_state::client_handler::DockmasterClientHandler;
+use crate::mcp_state::result_cache::ResultCache;
 use crate::mcp_state::tokio_child_process_custom::TokioChildProcessCustom;
 use crate::registry::server_registry::ServerRegistry;
 use crate::types::ServerStatus;
+use crate::types::ArgumentPreset;
 use crate::types::ServerToolInfo;
+use crate::types::ToolOverrides;
+use crate::types::ToolTarget;
 use crate::utils::command::CommandWrappedInShellBuilder;
-use log::{error, info};
+use crate::utils::redaction::{is_redacted, redact_env_value};
+use crate::utils::secrets::secret_references;
+use crate::utils::validate_name;
+use log::{error, info, warn};
 use rmcp::model::{
-    CallToolResult, ClientCapabilities, ClientInfo, Implementation, InitializeRequestParam,
+    CallToolRequest, CallToolRequestParam, CallToolResult, ClientCapabilities, ClientInfo,
+    ClientRequest, GetMeta, Implementation, ProgressToken, ServerResult,
 };
 use rmcp::service::RunningService;
 use rmcp::{RoleClient, ServiceError, ServiceExt};
 use serde_json::{json, Map, Value};
 use std::collections::HashMap;
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, RwLock};
 
 /// MCPState: the main service layer
 ///
@@ -23,13 +37,17 @@ use tokio::sync::RwLock;
 pub struct MCPState {
     pub tool_registry: Arc<RwLock<ServerRegistry>>,
     pub server_tools: Arc<RwLock<HashMap<String, Vec<ServerToolInfo>>>>,
+    /// Exposed tool name -> owning server and upstream name, kept in sync with `server_tools`
+    pub tool_index: Arc<RwLock<HashMap<String, ToolTarget>>>,
     pub mcp_clients: Arc<RwLock<HashMap<String, MCPClient>>>,
     pub are_tools_hidden: Arc<RwLock<bool>>,
+    /// Results of the tools with caching enabled
+    pub result_cache: Arc<Mutex<ResultCache>>,
 }
 
 #[derive(Clone)]
 pub struct MCPClient {
-    pub client: Arc<RunningService<RoleClient, InitializeRequestParam>>,
+    pub client: Arc<RunningService<RoleClient, DockmasterClientHandler>>,
     // pub transport: StdioTransportType,
     pub server_status: ServerStatus,
 }
@@ -42,12 +60,16 @@ impl MCPState {
     ) -> Self {
         // Initialize with default value
         let are_tools_hidden = Arc::new(RwLock::new(false));
+        let tool_index = Arc::new(RwLock::new(HashMap::new()));
+        let result_cache = Arc::new(Mutex::new(ResultCache::new(ResultCacheConfig::from_env())));
 
         Self {
             tool_registry,
             server_tools,
+            tool_index,
             mcp_clients,
             are_tools_hidden,
+            result_cache,
         }
     }
 
@@ -120,38 +142,332 @@ impl MCPState {
                 error!("Cancellation error for client {server_id}: {e}");
             }
         }
-        // Remove the server tools
-        let _ = self.server_tools.write().await.remove(server_id);
+        // Remove the server tools and their routing entries
+        {
+            let mut server_tools = self.server_tools.write().await;
+            let mut tool_index = self.tool_index.write().await;
+            server_tools.remove(server_id);
+            reindex_server_tools(&mut tool_index, &server_tools);
+        }
+        broadcast_server_lists_changed().await;
         Ok(())
     }
 
+    /// Resolve an exposed tool name to the server that owns it
+    pub async fn resolve_tool(&self, tool_name: &str) -> Option<ToolTarget> {
+        self.tool_index.read().await.get(tool_name).cloned()
+    }
+
     /// Execute a tool on a server
+    ///
+    /// When a progress token is given, it is sent along so the server can report progress with it.
     pub async fn execute_tool(
         &self,
         server_id: &str,
         tool_id: &str,
-        parameters: Map<String, Value>,
+        parameters: Option<Map<String, Value>>,
+        progress_token: Option<ProgressToken>,
     ) -> Result<CallToolResult, ServiceError> {
         let mcp_client = self.mcp_clients.read().await.get(server_id).cloned();
         if let Some(mcp_client) = mcp_client {
             info!("[execute tool] Successfully got client for server: {server_id}");
-            let result = mcp_client
-                .client
-                .call_tool(rmcp::model::CallToolRequestParam {
+            let mut request = ClientRequest::CallToolRequest(CallToolRequest::new(
+                CallToolRequestParam {
                     name: tool_id.to_string().into(),
-                    arguments: Some(parameters),
-                })
-                .await?;
+                    arguments: parameters,
+                },
+            ));
+            if let Some(progress_token) = progress_token {
+                request.get_meta_mut().set_progress_token(progress_token);
+            }
 
-            Ok(result)
+            match mcp_client.client.send_request(request).await? {
+                ServerResult::CallToolResult(result) => Ok(result),
+                _ => Err(ServiceError::UnexpectedResponse),
+            }
         } else {
             Err(ServiceError::TransportClosed)
         }
     }
 
+    /// Enable or disable a single tool of a server, `tool_id` may be the tool id or its name.
+    ///
+    /// Inactive tools are neither listed to clients nor routed, the choice is persisted and
+    /// survives rediscovery.
+    pub async fn set_tool_active(
+        &self,
+        server_id: &str,
+        tool_id: &str,
+        active: bool,
+    ) -> Result<ServerToolInfo, String> {
+        // A deactivated tool hands its name over to another server exposing it
+        let tool = self
+            .update_tool(server_id, tool_id, |tool| {
+                tool.is_active = active;
+                Ok(())
+            })
+            .await?;
+        info!(
+            "Tool '{}' of server {server_id} is now {}",
+            tool.name,
+            if active { "active" } else { "inactive" }
+        );
+        broadcast_tools_list_changed().await;
+
+        Ok(tool)
+    }
+
+    /// Change a tool of a server, persist it and re-index the tool names.
+    ///
+    /// `tool_id` may be the tool id, its upstream name or its exposed name. Nothing is saved when
+    /// `update` refuses the change.
+    async fn update_tool(
+        &self,
+        server_id: &str,
+        tool_id: &str,
+        update: impl FnOnce(&mut ServerToolInfo) -> Result<(), String>,
+    ) -> Result<ServerToolInfo, String> {
+        let tool = {
+            let registry = self.tool_registry.read().await;
+            let mut tool = registry
+                .get_server_tools(server_id)?
+                .into_iter()
+                .find(|tool| tool.matches_id(tool_id))
+                .ok_or_else(|| format!("Tool '{tool_id}' not found on server {server_id}"))?;
+            update(&mut tool)?;
+            registry.save_server_tool(&tool)?;
+            tool
+        };
+
+        let mut server_tools = self.server_tools.write().await;
+        let mut tool_index = self.tool_index.write().await;
+        if let Some(tools) = server_tools.get_mut(server_id) {
+            for server_tool in tools.iter_mut().filter(|t| t.id == tool.id) {
+                *server_tool = tool.clone();
+            }
+        }
+        reindex_server_tools(&mut tool_index, &server_tools);
+        Ok(tool)
+    }
+
+    /// Active tools that own their exposed name, by server ID.
+    ///
+    /// Tools shadowed by a server with a smaller ID exposing the same name are left out, they
+    /// cannot be called under that name.
+    pub async fn routed_tools(&self) -> Vec<ServerToolInfo> {
+        let server_tools = self.server_tools.read().await;
+        let tool_index = self.tool_index.read().await;
+        let mut server_ids: Vec<&String> = server_tools.keys().collect();
+        server_ids.sort();
+        server_ids
+            .into_iter()
+            .flat_map(|server_id| &server_tools[server_id])
+            .filter(|tool| {
+                tool.is_active
+                    && tool_index.get(tool.exposed_name()).is_some_and(|target| {
+                        target.server_id == tool.server_id && target.tool_name == tool.name
+                    })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Inactive tool exposed under `tool_name`, if any, to explain why a call is refused
+    pub async fn find_inactive_tool(&self, tool_name: &str) -> Option<ServerToolInfo> {
+        let server_tools = self.server_tools.read().await;
+        server_tools
+            .values()
+            .flatten()
+            .find(|tool| tool.exposed_name() == tool_name && !tool.is_active)
+            .cloned()
+    }
+
+    /// Expose a tool under another name or descriptions, `None` restores the upstream ones.
+    ///
+    /// `tool_id` may be the tool id, its upstream name or its exposed name. The overrides are
+    /// persisted and survive rediscovery; calls to the new name are routed to the upstream tool.
+    pub async fn set_tool_overrides(
+        &self,
+        server_id: &str,
+        tool_id: &str,
+        overrides: Option<ToolOverrides>,
+    ) -> Result<ServerToolInfo, String> {
+        let overrides = overrides.filter(|overrides| !overrides.is_empty());
+        let name = overrides.as_ref().and_then(|o| o.name.clone());
+        if let Some(name) = &name {
+            validate_name("tool", name)?;
+            if get_tool_names().contains(name) {
+                return Err(format!("'{name}' is the name of a built-in tool"));
+            }
+            let registry = self.tool_registry.read().await;
+            if registry.get_composite_tool(name).is_ok() {
+                return Err(format!("'{name}' is the name of a composite tool"));
+            }
+        }
+
+        // A chosen name must route to exactly one tool, upstream names may be shadowed
+        let namesakes: Vec<ServerToolInfo> = self
+            .server_tools
+            .read()
+            .await
+            .values()
+            .flatten()
+            .filter(|other| name.as_deref() == Some(other.exposed_name()))
+            .cloned()
+            .collect();
+
+        let tool = self
+            .update_tool(server_id, tool_id, |tool| {
+                let clash = namesakes
+                    .iter()
+                    .find(|other| !(other.server_id == tool.server_id && other.id == tool.id));
+                if let Some(other) = clash {
+                    return Err(format!(
+                        "Tool name '{}' is already used by tool '{}' of server {}",
+                        other.exposed_name(),
+                        other.name,
+                        other.server_id
+                    ));
+                }
+                tool.overrides = overrides;
+                Ok(())
+            })
+            .await?;
+        info!(
+            "Tool '{}' of server {server_id} is now exposed as '{}'",
+            tool.name,
+            tool.exposed_name()
+        );
+        broadcast_tools_list_changed().await;
+
+        Ok(tool)
+    }
+
+    /// Replace the argument presets of a tool, an empty map removes them.
+    ///
+    /// Every preset must name an argument of the tool's input schema.
+    pub async fn set_tool_argument_presets(
+        &self,
+        server_id: &str,
+        tool_id: &str,
+        presets: HashMap<String, ArgumentPreset>,
+    ) -> Result<ServerToolInfo, String> {
+        let tool = self
+            .update_tool(server_id, tool_id, |tool| {
+                for argument in presets.keys() {
+                    let known = tool
+                        .input_schema
+                        .as_ref()
+                        .is_some_and(|schema| schema.properties.contains_key(argument));
+                    if !known {
+                        return Err(format!(
+                            "Tool '{}' of server {server_id} has no argument '{argument}'",
+                            tool.name
+                        ));
+                    }
+                }
+                tool.argument_presets = presets;
+                Ok(())
+            })
+            .await?;
+        info!(
+            "Tool '{}' of server {server_id} now has {} argument presets",
+            tool.name,
+            tool.argument_presets.len()
+        );
+        broadcast_tools_list_changed().await;
+
+        Ok(tool)
+    }
+
+    /// Cache the results of a tool for `ttl` seconds, `None` disables caching
+    pub async fn set_tool_cache_ttl(
+        &self,
+        server_id: &str,
+        tool_id: &str,
+        ttl: Option<u64>,
+    ) -> Result<ServerToolInfo, String> {
+        if ttl == Some(0) {
+            return Err("The cache TTL must be at least one second".to_string());
+        }
+        let tool = self
+            .update_tool(server_id, tool_id, |tool| {
+                tool.cache_ttl = ttl;
+                Ok(())
+            })
+            .await?;
+        match ttl {
+            Some(ttl) => info!(
+                "Results of tool '{}' of server {server_id} are cached for {ttl}s",
+                tool.name
+            ),
+            None => info!(
+                "Results of tool '{}' of server {server_id} are no longer cached",
+                tool.name
+            ),
+        }
+        self.result_cache
+            .lock()
+            .await
+            .invalidate(server_id, Some(&tool.name));
+
+        Ok(tool)
+    }
+
+    /// Plain values of the secrets a server is given: its secret env values and the vault
+    /// secrets they reference
+    pub async fn server_secret_values(&self, server_id: &str) -> Result<Vec<String>, String> {
+        let registry = self.tool_registry.read().await;
+        let env = registry
+            .get_server(server_id)?
+            .configuration
+            .and_then(|configuration| configuration.env)
+            .unwrap_or_default();
+
+        let mut values = Vec::new();
+        for (key, env) in env {
+            let Some(value) = env.default else {
+                continue;
+            };
+            let value = registry.decrypt_secret(&value)?;
+            // A secret deleted from the vault was never handed to the server
+            values.extend(
+                secret_references(&value)
+                    .into_iter()
+                    .filter_map(|name| registry.get_secret_value(name).ok()),
+            );
+            if is_redacted(&key, env.secret, &value) {
+                values.push(value);
+            }
+        }
+        Ok(values)
+    }
+
+    /// Tell every running server that the roots of the downstream client changed
+    pub async fn notify_roots_list_changed(&self) {
+        // Servers were not told about roots support
+        if !ClientForwardingConfig::from_env().enabled {
+            return;
+        }
+        let mcp_clients: Vec<(String, MCPClient)> = self
+            .mcp_clients
+            .read()
+            .await
+            .iter()
+            .map(|(server_id, mcp_client)| (server_id.clone(), mcp_client.clone()))
+            .collect();
+        for (server_id, mcp_client) in mcp_clients {
+            if let Err(e) = mcp_client.client.notify_roots_list_changed().await {
+                warn!("Failed to notify roots list change to server {server_id}: {e}");
+            }
+        }
+    }
+
     /// Restart a server by its ID
     pub async fn restart_server(&self, server_id: &str) -> Result<(), String> {
         info!("Attempting to restart server: {server_id}");
+        // Results of the previous process may no longer hold
+        self.result_cache.lock().await.invalidate(server_id, None);
 
         // Get tool from database
         let server_data = {
@@ -225,25 +541,42 @@ impl MCPState {
                     server_id
                 );
                 // Convert ToolEnvironment -> resolved values
+                let registry = self.tool_registry.read().await;
                 let mut simple_env_map: HashMap<String, String> = HashMap::new();
                 for (k, tool_env) in env_map.iter() {
                     if let Some(value) = &tool_env.default {
-                        // Resolve template variables in environment values
-                        match crate::validation::resolve_template_variables(value) {
+                        // Secrets are only decrypted here, to hand them to the process
+                        let value = &if tool_env.secret {
+                            registry.decrypt_secret(value).map_err(|e| {
+                                error!("Failed to decrypt environment variable '{k}': {e}");
+                                format!("Failed to decrypt environment variable '{k}': {e}")
+                            })?
+                        } else {
+                            value.clone()
+                        };
+                        // Resolved references hold the vault secrets, only their names are logged
+                        let uses_vault = !secret_references(value).is_empty();
+                        let logged = redact_env_value(k, tool_env.secret, value);
+                        // Resolve template variables and vault secrets in environment values
+                        match crate::validation::resolve_template_variables_with_secrets(value, |name| {
+                            registry.get_secret_value(name)
+                        }) {
                             Ok(resolved_value) => {
-                                if resolved_value != *value {
-                                    info!("Resolved environment variable '{}' template '{}' to '{}'", k, value, resolved_value);
+                                if resolved_value != *value && !uses_vault {
+                                    let resolved = redact_env_value(k, tool_env.secret, &resolved_value);
+                                    info!("Resolved environment variable '{}' template '{}' to '{}'", k, logged, resolved);
                                 }
                                 simple_env_map.insert(k.clone(), resolved_value);
                             },
                             Err(e) => {
-                                error!("Failed to resolve environment variable '{}' template '{}': {}", k, value, e);
+                                error!("Failed to resolve environment variable '{}' template '{}': {}", k, logged, e);
                                 // Use original value as fallback
                                 simple_env_map.insert(k.clone(), value.clone());
                             }
                         }
                     }
                 }
+                drop(registry);
                 Some(simple_env_map)
             } else {
                 info!("No environment variables found for server {server_id}");
@@ -365,9 +698,20 @@ impl MCPState {
             }
         }
 
+        // Capabilities are fixed before any downstream client connects, so they are declared here
+        // and each forwarded request is checked against the client that receives it
+        let capabilities = if ClientForwardingConfig::from_env().enabled {
+            ClientCapabilities::builder()
+                .enable_roots()
+                .enable_roots_list_changed()
+                .enable_sampling()
+                .build()
+        } else {
+            ClientCapabilities::default()
+        };
         let client_info = ClientInfo {
             protocol_version: Default::default(),
-            capabilities: ClientCapabilities::default(),
+            capabilities,
             client_info: Implementation {
                 name: "dockmaster-mcp-client".into(),
                 version: env!("CARGO_PKG_VERSION").into(),
@@ -384,7 +728,9 @@ impl MCPState {
                     format!("Failed to create tokio child process for server '{}': {}", server_id, e)
                 }
             })?;
-        let service = client_info
+        let client_handler =
+            DockmasterClientHandler::new(server_id.to_string(), self.clone(), client_info);
+        let service = client_handler
             .serve(tokio_child_process)
             .await
             .map_err(|e| {
@@ -446,9 +792,16 @@ impl MCPState {
         //     }
         // }
 
+        drop(are_tools_hidden);
+
         // Persist the state to the database
-        let registry = self.tool_registry.read().await;
-        registry.save_setting("tools_hidden", if hidden { "true" } else { "false" })
+        {
+            let registry = self.tool_registry.read().await;
+            registry.save_setting("tools_hidden", if hidden { "true" } else { "false" })?;
+        }
+
+        broadcast_tools_list_changed().await;
+        Ok(())
     }
 
     pub async fn discover_server_tools(
@@ -465,10 +818,11 @@ impl MCPState {
                 ServerStatus::Running => {
                     info!("Server status is Running, about to call list_tools");
 
-                    let list_tools = match mcp_client.client.list_tools(None).await {
-                        Ok(result) => {
+                    // Follow next_cursor so servers that paginate tools/list are fully discovered
+                    let tools = match mcp_client.client.list_all_tools().await {
+                        Ok(tools) => {
                             info!("mcp_client: list_tools call succeeded");
-                            result
+                            tools
                         }
                         Err(e) => {
                             error!("mcp_client: list_tools call failed: {e}");
@@ -476,35 +830,25 @@ impl MCPState {
                         }
                     };
 
-                    let tools = list_tools.tools;
                     info!(
                         "Successfully discovered {} tools for {}",
                         tools.len(),
                         server_id
                     );
 
-                    // Save the tools to the database
-                    let registry = self.tool_registry.read().await;
-
-                    let mut server_tool_infos = Vec::new();
-                    for tool in &tools {
-                        info!("Saving tool info to database: {tool:?}");
-                        let server_tool_info =
-                            ServerToolInfo::from_tool(tool.clone(), server_id.to_string())
-                                .inspect_err(|e| {
-                                    error!("failed to create server tool info from tool: {e}")
-                                })?;
-                        registry
-                            .save_server_tool(&server_tool_info)
-                            .inspect_err(|e| {
-                                error!("failed to save server tool to database: {e}")
-                            })?;
-                        server_tool_infos.push(server_tool_info);
-                    }
-
-                    // Save the tools to the server_tools map
-                    let mut server_tools = self.server_tools.write().await;
-                    server_tools.insert(server_id.to_string(), server_tool_infos.clone());
+                    let server_tool_infos = tools
+                        .into_iter()
+                        .map(|tool| {
+                            info!("Saving tool info to database: {tool:?}");
+                            ServerToolInfo::from_tool(tool, server_id.to_string()).inspect_err(
+                                |e| error!("failed to create server tool info from tool: {e}"),
+                            )
+                        })
+                        .collect::<Result<Vec<_>, String>>()?;
+                    let server_tool_infos = self
+                        .save_discovered_tools(server_id, server_tool_infos)
+                        .await?;
+                    broadcast_server_lists_changed().await;
 
                     Ok(server_tool_infos)
                 }
@@ -515,4 +859,91 @@ impl MCPState {
             Err(format!("No client found for server: {server_id}"))
         }
     }
+
+    /// Store the tools a server listed and route their names to it.
+    ///
+    /// Tools keep what the user chose for them: disabled, renamed, described, presets and
+    /// caching. Stored tools missing from the listing keep their rows and are left out until the
+    /// server provides them again, so a tool omitted for one restart loses none of these.
+    pub async fn save_discovered_tools(
+        &self,
+        server_id: &str,
+        discovered: Vec<ServerToolInfo>,
+    ) -> Result<Vec<ServerToolInfo>, String> {
+        let mut server_tool_infos = Vec::with_capacity(discovered.len());
+        {
+            let registry = self.tool_registry.read().await;
+            let stored_tools = registry.get_server_tools(server_id).unwrap_or_default();
+            for mut server_tool_info in discovered {
+                if let Some(stored_tool) = stored_tools
+                    .iter()
+                    .find(|stored_tool| stored_tool.id == server_tool_info.id)
+                {
+                    server_tool_info.is_active = stored_tool.is_active;
+                    server_tool_info.overrides = stored_tool.overrides.clone();
+                    server_tool_info.argument_presets = stored_tool.argument_presets.clone();
+                    server_tool_info.cache_ttl = stored_tool.cache_ttl;
+                }
+                registry
+                    .save_server_tool(&server_tool_info)
+                    .inspect_err(|e| error!("failed to save server tool to database: {e}"))?;
+                server_tool_infos.push(server_tool_info);
+            }
+            for stored_tool in &stored_tools {
+                if !server_tool_infos
+                    .iter()
+                    .any(|tool| tool.id == stored_tool.id)
+                {
+                    info!(
+                        "Tool '{}' is not provided by {server_id} for now",
+                        stored_tool.name
+                    );
+                }
+            }
+        }
+
+        // Save the tools to the server_tools map and route their names to this server
+        let mut server_tools = self.server_tools.write().await;
+        let mut tool_index = self.tool_index.write().await;
+        server_tools.insert(server_id.to_string(), server_tool_infos.clone());
+        reindex_server_tools(&mut tool_index, &server_tools);
+
+        for tool in &server_tool_infos {
+            if let Some(target) = tool_index.get(tool.exposed_name()) {
+                if target.server_id != server_id {
+                    warn!(
+                        "Tool '{}' of server {} is shadowed by server {}",
+                        tool.exposed_name(),
+                        server_id,
+                        target.server_id
+                    );
+                }
+            }
+        }
+        Ok(server_tool_infos)
+    }
+}
+
+/// Rebuild the routing entries from the active tools of `server_tools`.
+///
+/// Tools are indexed by their exposed name and point to their upstream name. A name exposed by
+/// several servers is routed to the server with the smallest ID and the others are shadowed, so
+/// the owner does not depend on the order the servers were started or rediscovered in.
+fn reindex_server_tools(
+    tool_index: &mut HashMap<String, ToolTarget>,
+    server_tools: &HashMap<String, Vec<ServerToolInfo>>,
+) {
+    tool_index.clear();
+    let mut server_ids: Vec<&String> = server_tools.keys().collect();
+    server_ids.sort();
+    for owner_id in server_ids {
+        for tool in server_tools[owner_id].iter().filter(|tool| tool.is_active) {
+            tool_index
+                .entry(tool.exposed_name().to_string())
+                .or_insert_with(|| ToolTarget {
+                    server_id: owner_id.clone(),
+                    tool_name: tool.name.clone(),
+                });
+        }
+    }
 }
@@ -1,4 +1,7 @@
 // fix this once we finish the refactor having a single entry point (McpCore)
 #![allow(clippy::module_inception)]
+pub mod client_handler;
 pub mod mcp_state;
+pub mod result_cache;
+pub mod server_logs;
 pub mod tokio_child_process_custom;
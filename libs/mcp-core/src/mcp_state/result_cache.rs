@@ -0,0 +1,180 @@
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+
+use serde_json::{Map, Value};
+
+use crate::config::ResultCacheConfig;
+use crate::types::ResultCacheStats;
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+struct CacheKey {
+    server_id: String,
+    tool_name: String,
+    /// Canonical JSON of the arguments
+    arguments: String,
+}
+
+struct CacheEntry {
+    result: Value,
+    bytes: usize,
+    expires_at: Instant,
+    last_used: Instant,
+}
+
+/// Results of tool calls, shared by every session.
+///
+/// Entries expire after the TTL they were stored with. Past the entry or size limit the least
+/// recently used entries are evicted.
+pub struct ResultCache {
+    config: ResultCacheConfig,
+    entries: HashMap<CacheKey, CacheEntry>,
+    bytes: usize,
+    hits: u64,
+    misses: u64,
+}
+
+/// JSON of the arguments with the object keys sorted at every level
+pub fn canonical_arguments(arguments: &Option<Map<String, Value>>) -> String {
+    fn canonical(value: &Value) -> Value {
+        match value {
+            Value::Object(object) => {
+                let mut entries: Vec<(&String, &Value)> = object.iter().collect();
+                entries.sort_by(|a, b| a.0.cmp(b.0));
+                Value::Object(
+                    entries
+                        .into_iter()
+                        .map(|(key, value)| (key.clone(), canonical(value)))
+                        .collect(),
+                )
+            }
+            Value::Array(values) => Value::Array(values.iter().map(canonical).collect()),
+            value => value.clone(),
+        }
+    }
+
+    let arguments = Value::Object(arguments.clone().unwrap_or_default());
+    canonical(&arguments).to_string()
+}
+
+impl ResultCache {
+    pub fn new(config: ResultCacheConfig) -> Self {
+        Self {
+            config,
+            entries: HashMap::new(),
+            bytes: 0,
+            hits: 0,
+            misses: 0,
+        }
+    }
+
+    fn key(server_id: &str, tool_name: &str, arguments: &Option<Map<String, Value>>) -> CacheKey {
+        CacheKey {
+            server_id: server_id.to_string(),
+            tool_name: tool_name.to_string(),
+            arguments: canonical_arguments(arguments),
+        }
+    }
+
+    /// Cached result of a call, counted as a hit or a miss
+    pub fn get(
+        &mut self,
+        server_id: &str,
+        tool_name: &str,
+        arguments: &Option<Map<String, Value>>,
+    ) -> Option<Value> {
+        let key = Self::key(server_id, tool_name, arguments);
+        let now = Instant::now();
+        if self
+            .entries
+            .get(&key)
+            .is_some_and(|entry| entry.expires_at <= now)
+        {
+            self.remove(&key);
+        }
+        match self.entries.get_mut(&key) {
+            Some(entry) => {
+                entry.last_used = now;
+                self.hits += 1;
+                Some(entry.result.clone())
+            }
+            None => {
+                self.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Store the result of a call for `ttl`, results larger than the cache are not stored
+    pub fn insert(
+        &mut self,
+        server_id: &str,
+        tool_name: &str,
+        arguments: &Option<Map<String, Value>>,
+        result: Value,
+        ttl: Duration,
+    ) {
+        let bytes = result.to_string().len();
+        if bytes > self.config.max_bytes || self.config.max_entries == 0 {
+            return;
+        }
+        let key = Self::key(server_id, tool_name, arguments);
+        self.remove(&key);
+
+        let now = Instant::now();
+        self.entries.retain(|_, entry| entry.expires_at > now);
+        self.bytes = self.entries.values().map(|entry| entry.bytes).sum();
+        while self.entries.len() >= self.config.max_entries
+            || self.bytes + bytes > self.config.max_bytes
+        {
+            let Some(oldest) = self
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.last_used)
+                .map(|(key, _)| key.clone())
+            else {
+                break;
+            };
+            self.remove(&oldest);
+        }
+
+        self.bytes += bytes;
+        self.entries.insert(
+            key,
+            CacheEntry {
+                result,
+                bytes,
+                expires_at: now + ttl,
+                last_used: now,
+            },
+        );
+    }
+
+    fn remove(&mut self, key: &CacheKey) {
+        if let Some(entry) = self.entries.remove(key) {
+            self.bytes -= entry.bytes;
+        }
+    }
+
+    /// Forget the results of a server's tools, or of one of them
+    pub fn invalidate(&mut self, server_id: &str, tool_name: Option<&str>) {
+        self.entries.retain(|key, _| {
+            key.server_id != server_id
+                || tool_name.is_some_and(|tool_name| key.tool_name != tool_name)
+        });
+        self.bytes = self.entries.values().map(|entry| entry.bytes).sum();
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+        self.bytes = 0;
+    }
+
+    pub fn stats(&self) -> ResultCacheStats {
+        ResultCacheStats {
+            hits: self.hits,
+            misses: self.misses,
+            entries: self.entries.len(),
+            bytes: self.bytes,
+        }
+    }
+}
@@ -0,0 +1,146 @@
+use std::io::SeekFrom;
+use std::path::{Path, PathBuf};
+
+use tokio::io::{AsyncReadExt, AsyncSeekExt, AsyncWriteExt};
+
+use crate::config::ServerLogConfig;
+use crate::utils::{default_storage_path, unix_timestamp};
+
+/// Environment variable overriding the directory of the per-server logs
+pub const SERVER_LOGS_DIR_ENV: &str = "DOCKMASTER_SERVER_LOGS_DIR";
+
+/// Bytes read at a time when reading a log backwards from its end
+const READ_CHUNK_BYTES: u64 = 16 * 1024;
+
+/// Directory holding one log file per server
+pub fn server_logs_dir() -> Result<PathBuf, String> {
+    match std::env::var(SERVER_LOGS_DIR_ENV) {
+        Ok(dir) if !dir.is_empty() => Ok(PathBuf::from(dir)),
+        _ => Ok(default_storage_path()?.join("logs")),
+    }
+}
+
+/// Log file of a server.
+///
+/// Bytes of the id other than ASCII letters, digits and `-` are escaped as `_` and their hex
+/// value, so the id is always a single file name and distinct ids never share a file.
+pub fn server_log_path(server_id: &str) -> Result<PathBuf, String> {
+    let mut file_name = String::with_capacity(server_id.len());
+    for byte in server_id.bytes() {
+        if byte.is_ascii_alphanumeric() || byte == b'-' {
+            file_name.push(byte as char);
+        } else {
+            file_name.push_str(&format!("_{byte:02x}"));
+        }
+    }
+    Ok(server_logs_dir()?.join(format!("{file_name}.log")))
+}
+
+/// Previous log of a server, where its log is moved once it outgrows the size cap
+pub fn rotated_server_log_path(server_id: &str) -> Result<PathBuf, String> {
+    Ok(server_log_path(server_id)?.with_extension("log.1"))
+}
+
+/// Append a line to the log of a server, rotating the log when it gets too large
+pub async fn append_server_log(server_id: &str, line: &str) -> Result<(), String> {
+    let path = server_log_path(server_id)?;
+    if let Some(parent) = path.parent() {
+        tokio::fs::create_dir_all(parent)
+            .await
+            .map_err(|e| format!("Failed to create server logs directory: {e}"))?;
+    }
+
+    let timestamp = unix_timestamp();
+
+    let mut file = tokio::fs::OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&path)
+        .await
+        .map_err(|e| format!("Failed to open server log {}: {e}", path.display()))?;
+    file.write_all(format!("{timestamp} {line}\n").as_bytes())
+        .await
+        .map_err(|e| format!("Failed to write server log {}: {e}", path.display()))?;
+    // Tokio files write in the background, wait until the line is on disk
+    file.flush()
+        .await
+        .map_err(|e| format!("Failed to write server log {}: {e}", path.display()))?;
+
+    let max_bytes = ServerLogConfig::from_env().max_bytes;
+    let size = file
+        .metadata()
+        .await
+        .map_err(|e| format!("Failed to read server log {}: {e}", path.display()))?
+        .len();
+    drop(file);
+    if max_bytes > 0 && size > max_bytes {
+        // Another append may have rotated the log already
+        match tokio::fs::rename(&path, rotated_server_log_path(server_id)?).await {
+            Ok(()) => {}
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => {}
+            Err(e) => {
+                return Err(format!(
+                    "Failed to rotate server log {}: {e}",
+                    path.display()
+                ))
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Last `max_lines` lines of the log of a server, empty when it has not logged anything yet.
+///
+/// Only the end of the log is read, the previous log is read too when the current one holds
+/// fewer lines.
+pub async fn read_server_log(server_id: &str, max_lines: usize) -> Result<Vec<String>, String> {
+    let mut lines = read_last_lines(&server_log_path(server_id)?, max_lines).await?;
+    if lines.len() < max_lines {
+        let mut previous = read_last_lines(
+            &rotated_server_log_path(server_id)?,
+            max_lines - lines.len(),
+        )
+        .await?;
+        previous.append(&mut lines);
+        lines = previous;
+    }
+    Ok(lines)
+}
+
+/// Last `max_lines` lines of a file, read backwards from its end, empty when it does not exist
+async fn read_last_lines(path: &Path, max_lines: usize) -> Result<Vec<String>, String> {
+    if max_lines == 0 {
+        return Ok(Vec::new());
+    }
+    let mut file = match tokio::fs::File::open(path).await {
+        Ok(file) => file,
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => return Ok(Vec::new()),
+        Err(e) => return Err(format!("Failed to read server log {}: {e}", path.display())),
+    };
+    let read_error =
+        |e: std::io::Error| format!("Failed to read server log {}: {e}", path.display());
+
+    let mut end = file.metadata().await.map_err(read_error)?.len();
+    let mut tail: Vec<u8> = Vec::new();
+    let mut newlines = 0;
+    // One newline more than lines wanted, so the first line kept is complete
+    while end > 0 && newlines <= max_lines {
+        let start = end.saturating_sub(READ_CHUNK_BYTES);
+        let mut chunk = vec![0u8; (end - start) as usize];
+        file.seek(SeekFrom::Start(start))
+            .await
+            .map_err(read_error)?;
+        file.read_exact(&mut chunk).await.map_err(read_error)?;
+        newlines += chunk.iter().filter(|byte| **byte == b'\n').count();
+        chunk.append(&mut tail);
+        tail = chunk;
+        end = start;
+    }
+
+    let content = String::from_utf8_lossy(&tail);
+    let lines: Vec<&str> = content.lines().collect();
+    Ok(lines[lines.len().saturating_sub(max_lines)..]
+        .iter()
+        .map(|line| line.to_string())
+        .collect())
+}
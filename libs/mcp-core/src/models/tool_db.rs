@@ -1,4 +1,7 @@
-use crate::schema::{app_settings, server_env, server_tools, servers};
+use crate::schema::{
+    app_settings, client_policies, composite_tools, profiles, secrets, server_env, server_tools,
+    servers,
+};
 use diesel::prelude::*;
 
 /// This struct corresponds to a row in the `tools` table.
@@ -66,6 +69,7 @@ pub struct DBServerEnv {
     pub env_value: String,
     pub env_description: String,
     pub env_required: bool,
+    pub env_secret: bool,
 }
 
 /// For inserting a new row into the `server_env` table
@@ -77,6 +81,7 @@ pub struct NewServerEnv {
     pub env_value: String,
     pub env_description: String,
     pub env_required: bool,
+    pub env_secret: bool,
 }
 
 /// For updating an existing row in the `server_env` table
@@ -87,6 +92,7 @@ pub struct UpdateServerEnv<'a> {
     pub env_value: Option<&'a str>,
     pub env_description: Option<&'a str>,
     pub env_required: Option<bool>,
+    pub env_secret: Option<bool>,
 }
 
 /// This struct corresponds to a row in the `server_tools` table.
@@ -100,6 +106,11 @@ pub struct DBServerTool {
     pub server_id: String,
     pub proxy_id: Option<String>,
     pub is_active: bool,
+    pub title: Option<String>,
+    pub annotations: Option<String>,
+    pub overrides: Option<String>,
+    pub argument_presets: Option<String>,
+    pub cache_ttl: Option<i32>,
 }
 
 /// For inserting a new row into the `server_tools` table
@@ -113,6 +124,11 @@ pub struct NewServerTool {
     pub server_id: String,
     pub proxy_id: Option<String>,
     pub is_active: bool,
+    pub title: Option<String>,
+    pub annotations: Option<String>,
+    pub overrides: Option<String>,
+    pub argument_presets: Option<String>,
+    pub cache_ttl: Option<i32>,
 }
 
 /// For updating an existing row in the `server_tools` table
@@ -125,6 +141,11 @@ pub struct UpdateServerTool {
     pub input_schema: Option<Option<String>>,
     pub proxy_id: Option<Option<String>>,
     pub is_active: Option<bool>,
+    pub title: Option<Option<String>>,
+    pub annotations: Option<Option<String>>,
+    pub overrides: Option<Option<String>>,
+    pub argument_presets: Option<Option<String>>,
+    pub cache_ttl: Option<Option<i32>>,
 }
 
 /// This struct corresponds to a row in the `app_settings` table.
@@ -142,3 +163,92 @@ pub struct NewAppSetting<'a> {
     pub key: &'a str,
     pub value: &'a str,
 }
+
+/// This struct corresponds to a row in the `profiles` table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = profiles)]
+pub struct DBProfile {
+    pub name: String,
+    pub description: String,
+    pub servers: String,
+    pub tools: String,
+    pub token_budget: Option<i32>,
+    pub schema_strictness: Option<String>,
+}
+
+/// For inserting or updating a row in the `profiles` table
+#[derive(Debug, Insertable, AsChangeset)]
+#[diesel(table_name = profiles)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewProfile<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    pub servers: &'a str,
+    pub tools: &'a str,
+    pub token_budget: Option<i32>,
+    pub schema_strictness: Option<&'a str>,
+}
+
+/// This struct corresponds to a row in the `client_policies` table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = client_policies)]
+pub struct DBClientPolicy {
+    pub profile: String,
+    pub allowed_servers: Option<String>,
+    pub max_calls_per_minute: Option<i32>,
+    pub require_approval: bool,
+    pub schema_strictness: Option<String>,
+}
+
+/// For inserting or updating a row in the `client_policies` table
+#[derive(Debug, Insertable, AsChangeset)]
+#[diesel(table_name = client_policies)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewClientPolicy<'a> {
+    pub profile: &'a str,
+    pub allowed_servers: Option<&'a str>,
+    pub max_calls_per_minute: Option<i32>,
+    pub require_approval: bool,
+    pub schema_strictness: Option<&'a str>,
+}
+
+/// This struct corresponds to a row in the `composite_tools` table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = composite_tools)]
+pub struct DBCompositeTool {
+    pub name: String,
+    pub description: String,
+    pub input_schema: String,
+    pub steps: String,
+    pub output: Option<String>,
+}
+
+/// For inserting or updating a row in the `composite_tools` table
+#[derive(Debug, Insertable, AsChangeset)]
+#[diesel(table_name = composite_tools)]
+#[diesel(treat_none_as_null = true)]
+pub struct NewCompositeTool<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    pub input_schema: &'a str,
+    pub steps: &'a str,
+    pub output: Option<&'a str>,
+}
+
+/// This struct corresponds to a row in the `secrets` table.
+#[derive(Debug, Queryable, Selectable)]
+#[diesel(table_name = secrets)]
+pub struct DBSecret {
+    pub name: String,
+    pub description: String,
+    pub value: String,
+}
+
+/// For inserting or updating a row in the `secrets` table
+#[derive(Debug, Insertable, AsChangeset)]
+#[diesel(table_name = secrets)]
+pub struct NewSecret<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    pub value: &'a str,
+}
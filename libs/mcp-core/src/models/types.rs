@@ -1,11 +1,19 @@
-use rmcp::model::{JsonObject, Tool};
+use rmcp::model::{JsonObject, ProgressToken, Tool, ToolAnnotations};
 use serde::{Deserialize, Serialize};
 use serde_json::{Map, Value};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt;
 use std::hash::{Hash, Hasher};
+use std::str::FromStr;
 use std::sync::Arc;
+use std::time::Duration;
+
+use crate::config::ArgumentValidationConfig;
+use crate::utils::argument_validation::{self, ArgumentViolation};
+use crate::utils::schema_normalization::normalize_schema;
+use crate::utils::redaction::redact_env;
+use crate::utils::secrets::secret_references;
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ServerId(String);
@@ -89,7 +97,7 @@ pub struct ToolConfigUpdateResponse {
 }
 
 /// Tool configuration for command and arguments
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Serialize, Deserialize)]
 pub struct ServerConfiguration {
     #[serde(default)]
     pub command: Option<String>,
@@ -99,6 +107,21 @@ pub struct ServerConfiguration {
     pub env: Option<HashMap<String, ServerEnvironment>>,
 }
 
+/// Configurations end up in the logs, the secret environment values are masked
+impl fmt::Debug for ServerConfiguration {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let mut env = self.env.clone();
+        if let Some(env) = &mut env {
+            redact_env(env);
+        }
+        f.debug_struct("ServerConfiguration")
+            .field("command", &self.command)
+            .field("args", &self.args)
+            .field("env", &env)
+            .finish()
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ServerEnvironment {
     #[serde(default)]
@@ -107,6 +130,9 @@ pub struct ServerEnvironment {
     pub default: Option<String>,
     #[serde(default)]
     pub required: bool,
+    /// The value is encrypted at rest and only decrypted to start the server
+    #[serde(default)]
+    pub secret: bool,
 }
 
 // ToolConfig struct has been removed and merged into ToolConfiguration
@@ -132,6 +158,20 @@ pub struct ServerDefinition {
     pub executable_path: Option<String>,
 }
 
+impl ServerDefinition {
+    /// Whether an env value of the server references the vault secret `name`
+    pub fn uses_secret(&self, name: &str) -> bool {
+        self.configuration
+            .as_ref()
+            .and_then(|configuration| configuration.env.as_ref())
+            .is_some_and(|env| {
+                env.values()
+                    .filter_map(|env| env.default.as_deref())
+                    .any(|value| secret_references(value).contains(&name))
+            })
+    }
+}
+
 #[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RuntimeServer {
     #[serde(flatten)]
@@ -139,6 +179,9 @@ pub struct RuntimeServer {
     pub id: ServerId,
     pub status: ServerStatus,
     pub tool_count: usize,
+    /// Size of the definitions of the active tools
+    #[serde(default)]
+    pub tool_size: ToolSize,
 }
 
 /// MCP server registration request
@@ -185,11 +228,21 @@ pub struct ServerRegistrationResponse {
     pub tool_id: Option<String>,
 }
 
+/// Routing target of a proxied tool: the server that owns it and its upstream name
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ToolTarget {
+    pub server_id: String,
+    pub tool_name: String,
+}
+
 /// MCP tool execution request
 #[derive(Deserialize)]
 pub struct ToolExecutionRequest {
-    pub tool_id: String,
+    pub target: ToolTarget,
     pub parameters: Option<Map<String, Value>>,
+    /// Token the upstream server reports progress of this call with
+    #[serde(default)]
+    pub progress_token: Option<ProgressToken>,
 }
 
 /// MCP tool execution response
@@ -443,12 +496,336 @@ pub struct ServerToolInfo {
     pub proxy_id: Option<String>,
     #[serde(default = "default_is_active")]
     pub is_active: bool,
+    /// Human readable name of the tool
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    /// Behavior hints such as `readOnlyHint` and `destructiveHint`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub annotations: Option<ToolAnnotations>,
+    /// User overrides of what clients see, `name` and `description` stay the upstream values
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub overrides: Option<ToolOverrides>,
+    /// Argument name -> value set by the user instead of the model
+    #[serde(default, rename = "argumentPresets", skip_serializing_if = "HashMap::is_empty")]
+    pub argument_presets: HashMap<String, ArgumentPreset>,
+    /// Seconds results of the tool stay cached, `None` disables caching
+    #[serde(default, rename = "cacheTtl", skip_serializing_if = "Option::is_none")]
+    pub cache_ttl: Option<u64>,
+    /// Size of the definition clients see, filled in when tools are listed
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<ToolSize>,
+}
+
+/// Context a tool definition takes, measured on its JSON as sent in `tools/list`
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ToolSize {
+    pub bytes: usize,
+    /// Estimated from `bytes`, tokenizers average about 4 bytes per token on JSON
+    pub tokens: usize,
+}
+
+impl ToolSize {
+    const BYTES_PER_TOKEN: usize = 4;
+
+    pub fn of(tool: &Tool) -> Self {
+        let bytes = serde_json::to_vec(tool).map_or(0, |json| json.len());
+        Self {
+            bytes,
+            tokens: bytes.div_ceil(Self::BYTES_PER_TOKEN),
+        }
+    }
+}
+
+impl std::iter::Sum for ToolSize {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::default(), |total, size| Self {
+            bytes: total.bytes + size.bytes,
+            tokens: total.tokens + size.tokens,
+        })
+    }
+}
+
+/// Counters of the cache of tool results since startup
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResultCacheStats {
+    pub hits: u64,
+    /// Calls of cached tools that went to the server
+    pub misses: u64,
+    pub entries: usize,
+    pub bytes: usize,
 }
 
 fn default_is_active() -> bool {
     true
 }
 
+/// Name and descriptions a tool is exposed with instead of the ones its server reports
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ToolOverrides {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub description: Option<String>,
+    /// Argument name -> description
+    #[serde(default, rename = "argumentDescriptions", skip_serializing_if = "HashMap::is_empty")]
+    pub argument_descriptions: HashMap<String, String>,
+}
+
+/// Named subset of the servers and tools, served to the clients connected to `/sse/{name}`
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct Profile {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// Servers whose tools are all included
+    #[serde(default)]
+    pub servers: Vec<String>,
+    /// Single tools included in addition to the ones of `servers`
+    #[serde(default)]
+    pub tools: Vec<ProfileTool>,
+    /// Estimated tokens the listed tool definitions may take, see [`ToolSize`]
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub token_budget: Option<u32>,
+    /// Normalization of the listed input schemas, unless the client's policy sets one
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_strictness: Option<SchemaStrictness>,
+}
+
+/// How far the input schemas listed to a client are normalized, see
+/// [`normalize_schema`](crate::utils::schema_normalization::normalize_schema)
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum SchemaStrictness {
+    /// Input schemas as the servers report them
+    #[default]
+    Off,
+    /// References inlined, `allOf` flattened, meta keywords removed and nesting limited
+    Standard,
+    /// Standard, keeping only the keywords every client accepts
+    Strict,
+}
+
+impl SchemaStrictness {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SchemaStrictness::Off => "off",
+            SchemaStrictness::Standard => "standard",
+            SchemaStrictness::Strict => "strict",
+        }
+    }
+}
+
+impl fmt::Display for SchemaStrictness {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.as_str())
+    }
+}
+
+impl FromStr for SchemaStrictness {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "off" => Ok(SchemaStrictness::Off),
+            "standard" => Ok(SchemaStrictness::Standard),
+            "strict" => Ok(SchemaStrictness::Strict),
+            _ => Err(format!(
+                "Invalid schema strictness '{s}', expected off, standard or strict"
+            )),
+        }
+    }
+}
+
+/// A tool of a server, `tool_id` may be the tool id, its upstream name or its exposed name
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ProfileTool {
+    pub server_id: String,
+    pub tool_id: String,
+}
+
+impl Profile {
+    /// Whether clients of this profile see and may call the tool
+    pub fn includes(&self, tool: &ServerToolInfo) -> bool {
+        self.servers.contains(&tool.server_id)
+            || self
+                .tools
+                .iter()
+                .any(|t| t.server_id == tool.server_id && tool.matches_id(&t.tool_id))
+    }
+
+    /// Whether clients of this profile see the server, with all its tools or some of them
+    pub fn includes_server(&self, server_id: &str) -> bool {
+        self.servers.iter().any(|id| id == server_id)
+            || self.tools.iter().any(|t| t.server_id == server_id)
+    }
+
+    /// Rank of an included tool when the token budget is exceeded, lower ranks are kept longer.
+    ///
+    /// Single tools come first in their order, then the tools of `servers` in server order.
+    pub fn priority(&self, tool: &ServerToolInfo) -> usize {
+        let single = self
+            .tools
+            .iter()
+            .position(|t| t.server_id == tool.server_id && tool.matches_id(&t.tool_id));
+        let server = || self.servers.iter().position(|id| *id == tool.server_id);
+        single
+            .or_else(|| server().map(|i| self.tools.len() + i))
+            .unwrap_or(self.tools.len() + self.servers.len())
+    }
+}
+
+/// A downstream client connected to the SSE endpoint
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct SessionInfo {
+    pub session_id: String,
+    /// Name and version the client sent in `initialize`
+    pub client_name: String,
+    pub client_version: String,
+    /// Profile the client connected to, `None` for `/sse`
+    pub profile: Option<String>,
+    /// Seconds since the Unix epoch
+    pub connected_at: u64,
+    pub tool_calls: u64,
+}
+
+/// Rules applied to the tool calls of the clients connected through a profile.
+///
+/// Policies are keyed on the profile set in the client's config rather than on the name a
+/// client reports in `clientInfo`, which any client may choose.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ClientPolicy {
+    /// Profile of the connections, `*` applies to the connections without a profile or whose
+    /// profile has no policy of its own
+    pub profile: String,
+    /// Servers whose tools the client may list and call, `None` allows every server
+    #[serde(default)]
+    pub allowed_servers: Option<Vec<String>>,
+    #[serde(default)]
+    pub max_calls_per_minute: Option<u32>,
+    /// Tool calls wait until the user approves them
+    #[serde(default)]
+    pub require_approval: bool,
+    /// Normalization of the listed input schemas, for clients that reject parts of JSON Schema
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub schema_strictness: Option<SchemaStrictness>,
+}
+
+impl ClientPolicy {
+    /// Profile of the policy applied to connections without a policy of their own
+    pub const ANY_PROFILE: &'static str = "*";
+
+    pub fn allows_server(&self, server_id: &str) -> bool {
+        self.allowed_servers
+            .as_ref()
+            .is_none_or(|servers| servers.iter().any(|server| server == server_id))
+    }
+}
+
+/// A tool call waiting for the user to approve it
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct PendingApproval {
+    pub id: String,
+    pub session_id: String,
+    pub client_name: String,
+    pub server_id: String,
+    pub tool_name: String,
+    pub arguments: Option<Map<String, Value>>,
+    /// Seconds since the Unix epoch
+    pub requested_at: u64,
+}
+
+/// A user-defined tool that runs a pipeline of upstream tool calls
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompositeTool {
+    /// Name the tool is listed under, next to the proxied tools
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// JSON Schema of the arguments, they are available to the steps as `$.input`
+    #[serde(default = "default_composite_input_schema")]
+    pub input_schema: Map<String, Value>,
+    pub steps: Vec<CompositeStep>,
+    /// Template of the combined output, the result of the last step when missing
+    #[serde(default)]
+    pub output: Option<Value>,
+}
+
+/// A call of an upstream tool inside a composite tool.
+///
+/// String values of `arguments` starting with `$` are JSONPath expressions evaluated against
+/// `{"input": ..., "steps": {"<id>": <result>}, "item": ...}`; a leading `$$` escapes a literal `$`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct CompositeStep {
+    /// Key the result of the step is available under, as `$.steps.<id>`
+    pub id: String,
+    pub server_id: String,
+    /// ID or name of the tool on the server
+    pub tool_id: String,
+    #[serde(default)]
+    pub arguments: Map<String, Value>,
+    /// JSONPath selecting a list, the tool is called once per element with the element as
+    /// `$.item` and the step result is the list of the results
+    #[serde(default)]
+    pub for_each: Option<String>,
+}
+
+fn default_composite_input_schema() -> Map<String, Value> {
+    Map::from_iter([
+        ("type".to_string(), Value::String("object".to_string())),
+        ("properties".to_string(), Value::Object(Map::new())),
+    ])
+}
+
+impl CompositeTool {
+    /// Arguments coerced and checked against the input schema as configured
+    pub fn prepare_arguments(
+        &self,
+        arguments: Option<Map<String, Value>>,
+        config: &ArgumentValidationConfig,
+    ) -> Result<Option<Map<String, Value>>, Vec<ArgumentViolation>> {
+        let schema = Value::Object(self.input_schema.clone());
+        argument_validation::prepare_arguments(&schema, arguments, config)
+    }
+
+    /// Tool definition listed to clients, with its input schema normalized to `strictness`
+    pub fn to_tool(&self, strictness: SchemaStrictness) -> Tool {
+        Tool {
+            name: self.name.clone().into(),
+            description: Some(self.description.clone().into()),
+            input_schema: Arc::new(normalize_schema(&self.input_schema, strictness)),
+            annotations: None,
+        }
+    }
+}
+
+/// Value of a tool argument chosen by the user
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ArgumentPreset {
+    pub value: Value,
+    /// The argument stays visible with `value` as its default and the model may pass another
+    /// value. Otherwise it is hidden from the model and always sent with `value`.
+    #[serde(default)]
+    pub overridable: bool,
+}
+
+/// A credential of the vault, defined once and referenced from server env values as
+/// `${secret:name}`. Its value is never sent back.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Secret {
+    pub name: String,
+    #[serde(default)]
+    pub description: String,
+    /// IDs of the servers whose env references the secret
+    #[serde(default)]
+    pub servers: Vec<String>,
+}
+
+impl ToolOverrides {
+    pub fn is_empty(&self) -> bool {
+        self.name.is_none() && self.description.is_none() && self.argument_descriptions.is_empty()
+    }
+}
+
 impl ServerToolInfo {
     /// Create a new ServerToolInfo from a JSON value
     pub fn from_value(value: Value, server_id: String) -> Result<ServerToolInfo, String> {
@@ -499,26 +876,142 @@ impl ServerToolInfo {
             input_schema: Some(input_schema),
             proxy_id: None,
             is_active: true,
+            title: tool
+                .annotations
+                .as_ref()
+                .and_then(|annotations| annotations.title.clone()),
+            annotations: tool.annotations,
+            overrides: None,
+            argument_presets: HashMap::new(),
+            cache_ttl: None,
+            size: None,
         })
     }
 
-    pub fn to_tool(self) -> Result<Tool, String> {
-        let input_schema: JsonObject = serde_json::to_value(self.input_schema)
+    /// Arguments to send upstream: the call's arguments with the presets merged in
+    pub fn apply_argument_presets(
+        &self,
+        arguments: Option<Map<String, Value>>,
+    ) -> Option<Map<String, Value>> {
+        if self.argument_presets.is_empty() {
+            return arguments;
+        }
+        let mut arguments = arguments.unwrap_or_default();
+        for (argument, preset) in &self.argument_presets {
+            if preset.overridable {
+                arguments
+                    .entry(argument.clone())
+                    .or_insert_with(|| preset.value.clone());
+            } else {
+                arguments.insert(argument.clone(), preset.value.clone());
+            }
+        }
+        Some(arguments)
+    }
+
+    /// Arguments to send upstream: the presets merged in, then coerced and checked against the
+    /// input schema as configured
+    pub fn prepare_arguments(
+        &self,
+        arguments: Option<Map<String, Value>>,
+        config: &ArgumentValidationConfig,
+    ) -> Result<Option<Map<String, Value>>, Vec<ArgumentViolation>> {
+        let arguments = self.apply_argument_presets(arguments);
+        match self.input_schema_value() {
+            Some(schema) => argument_validation::prepare_arguments(&schema, arguments, config),
+            None => Ok(arguments),
+        }
+    }
+
+    /// Input schema as the server reported it
+    fn input_schema_value(&self) -> Option<Value> {
+        let mut schema = serde_json::to_value(self.input_schema.as_ref()?).ok()?;
+        // A schema reported without `type` is stored with an empty one
+        if schema.get("type").is_some_and(|r#type| r#type == "") {
+            schema.as_object_mut()?.remove("type");
+        }
+        Some(schema)
+    }
+
+    /// How long results of the tool are cached, `None` when caching is off for the tool.
+    ///
+    /// Tools annotated as non-idempotent are never cached, read-only tools count as idempotent.
+    pub fn result_cache_ttl(&self) -> Option<Duration> {
+        let non_idempotent = self.annotations.as_ref().is_some_and(|annotations| {
+            annotations.idempotent_hint == Some(false) && annotations.read_only_hint != Some(true)
+        });
+        match self.cache_ttl {
+            Some(ttl) if !non_idempotent => Some(Duration::from_secs(ttl)),
+            _ => None,
+        }
+    }
+
+    /// Whether `tool_id` designates this tool by id, upstream name or exposed name
+    pub fn matches_id(&self, tool_id: &str) -> bool {
+        self.id == tool_id || self.name == tool_id || self.exposed_name() == tool_id
+    }
+
+    /// Name clients see and call the tool by
+    pub fn exposed_name(&self) -> &str {
+        self.overrides
+            .as_ref()
+            .and_then(|overrides| overrides.name.as_deref())
+            .unwrap_or(&self.name)
+    }
+
+    /// Tool definition listed to clients, with its input schema normalized to `strictness`
+    pub fn to_tool(self, strictness: SchemaStrictness) -> Result<Tool, String> {
+        let overrides = self.overrides.clone().unwrap_or_default();
+        let mut input_schema = self.input_schema.clone();
+        if let Some(schema) = input_schema.as_mut() {
+            for (argument, description) in &overrides.argument_descriptions {
+                if let Some(property) = schema.properties.get_mut(argument) {
+                    property.description = description.clone();
+                }
+            }
+            // Pinned arguments are hidden, overridable ones become optional with a default
+            for (argument, preset) in &self.argument_presets {
+                schema.required.retain(|required| required != argument);
+                if preset.overridable {
+                    if let Some(property) = schema.properties.get_mut(argument) {
+                        property.default = Some(preset.value.clone());
+                    }
+                } else {
+                    schema.properties.remove(argument);
+                }
+            }
+        }
+
+        let input_schema: JsonObject = serde_json::to_value(input_schema)
             .map_err(|e| e.to_string())
             .map_err(|e| format!("failed to serialize input schema: {e}"))?
             .as_object()
             .unwrap_or(&serde_json::Map::new())
             .to_owned();
-        let binding = self.name.clone();
+        let input_schema = normalize_schema(&input_schema, strictness);
+        let binding = self.exposed_name().to_string();
         let name = Cow::Borrowed(&binding);
-        let binding = self.description.clone();
+        let binding = overrides.description.unwrap_or_else(|| self.description.clone());
         let description = Cow::Borrowed(&binding);
 
+        // The title travels in the annotations, keep it when the server only reported a title
+        let annotations = match (self.annotations, self.title) {
+            (Some(mut annotations), Some(title)) => {
+                annotations.title.get_or_insert(title);
+                Some(annotations)
+            }
+            (None, Some(title)) => Some(ToolAnnotations {
+                title: Some(title),
+                ..Default::default()
+            }),
+            (annotations, None) => annotations,
+        };
+
         Ok(Tool {
             name: name.into_owned().into(),
             description: Some(description.into_owned().into()),
             input_schema: Arc::new(input_schema),
-            annotations: None,
+            annotations,
         })
     }
 }
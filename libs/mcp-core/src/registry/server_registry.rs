@@ -1,7 +1,9 @@
 use std::collections::HashMap;
 
 use crate::{
-    database::db_manager::DBManager, models::types::ServerDefinition, types::ServerToolInfo,
+    database::db_manager::DBManager,
+    models::types::{ClientPolicy, CompositeTool, Profile, Secret, ServerDefinition},
+    types::ServerToolInfo,
 };
 
 /// ServerRegistry: database logic only
@@ -38,6 +40,11 @@ impl ServerRegistry {
         self.db_manager.save_server(tool_id, tool)
     }
 
+    /// Decrypt a secret environment value of a server
+    pub fn decrypt_secret(&self, value: &str) -> Result<String, String> {
+        self.db_manager.decrypt_secret(value)
+    }
+
     /// Delete a server
     pub fn delete_server(&self, tool_id: &str) -> Result<(), String> {
         self.db_manager.delete_server(tool_id)
@@ -76,4 +83,84 @@ impl ServerRegistry {
     pub fn save_setting(&self, key: &str, value: &str) -> Result<(), String> {
         self.db_manager.save_setting(key, value)
     }
+
+    /// Get a profile by name
+    pub fn get_profile(&self, name: &str) -> Result<Profile, String> {
+        self.db_manager.get_profile(name)
+    }
+
+    /// Get all profiles
+    pub fn get_profiles(&self) -> Result<Vec<Profile>, String> {
+        self.db_manager.get_profiles()
+    }
+
+    /// Save or update a profile
+    pub fn save_profile(&self, profile: &Profile) -> Result<(), String> {
+        self.db_manager.save_profile(profile)
+    }
+
+    /// Delete a profile
+    pub fn delete_profile(&self, name: &str) -> Result<(), String> {
+        self.db_manager.delete_profile(name)
+    }
+
+    /// Get the policy of a client, `None` when it has none
+    pub fn get_client_policy(&self, profile: &str) -> Result<Option<ClientPolicy>, String> {
+        self.db_manager.get_client_policy(profile)
+    }
+
+    /// Get all client policies
+    pub fn get_client_policies(&self) -> Result<Vec<ClientPolicy>, String> {
+        self.db_manager.get_client_policies()
+    }
+
+    /// Save or update the policy of a client
+    pub fn save_client_policy(&self, policy: &ClientPolicy) -> Result<(), String> {
+        self.db_manager.save_client_policy(policy)
+    }
+
+    /// Delete the policy of a client
+    pub fn delete_client_policy(&self, profile: &str) -> Result<(), String> {
+        self.db_manager.delete_client_policy(profile)
+    }
+
+    /// Get a composite tool
+    pub fn get_composite_tool(&self, name: &str) -> Result<CompositeTool, String> {
+        self.db_manager.get_composite_tool(name)
+    }
+
+    /// Get all composite tools
+    pub fn get_composite_tools(&self) -> Result<Vec<CompositeTool>, String> {
+        self.db_manager.get_composite_tools()
+    }
+
+    /// Save or update a composite tool
+    pub fn save_composite_tool(&self, tool: &CompositeTool) -> Result<(), String> {
+        self.db_manager.save_composite_tool(tool)
+    }
+
+    /// Delete a composite tool
+    pub fn delete_composite_tool(&self, name: &str) -> Result<(), String> {
+        self.db_manager.delete_composite_tool(name)
+    }
+
+    /// Get all vault secrets without their values
+    pub fn get_secrets(&self) -> Result<Vec<Secret>, String> {
+        self.db_manager.get_secrets()
+    }
+
+    /// Get the decrypted value of a vault secret
+    pub fn get_secret_value(&self, name: &str) -> Result<String, String> {
+        self.db_manager.get_secret_value(name)
+    }
+
+    /// Save or update a vault secret
+    pub fn save_secret(&self, name: &str, description: &str, value: &str) -> Result<(), String> {
+        self.db_manager.save_secret(name, description, value)
+    }
+
+    /// Delete a vault secret
+    pub fn delete_secret(&self, name: &str) -> Result<(), String> {
+        self.db_manager.delete_secret(name)
+    }
 }
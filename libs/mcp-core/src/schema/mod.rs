@@ -9,6 +9,11 @@ diesel::table! {
         server_id -> Text,
         proxy_id -> Nullable<Text>,
         is_active -> Bool,
+        title -> Nullable<Text>,
+        annotations -> Nullable<Text>,
+        overrides -> Nullable<Text>,
+        argument_presets -> Nullable<Text>,
+        cache_ttl -> Nullable<Integer>,
     }
 }
 
@@ -19,6 +24,7 @@ diesel::table! {
         env_value -> Text,
         env_description -> Text,
         env_required -> Bool,
+        env_secret -> Bool,
     }
 }
 
@@ -47,6 +53,54 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    profiles (name) {
+        name -> Text,
+        description -> Text,
+        servers -> Text,
+        tools -> Text,
+        token_budget -> Nullable<Integer>,
+        schema_strictness -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    client_policies (profile) {
+        profile -> Text,
+        allowed_servers -> Nullable<Text>,
+        max_calls_per_minute -> Nullable<Integer>,
+        require_approval -> Bool,
+        schema_strictness -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    composite_tools (name) {
+        name -> Text,
+        description -> Text,
+        input_schema -> Text,
+        steps -> Text,
+        output -> Nullable<Text>,
+    }
+}
+
+diesel::table! {
+    secrets (name) {
+        name -> Text,
+        description -> Text,
+        value -> Text,
+    }
+}
+
 diesel::joinable!(server_env -> servers (server_id));
 
-diesel::allow_tables_to_appear_in_same_query!(server_tools, server_env, servers, app_settings,);
+diesel::allow_tables_to_appear_in_same_query!(
+    server_tools,
+    server_env,
+    servers,
+    app_settings,
+    profiles,
+    client_policies,
+    composite_tools,
+    secrets,
+);
@@ -0,0 +1,149 @@
+use log::warn;
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Number, Value};
+
+use crate::config::ArgumentValidationConfig;
+
+/// An argument value that does not satisfy the tool's input schema
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ArgumentViolation {
+    /// JSON pointer of the value in the arguments, empty for the arguments themselves
+    pub path: String,
+    pub message: String,
+}
+
+/// One line per violation, for error messages
+pub fn format_violations(violations: &[ArgumentViolation]) -> String {
+    violations
+        .iter()
+        .map(|violation| match violation.path.as_str() {
+            "" => violation.message.clone(),
+            path => format!("{path}: {}", violation.message),
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Coerce arguments to an input schema and check them against it, as configured
+pub fn prepare_arguments(
+    schema: &Value,
+    mut arguments: Option<Map<String, Value>>,
+    config: &ArgumentValidationConfig,
+) -> Result<Option<Map<String, Value>>, Vec<ArgumentViolation>> {
+    if config.coerce {
+        if let Some(arguments) = arguments.as_mut() {
+            coerce_arguments(schema, arguments);
+        }
+    }
+    if config.enabled {
+        validate_arguments(schema, &arguments.clone().unwrap_or_default())?;
+    }
+    Ok(arguments)
+}
+
+/// Check arguments against an input schema and return every violation.
+///
+/// Schemas the validator cannot compile are not enforced, the server remains the judge.
+pub fn validate_arguments(
+    schema: &Value,
+    arguments: &Map<String, Value>,
+) -> Result<(), Vec<ArgumentViolation>> {
+    let validator = match jsonschema::validator_for(schema) {
+        Ok(validator) => validator,
+        Err(e) => {
+            warn!("Not validating arguments, the input schema is invalid: {e}");
+            return Ok(());
+        }
+    };
+    let instance = Value::Object(arguments.clone());
+    let violations: Vec<ArgumentViolation> = validator
+        .iter_errors(&instance)
+        .map(|error| ArgumentViolation {
+            path: error.instance_path.to_string(),
+            message: error.to_string(),
+        })
+        .collect();
+    if violations.is_empty() {
+        Ok(())
+    } else {
+        Err(violations)
+    }
+}
+
+/// Types a schema allows, from `type` as a name or a list of names
+fn schema_types(schema: &Map<String, Value>) -> Vec<&str> {
+    match schema.get("type") {
+        Some(Value::String(name)) => vec![name.as_str()],
+        Some(Value::Array(names)) => names.iter().filter_map(Value::as_str).collect(),
+        _ => Vec::new(),
+    }
+}
+
+/// The number or boolean a string stands for, when the schema wants one and no string
+fn coerce_string(types: &[&str], text: &str) -> Option<Value> {
+    if types.contains(&"string") {
+        return None;
+    }
+    let text = text.trim();
+    if types.contains(&"integer") || types.contains(&"number") {
+        if let Ok(integer) = text.parse::<i64>() {
+            return Some(Value::Number(integer.into()));
+        }
+    }
+    if types.contains(&"number") {
+        if let Some(number) = text.parse::<f64>().ok().and_then(Number::from_f64) {
+            return Some(Value::Number(number));
+        }
+    }
+    if types.contains(&"boolean") {
+        match text {
+            "true" => return Some(Value::Bool(true)),
+            "false" => return Some(Value::Bool(false)),
+            _ => {}
+        }
+    }
+    None
+}
+
+fn coerce_value(schema: &Value, value: &mut Value) {
+    let Some(schema) = schema.as_object() else {
+        return;
+    };
+    match value {
+        Value::String(text) => {
+            if let Some(coerced) = coerce_string(&schema_types(schema), text) {
+                *value = coerced;
+            }
+        }
+        Value::Object(object) => coerce_object(schema, object),
+        Value::Array(items) => {
+            if let Some(items_schema) = schema.get("items") {
+                for item in items {
+                    coerce_value(items_schema, item);
+                }
+            }
+        }
+        _ => {}
+    }
+}
+
+fn coerce_object(schema: &Map<String, Value>, object: &mut Map<String, Value>) {
+    let Some(Value::Object(properties)) = schema.get("properties") else {
+        return;
+    };
+    for (name, value) in object.iter_mut() {
+        if let Some(property) = properties.get(name) {
+            coerce_value(property, value);
+        }
+    }
+}
+
+/// Convert strings such as `"42"` or `"true"` to the number or boolean the schema declares.
+///
+/// Only strings that are valid numbers or booleans are converted, and only where the schema
+/// does not also allow a string. Nested objects and lists are converted too.
+pub fn coerce_arguments(schema: &Value, arguments: &mut Map<String, Value>) {
+    if let Some(schema) = schema.as_object() {
+        coerce_object(schema, arguments);
+    }
+}
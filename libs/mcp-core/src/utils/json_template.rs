@@ -0,0 +1,80 @@
+use serde_json::{Map, Value};
+use serde_json_path::JsonPath;
+
+/// Parse a JSONPath expression such as `$.steps.search.items[*].id`
+fn parse_path(path: &str) -> Result<JsonPath, String> {
+    JsonPath::parse(path).map_err(|e| format!("Invalid JSONPath '{path}': {e}"))
+}
+
+/// Values matched by a JSONPath expression, in document order
+pub fn select(path: &str, context: &Value) -> Result<Vec<Value>, String> {
+    Ok(parse_path(path)?
+        .query(context)
+        .all()
+        .into_iter()
+        .cloned()
+        .collect())
+}
+
+/// Elements a `for_each` expression iterates over.
+///
+/// A single matched list is iterated element by element, otherwise every match is one element.
+pub fn select_items(path: &str, context: &Value) -> Result<Vec<Value>, String> {
+    let mut matches = select(path, context)?;
+    match matches.as_mut_slice() {
+        [Value::Array(items)] => Ok(std::mem::take(items)),
+        _ => Ok(matches),
+    }
+}
+
+/// Path expression of a template string, `None` for literal strings
+fn template_path(value: &str) -> Option<&str> {
+    (value.starts_with('$') && !value.starts_with("$$")).then_some(value)
+}
+
+/// Replace the JSONPath strings of a template with the values they select in `context`.
+///
+/// Strings starting with `$` are expressions: no match gives `null`, one match gives the value
+/// and several matches give a list. A leading `$$` stands for a literal `$`. Objects and lists
+/// are rendered recursively, other values are kept.
+pub fn render_template(template: &Value, context: &Value) -> Result<Value, String> {
+    match template {
+        Value::String(value) => match template_path(value) {
+            Some(path) => {
+                let mut matches = select(path, context)?;
+                Ok(match matches.len() {
+                    0 => Value::Null,
+                    1 => matches.remove(0),
+                    _ => Value::Array(matches),
+                })
+            }
+            None => Ok(Value::String(
+                value.strip_prefix('$').unwrap_or(value).to_string(),
+            )),
+        },
+        Value::Array(values) => values
+            .iter()
+            .map(|value| render_template(value, context))
+            .collect::<Result<Vec<_>, _>>()
+            .map(Value::Array),
+        Value::Object(values) => values
+            .iter()
+            .map(|(key, value)| Ok((key.clone(), render_template(value, context)?)))
+            .collect::<Result<Map<_, _>, String>>()
+            .map(Value::Object),
+        value => Ok(value.clone()),
+    }
+}
+
+/// Check that every JSONPath string of a template parses, without evaluating it
+pub fn validate_template(template: &Value) -> Result<(), String> {
+    match template {
+        Value::String(value) => match template_path(value) {
+            Some(path) => parse_path(path).map(|_| ()),
+            None => Ok(()),
+        },
+        Value::Array(values) => values.iter().try_for_each(validate_template),
+        Value::Object(values) => values.values().try_for_each(validate_template),
+        _ => Ok(()),
+    }
+}
@@ -1,11 +1,45 @@
-use std::{fs, path::PathBuf};
+use std::{
+    fs,
+    path::PathBuf,
+    time::{SystemTime, UNIX_EPOCH},
+};
 
 use directories::ProjectDirs;
 use log::info;
 
+pub mod argument_validation;
 pub mod command;
 pub mod github;
+pub mod json_template;
 pub mod process;
+pub mod redaction;
+pub mod schema_normalization;
+pub mod secrets;
+
+/// Seconds since the Unix epoch
+pub fn unix_timestamp() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
+}
+
+/// Names of tools, profiles and secrets appear in tool lists, URLs and references, so they are
+/// 1 to 64 letters, digits, `_` or `-`. `noun` names what is checked in the error message.
+pub fn validate_name(noun: &str, name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && name.len() <= 64
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid {noun} name '{name}', use 1 to 64 letters, digits, '_' or '-'"
+        ))
+    }
+}
 
 /// Get the default storage path
 pub fn default_storage_path() -> Result<PathBuf, String> {
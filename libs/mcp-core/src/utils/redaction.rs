@@ -0,0 +1,93 @@
+use std::collections::HashMap;
+
+use serde_json::Value;
+
+use crate::models::types::{ServerDefinition, ServerEnvironment};
+use crate::utils::secrets::{is_secret_key, secret_references};
+
+/// Mask replacing secret values in logs and responses.
+///
+/// Sent back as the value of an environment variable, it keeps the stored value.
+pub const REDACTED: &str = "********";
+
+/// Whether the value of an environment variable is masked: the variable is flagged secret or
+/// named like a secret, such as `OPENAI_API_KEY`.
+///
+/// Empty values and values referencing the vault as `${secret:name}` hold nothing secret and
+/// stay visible.
+pub fn is_redacted(key: &str, secret: bool, value: &str) -> bool {
+    (secret || is_secret_key(key)) && !value.is_empty() && secret_references(value).is_empty()
+}
+
+/// The value of an environment variable as it may be logged or returned
+pub fn redact_env_value<'a>(key: &str, secret: bool, value: &'a str) -> &'a str {
+    if is_redacted(key, secret, value) {
+        REDACTED
+    } else {
+        value
+    }
+}
+
+/// Mask the secret defaults of environment variables
+pub fn redact_env(env: &mut HashMap<String, ServerEnvironment>) {
+    for (key, env) in env.iter_mut() {
+        if let Some(value) = &mut env.default {
+            if is_redacted(key, env.secret, value) {
+                *value = REDACTED.to_string();
+            }
+        }
+    }
+}
+
+/// Mask the secret environment values of a server definition
+pub fn redact_server(definition: &mut ServerDefinition) {
+    if let Some(env) = definition
+        .configuration
+        .as_mut()
+        .and_then(|configuration| configuration.env.as_mut())
+    {
+        redact_env(env);
+    }
+}
+
+/// Values shorter than this are not masked in free text, it would garble the text while
+/// hiding next to nothing
+const MIN_MASKED_VALUE_CHARS: usize = 4;
+
+/// Mask every occurrence of the given secret values in free text, such as a log line
+pub fn redact_values(text: &str, values: &[String]) -> String {
+    let mut values: Vec<&String> = values
+        .iter()
+        .filter(|value| value.chars().count() >= MIN_MASKED_VALUE_CHARS)
+        .collect();
+    // A secret containing another one is masked whole
+    values.sort_by_key(|value| std::cmp::Reverse(value.len()));
+    values.into_iter().fold(text.to_string(), |text, value| {
+        text.replace(value.as_str(), REDACTED)
+    })
+}
+
+/// Mask the secret values in every string of a JSON value, such as the data of a log message
+pub fn redact_json_values(value: Value, values: &[String]) -> Value {
+    match value {
+        Value::String(text) => Value::String(redact_values(&text, values)),
+        Value::Array(items) => Value::Array(
+            items
+                .into_iter()
+                .map(|item| redact_json_values(item, values))
+                .collect(),
+        ),
+        Value::Object(fields) => Value::Object(
+            fields
+                .into_iter()
+                .map(|(key, field)| {
+                    (
+                        redact_values(&key, values),
+                        redact_json_values(field, values),
+                    )
+                })
+                .collect(),
+        ),
+        value => value,
+    }
+}
@@ -0,0 +1,268 @@
+use serde_json::{Map, Value};
+
+use crate::models::types::SchemaStrictness;
+
+/// Nesting of subschemas kept at most, deeper subschemas are reduced to their type
+pub const MAX_SCHEMA_DEPTH: usize = 10;
+
+/// Keywords that only matter to the `$ref`s they serve or to schema tooling, removed once the
+/// references are inlined
+const META_KEYWORDS: [&str; 6] = [
+    "$schema",
+    "$id",
+    "$comment",
+    "$anchor",
+    "$defs",
+    "definitions",
+];
+
+/// Keywords a strict schema keeps, the subset of JSON Schema every client accepts
+const STRICT_KEYWORDS: [&str; 15] = [
+    "type",
+    "description",
+    "title",
+    "properties",
+    "required",
+    "items",
+    "enum",
+    "default",
+    "minimum",
+    "maximum",
+    "minLength",
+    "maxLength",
+    "pattern",
+    "minItems",
+    "maxItems",
+];
+
+/// Keywords whose value is a map of names to schemas
+const SCHEMA_MAP_KEYWORDS: [&str; 2] = ["properties", "patternProperties"];
+
+/// Keywords whose value is a subschema of a nested value
+const SUBSCHEMA_KEYWORDS: [&str; 8] = [
+    "items",
+    "additionalProperties",
+    "additionalItems",
+    "unevaluatedProperties",
+    "unevaluatedItems",
+    "contains",
+    "propertyNames",
+    "not",
+];
+
+struct Normalizer<'a> {
+    root: &'a Value,
+    strict: bool,
+    /// `$ref`s being inlined, a `$ref` to one of them is recursive
+    expanding: Vec<String>,
+}
+
+/// Merge a schema describing the same value into `target`, `target`'s own keywords win.
+///
+/// Properties of both are merged and the required properties of both are required.
+fn merge_schema(target: &mut Map<String, Value>, schema: Value) {
+    let Value::Object(schema) = schema else {
+        return;
+    };
+    for (key, value) in schema {
+        match (key.as_str(), target.get_mut(&key), value) {
+            ("properties", Some(Value::Object(properties)), Value::Object(more)) => {
+                for (name, property) in more {
+                    match properties.get_mut(&name) {
+                        Some(Value::Object(existing)) => merge_schema(existing, property),
+                        Some(_) => {}
+                        None => {
+                            properties.insert(name, property);
+                        }
+                    }
+                }
+            }
+            ("required", Some(Value::Array(required)), Value::Array(more)) => {
+                for name in more {
+                    if !required.contains(&name) {
+                        required.push(name);
+                    }
+                }
+            }
+            (_, Some(_), _) => {}
+            (_, None, value) => {
+                target.insert(key, value);
+            }
+        }
+    }
+}
+
+/// First type of a list of types other than `null`, for clients that take a single type
+fn single_type(r#type: &Value) -> Value {
+    match r#type {
+        Value::Array(types) => types
+            .iter()
+            .find(|r#type| *r#type != "null")
+            .or(types.first())
+            .cloned()
+            .unwrap_or(Value::Null),
+        r#type => r#type.clone(),
+    }
+}
+
+impl Normalizer<'_> {
+    /// The type and description of a schema, in place of its subschemas
+    fn truncated(&self, schema: &Value) -> Value {
+        let mut truncated = Map::new();
+        if let Some(r#type) = schema.get("type") {
+            let r#type = match self.strict {
+                true => single_type(r#type),
+                false => r#type.clone(),
+            };
+            truncated.insert("type".to_string(), r#type);
+        }
+        if let Some(description) = schema.get("description") {
+            truncated.insert("description".to_string(), description.clone());
+        }
+        Value::Object(truncated)
+    }
+
+    /// The normalized schema a local `$ref` points to, `None` for references it cannot resolve
+    fn inline_ref(&mut self, reference: &str, depth: usize) -> Option<Value> {
+        let pointer = reference.strip_prefix('#')?;
+        let target = self.root.pointer(pointer)?;
+        if self
+            .expanding
+            .iter()
+            .any(|expanding| expanding == reference)
+        {
+            return Some(self.truncated(target));
+        }
+        self.expanding.push(reference.to_string());
+        let inlined = self.schema(target, depth);
+        self.expanding.pop();
+        Some(inlined)
+    }
+
+    /// Normalized value of a keyword of a schema at `depth`
+    fn keyword(&mut self, key: &str, value: &Value, depth: usize) -> Value {
+        match value {
+            Value::Object(schemas) if SCHEMA_MAP_KEYWORDS.contains(&key) => Value::Object(
+                schemas
+                    .iter()
+                    .map(|(name, schema)| (name.clone(), self.schema(schema, depth + 1)))
+                    .collect(),
+            ),
+            Value::Array(schemas) if key == "items" || key == "prefixItems" => Value::Array(
+                schemas
+                    .iter()
+                    .map(|schema| self.schema(schema, depth + 1))
+                    .collect(),
+            ),
+            // Alternatives describe the same value as the schema they belong to
+            Value::Array(schemas) if key == "anyOf" || key == "oneOf" => Value::Array(
+                schemas
+                    .iter()
+                    .map(|schema| self.schema(schema, depth))
+                    .collect(),
+            ),
+            value if SUBSCHEMA_KEYWORDS.contains(&key) => self.schema(value, depth + 1),
+            value if self.strict && key == "type" => single_type(value),
+            value => value.clone(),
+        }
+    }
+
+    /// A strict schema takes the first alternative that is not `null` in place of its
+    /// `anyOf` or `oneOf`, which only narrows what the server accepts
+    fn collapse_alternatives(&self, schema: &mut Map<String, Value>) {
+        for key in ["anyOf", "oneOf"] {
+            let Some(Value::Array(alternatives)) = schema.remove(key) else {
+                continue;
+            };
+            let alternative = alternatives.into_iter().find(|alternative| {
+                alternative
+                    .get("type")
+                    .is_none_or(|r#type| r#type != "null")
+            });
+            if let Some(alternative) = alternative {
+                merge_schema(schema, alternative);
+            }
+        }
+    }
+
+    fn schema(&mut self, schema: &Value, depth: usize) -> Value {
+        let Value::Object(keywords) = schema else {
+            return schema.clone();
+        };
+        if depth > MAX_SCHEMA_DEPTH {
+            return self.truncated(schema);
+        }
+
+        let mut normalized = Map::new();
+        // Schemas describing the same value, merged in once the own keywords are normalized
+        let mut merged = Vec::new();
+        for (key, value) in keywords {
+            match (key.as_str(), value) {
+                ("$ref", Value::String(reference)) => match self.inline_ref(reference, depth) {
+                    Some(inlined) => merged.push(inlined),
+                    // References outside the schema are left to the clients that follow them
+                    None if !self.strict => {
+                        normalized.insert(key.clone(), value.clone());
+                    }
+                    None => {}
+                },
+                ("allOf", Value::Array(schemas)) => {
+                    merged.extend(schemas.iter().map(|schema| self.schema(schema, depth)));
+                }
+                (key, _) if META_KEYWORDS.contains(&key) => {}
+                (key, value) => {
+                    let value = self.keyword(key, value, depth);
+                    normalized.insert(key.to_string(), value);
+                }
+            }
+        }
+        for schema in merged {
+            merge_schema(&mut normalized, schema);
+        }
+
+        if self.strict {
+            self.collapse_alternatives(&mut normalized);
+            if let Some(constant) = normalized.remove("const") {
+                normalized
+                    .entry("enum")
+                    .or_insert_with(|| Value::Array(vec![constant]));
+            }
+            normalized.retain(|key, _| STRICT_KEYWORDS.contains(&key.as_str()));
+        }
+        Value::Object(normalized)
+    }
+}
+
+/// Normalize a tool input schema for clients that reject parts of JSON Schema.
+///
+/// [`SchemaStrictness::Standard`] inlines local `$ref`s, flattens `allOf` into the schema that
+/// holds it, removes the meta keywords such as `$schema` and `$defs` and reduces the subschemas
+/// nested deeper than [`MAX_SCHEMA_DEPTH`] to their type. Recursive references are reduced the
+/// same way. [`SchemaStrictness::Strict`] also replaces `anyOf` and `oneOf` with their first
+/// alternative, lists of types with their first type and keeps only the keywords every client
+/// accepts. The input schema remains an object schema.
+pub fn normalize_schema(
+    schema: &Map<String, Value>,
+    strictness: SchemaStrictness,
+) -> Map<String, Value> {
+    if strictness == SchemaStrictness::Off {
+        return schema.clone();
+    }
+    let root = Value::Object(schema.clone());
+    let mut normalizer = Normalizer {
+        root: &root,
+        strict: strictness == SchemaStrictness::Strict,
+        expanding: Vec::new(),
+    };
+    let mut normalized = match normalizer.schema(&root, 0) {
+        Value::Object(normalized) => normalized,
+        _ => Map::new(),
+    };
+    if normalized
+        .get("type")
+        .is_none_or(|r#type| r#type != "object")
+    {
+        normalized.insert("type".to_string(), Value::String("object".to_string()));
+    }
+    normalized
+}
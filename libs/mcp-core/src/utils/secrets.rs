@@ -0,0 +1,186 @@
+use std::{fs, io::Write, path::Path};
+
+use aes_gcm::{
+    aead::{Aead, AeadCore, KeyInit, OsRng},
+    Aes256Gcm, Key, Nonce,
+};
+use base64::{engine::general_purpose::STANDARD as BASE64, Engine};
+use log::info;
+
+use crate::config::SecretKeySource;
+
+/// Prefix of encrypted values, followed by the base64 of the nonce and the ciphertext
+pub const ENCRYPTED_PREFIX: &str = "enc:v1:";
+
+/// Start of a reference to a vault secret in an environment value, `${secret:name}`
+pub const SECRET_REFERENCE_PREFIX: &str = "${secret:";
+
+/// Name of the key file created next to the database
+pub const KEY_FILE_NAME: &str = "secret.key";
+
+const KEYRING_SERVICE: &str = "mcp-dockmaster";
+const KEYRING_USER: &str = "secret-key";
+
+/// AES-GCM nonces are 96 bits
+const NONCE_BYTES: usize = 12;
+
+/// Suffixes of the environment variable names that hold secrets
+const SECRET_KEY_SUFFIXES: [&str; 5] = ["_KEY", "_TOKEN", "_SECRET", "_PASSWORD", "_CREDENTIALS"];
+
+/// Whether an environment variable name looks like it holds a secret, such as `GITHUB_TOKEN`
+pub fn is_secret_key(key: &str) -> bool {
+    let key = key.to_uppercase();
+    SECRET_KEY_SUFFIXES
+        .iter()
+        .any(|suffix| key.ends_with(suffix) || key == suffix[1..])
+}
+
+/// Whether a stored value is encrypted
+pub fn is_encrypted(value: &str) -> bool {
+    value.starts_with(ENCRYPTED_PREFIX)
+}
+
+/// Names of the vault secrets a value references as `${secret:name}`
+pub fn secret_references(value: &str) -> Vec<&str> {
+    let mut names = Vec::new();
+    let mut rest = value;
+    while let Some(start) = rest.find(SECRET_REFERENCE_PREFIX) {
+        rest = &rest[start + SECRET_REFERENCE_PREFIX.len()..];
+        let Some(end) = rest.find('}') else {
+            break;
+        };
+        names.push(&rest[..end]);
+        rest = &rest[end + 1..];
+    }
+    names
+}
+
+/// Whether a secret value still has to be encrypted before it is stored.
+///
+/// Values referencing the vault are kept readable, the secrets they reference are stored
+/// encrypted in the vault.
+pub fn needs_encryption(value: &str) -> bool {
+    !value.is_empty() && !is_encrypted(value) && secret_references(value).is_empty()
+}
+
+/// Encrypts and decrypts secret values with a key held outside the database
+#[derive(Clone)]
+pub struct SecretCipher {
+    cipher: Aes256Gcm,
+}
+
+impl SecretCipher {
+    /// Cipher of a base64 encoded 256-bit key
+    pub fn from_encoded_key(encoded: &str) -> Result<Self, String> {
+        let key = BASE64
+            .decode(encoded.trim())
+            .map_err(|e| format!("Invalid secret key: {e}"))?;
+        if key.len() != 32 {
+            return Err(format!(
+                "Invalid secret key: expected 32 bytes, got {}",
+                key.len()
+            ));
+        }
+        Ok(Self {
+            cipher: Aes256Gcm::new(Key::<Aes256Gcm>::from_slice(&key)),
+        })
+    }
+
+    /// Cipher of the key held by `source`, a random key is generated and stored on first use.
+    ///
+    /// `data_dir` holds the key file when the source does not name one.
+    pub fn load(source: &SecretKeySource, data_dir: &Path) -> Result<Self, String> {
+        match source {
+            SecretKeySource::File(path) => {
+                let path = path.clone().unwrap_or_else(|| data_dir.join(KEY_FILE_NAME));
+                Self::load_key_file(&path)
+            }
+            SecretKeySource::Keyring => Self::load_keyring(),
+        }
+    }
+
+    fn generate_encoded_key() -> String {
+        BASE64.encode(Aes256Gcm::generate_key(OsRng))
+    }
+
+    fn load_key_file(path: &Path) -> Result<Self, String> {
+        if path.exists() {
+            let encoded = fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read secret key file {}: {e}", path.display()))?;
+            return Self::from_encoded_key(&encoded);
+        }
+
+        info!("Creating secret key file {}", path.display());
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)
+                .map_err(|e| format!("Failed to create secret key directory: {e}"))?;
+        }
+        let encoded = Self::generate_encoded_key();
+        let mut options = fs::OpenOptions::new();
+        options.write(true).create_new(true);
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::OpenOptionsExt;
+            options.mode(0o600);
+        }
+        let mut file = options
+            .open(path)
+            .map_err(|e| format!("Failed to create secret key file {}: {e}", path.display()))?;
+        file.write_all(encoded.as_bytes())
+            .map_err(|e| format!("Failed to write secret key file {}: {e}", path.display()))?;
+        Self::from_encoded_key(&encoded)
+    }
+
+    fn load_keyring() -> Result<Self, String> {
+        let entry = keyring::Entry::new(KEYRING_SERVICE, KEYRING_USER)
+            .map_err(|e| format!("Failed to open the OS keyring: {e}"))?;
+        match entry.get_password() {
+            Ok(encoded) => Self::from_encoded_key(&encoded),
+            Err(keyring::Error::NoEntry) => {
+                info!("Storing a new secret key in the OS keyring");
+                let encoded = Self::generate_encoded_key();
+                entry.set_password(&encoded).map_err(|e| {
+                    format!("Failed to store the secret key in the OS keyring: {e}")
+                })?;
+                Self::from_encoded_key(&encoded)
+            }
+            Err(e) => Err(format!(
+                "Failed to read the secret key from the OS keyring: {e}"
+            )),
+        }
+    }
+
+    /// Encrypt a value, values that are already encrypted are returned as they are
+    pub fn encrypt(&self, value: &str) -> Result<String, String> {
+        if is_encrypted(value) {
+            return Ok(value.to_string());
+        }
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let ciphertext = self
+            .cipher
+            .encrypt(&nonce, value.as_bytes())
+            .map_err(|e| format!("Failed to encrypt secret: {e}"))?;
+        let mut payload = nonce.to_vec();
+        payload.extend(ciphertext);
+        Ok(format!("{ENCRYPTED_PREFIX}{}", BASE64.encode(payload)))
+    }
+
+    /// Decrypt a value, values that are not encrypted are returned as they are
+    pub fn decrypt(&self, value: &str) -> Result<String, String> {
+        let Some(encoded) = value.strip_prefix(ENCRYPTED_PREFIX) else {
+            return Ok(value.to_string());
+        };
+        let payload = BASE64
+            .decode(encoded)
+            .map_err(|e| format!("Invalid encrypted secret: {e}"))?;
+        if payload.len() < NONCE_BYTES {
+            return Err("Invalid encrypted secret: too short".to_string());
+        }
+        let (nonce, ciphertext) = payload.split_at(NONCE_BYTES);
+        let plaintext = self
+            .cipher
+            .decrypt(Nonce::from_slice(nonce), ciphertext)
+            .map_err(|_| "Failed to decrypt secret, the key does not match".to_string())?;
+        String::from_utf8(plaintext).map_err(|e| format!("Invalid decrypted secret: {e}"))
+    }
+}
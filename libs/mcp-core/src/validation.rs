@@ -317,7 +317,19 @@ fn validate_configuration_consistency(
 }
 
 /// Resolve template variables in a string (e.g., $HOME, $USER)
+///
+/// References to vault secrets, `${secret:name}`, are left as they are, they are only resolved
+/// when the server is spawned, see [`resolve_template_variables_with_secrets`].
 pub fn resolve_template_variables(input: &str) -> Result<String> {
+    resolve_template_variables_with_secrets(input, |name| Ok(format!("${{secret:{name}}}")))
+}
+
+/// Resolve template variables in a string, `${secret:name}` resolves to the value `secret_value`
+/// returns for the secret `name`
+pub fn resolve_template_variables_with_secrets(
+    input: &str,
+    secret_value: impl Fn(&str) -> std::result::Result<String, String>,
+) -> Result<String> {
     let mut result = input.to_string();
 
     // Common template variables
@@ -333,22 +345,29 @@ pub fn resolve_template_variables(input: &str) -> Result<String> {
         }
     }
 
-    // Handle ${VAR} syntax
-    while let Some(start) = result.find("${") {
-        if let Some(end) = result[start..].find('}') {
-            let var_name = &result[start + 2..start + end];
-            if let Ok(var_value) = std::env::var(var_name) {
-                let full_template = format!("${{{}}}", var_name);
-                result = result.replace(&full_template, &var_value);
-            } else {
-                return Err(anyhow!("Environment variable '{}' not found", var_name));
-            }
-        } else {
+    // Handle ${VAR} syntax, substituted values are not resolved again
+    let mut resolved = String::with_capacity(result.len());
+    let mut rest = result.as_str();
+    while let Some(start) = rest.find("${") {
+        let Some(end) = rest[start..].find('}') else {
             break;
+        };
+        resolved.push_str(&rest[..start]);
+        let var_name = &rest[start + 2..start + end];
+        if let Some(secret_name) = var_name.strip_prefix("secret:") {
+            let value = secret_value(secret_name)
+                .map_err(|e| anyhow!("Secret '{}' could not be resolved: {}", secret_name, e))?;
+            resolved.push_str(&value);
+        } else if let Ok(var_value) = std::env::var(var_name) {
+            resolved.push_str(&var_value);
+        } else {
+            return Err(anyhow!("Environment variable '{}' not found", var_name));
         }
+        rest = &rest[start + end + 1..];
     }
+    resolved.push_str(rest);
 
-    Ok(result)
+    Ok(resolved)
 }
 
 /// Convert relative paths to absolute paths
@@ -7,7 +7,7 @@ mod tests {
     use mcp_core::{
         core::{mcp_core::MCPCore, mcp_core_proxy_ext::McpCoreProxyExt},
         init_logging,
-        models::types::ToolExecutionRequest,
+        models::types::{ToolExecutionRequest, ToolTarget},
         types::{ServerConfiguration, ServerRegistrationRequest, ServerUpdateRequest},
     };
     use serde_json::Map;
@@ -88,8 +88,12 @@ mod tests {
 
         // Execute tool
         let request = ToolExecutionRequest {
-            tool_id: format!("{}:{}", tool_id, "hello_world"),
+            target: ToolTarget {
+                server_id: tool_id.clone(),
+                tool_name: "hello_world".to_string(),
+            },
             parameters: Some(Map::new()),
+            progress_token: None,
         };
 
         let result = mcp_core.execute_proxy_tool(request).await?;
@@ -130,8 +134,12 @@ mod tests {
         let mut parameters = Map::new();
         parameters.insert("message".to_string(), json!("custom message"));
         let request = ToolExecutionRequest {
-            tool_id: format!("{}:{}", tool_id, "hello_world_with_input"),
+            target: ToolTarget {
+                server_id: tool_id.clone(),
+                tool_name: "hello_world_with_input".to_string(),
+            },
             parameters: Some(parameters),
+            progress_token: None,
         };
 
         let result = mcp_core.execute_proxy_tool(request).await?;
@@ -172,8 +180,12 @@ mod tests {
         let mut parameters = Map::new();
         parameters.insert("config".to_string(), json!("test-config"));
         let request = ToolExecutionRequest {
-            tool_id: format!("{}:{}", tool_id, "hello_world_with_config"),
+            target: ToolTarget {
+                server_id: tool_id.clone(),
+                tool_name: "hello_world_with_config".to_string(),
+            },
             parameters: Some(parameters),
+            progress_token: None,
         };
 
         let result = mcp_core.execute_proxy_tool(request).await?;
@@ -483,8 +495,12 @@ mod tests {
         // Verify server is running by executing a command
         let parameters = Map::new();
         let request = ToolExecutionRequest {
-            tool_id: format!("{}:{}", server_id, "hello_world"),
+            target: ToolTarget {
+                server_id: server_id.clone(),
+                tool_name: "hello_world".to_string(),
+            },
             parameters: Some(parameters),
+            progress_token: None,
         };
         let result = mcp_core.execute_proxy_tool(request).await?;
         assert!(result.success, "Initial server execution failed");
@@ -505,8 +521,12 @@ mod tests {
         loop {
             let parameters = Map::new();
             let request = ToolExecutionRequest {
-                tool_id: format!("{}:{}", server_id, "hello_world"),
+                target: ToolTarget {
+                    server_id: server_id.clone(),
+                    tool_name: "hello_world".to_string(),
+                },
                 parameters: Some(parameters),
+                progress_token: None,
             };
 
             match mcp_core.execute_proxy_tool(request).await {
@@ -532,8 +552,12 @@ mod tests {
         // Verify server is stopped by attempting to execute a command (one final check)
         let parameters = Map::new();
         let request = ToolExecutionRequest {
-            tool_id: format!("{}:{}", server_id, "hello_world"),
+            target: ToolTarget {
+                server_id: server_id.clone(),
+                tool_name: "hello_world".to_string(),
+            },
             parameters: Some(parameters),
+            progress_token: None,
         };
         let result = mcp_core.execute_proxy_tool(request).await;
         assert!(
@@ -552,8 +576,12 @@ mod tests {
         // Verify server is running again
         let parameters = Map::new();
         let request = ToolExecutionRequest {
-            tool_id: format!("{}:{}", server_id, "hello_world"),
+            target: ToolTarget {
+                server_id: server_id.clone(),
+                tool_name: "hello_world".to_string(),
+            },
             parameters: Some(parameters),
+            progress_token: None,
         };
         let result = mcp_core.execute_proxy_tool(request).await?;
         assert!(result.success, "Server did not restart successfully");
@@ -3,10 +3,30 @@ mod integration {
 }
 
 mod unit {
+    mod argument_validation_tests;
+    mod builtin_tools_tests;
+    mod client_policy_tests;
+    mod common;
+    mod composite_tool_tests;
     mod db_manager_tests;
     mod github_tests;
+    mod http_auth_tests;
     mod mcp_core_runtimes_ext;
+    mod pagination_tests;
+    mod profile_tests;
+    mod redaction_tests;
+    mod result_cache_tests;
+    mod result_store_tests;
+    mod schema_normalization_tests;
+    mod secret_storage_tests;
+    mod secret_vault_tests;
+    mod server_logs_tests;
     mod server_tool_info_db_tests;
     mod server_tool_info_deserialization_tests;
     mod template_resolution_tests;
+    mod tool_activation_tests;
+    mod tool_argument_presets_tests;
+    mod tool_budget_tests;
+    mod tool_override_tests;
+    mod tool_search_tests;
 }
@@ -0,0 +1,115 @@
+#[cfg(test)]
+mod tests {
+    use mcp_core::{
+        config::ArgumentValidationConfig,
+        models::types::ServerToolInfo,
+        utils::argument_validation::{coerce_arguments, format_violations, validate_arguments},
+    };
+    use serde_json::{json, Map, Value};
+
+    fn schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "query": {"type": "string"},
+                "limit": {"type": "integer", "minimum": 1},
+                "ratio": {"type": "number"},
+                "open": {"type": "boolean"},
+                "id": {"type": ["string", "integer"]},
+                "filter": {
+                    "type": "object",
+                    "properties": {"min_stars": {"type": "integer"}}
+                },
+                "ids": {"type": "array", "items": {"type": "integer"}}
+            },
+            "required": ["query"]
+        })
+    }
+
+    fn arguments(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    fn search_tool() -> ServerToolInfo {
+        ServerToolInfo::from_value(
+            json!({"name": "search", "description": "Search", "inputSchema": schema()}),
+            "github".to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_validate_arguments_lists_every_violation() {
+        assert!(validate_arguments(&schema(), &arguments(json!({"query": "rust"}))).is_ok());
+
+        let violations =
+            validate_arguments(&schema(), &arguments(json!({"limit": 0, "open": "yes"})))
+                .unwrap_err();
+        let mut paths: Vec<&str> = violations.iter().map(|v| v.path.as_str()).collect();
+        paths.sort_unstable();
+        assert_eq!(paths, vec!["", "/limit", "/open"]);
+        let message = format_violations(&violations);
+        assert!(message.contains("\"query\" is a required property"));
+        assert!(message.contains("/limit: "));
+
+        // Schemas the validator cannot compile are not enforced
+        let invalid = json!({"type": "unknown"});
+        assert!(validate_arguments(&invalid, &arguments(json!({"a": 1}))).is_ok());
+    }
+
+    #[test]
+    fn test_coerce_arguments() {
+        let mut coerced = arguments(json!({
+            "query": "42",
+            "limit": " 10 ",
+            "ratio": "0.5",
+            "open": "true",
+            "id": "7",
+            "filter": {"min_stars": "100"},
+            "ids": ["1", "2", "x"],
+            "unknown": "3"
+        }));
+        coerce_arguments(&schema(), &mut coerced);
+
+        assert_eq!(
+            Value::Object(coerced),
+            json!({
+                "query": "42",
+                "limit": 10,
+                "ratio": 0.5,
+                "open": true,
+                "id": "7",
+                "filter": {"min_stars": 100},
+                "ids": [1, 2, "x"],
+                "unknown": "3"
+            })
+        );
+    }
+
+    #[test]
+    fn test_prepare_arguments() {
+        let tool = search_tool();
+        let strict = ArgumentValidationConfig::default();
+        let lenient = ArgumentValidationConfig {
+            enabled: true,
+            coerce: true,
+        };
+        let loose = Some(arguments(json!({"query": "rust", "limit": "5"})));
+
+        assert!(tool.prepare_arguments(loose.clone(), &strict).is_err());
+        assert_eq!(
+            tool.prepare_arguments(loose.clone(), &lenient).unwrap(),
+            Some(arguments(json!({"query": "rust", "limit": 5})))
+        );
+
+        let disabled = ArgumentValidationConfig {
+            enabled: false,
+            coerce: false,
+        };
+        assert_eq!(
+            tool.prepare_arguments(loose.clone(), &disabled).unwrap(),
+            loose
+        );
+        assert!(tool.prepare_arguments(None, &strict).is_err());
+    }
+}
@@ -0,0 +1,72 @@
+#[cfg(test)]
+mod tests {
+    use mcp_core::mcp_server_implementation::tools::{get_builtin_tools, get_tool_names};
+    use serial_test::serial;
+
+    #[test]
+    #[serial]
+    fn test_builtin_tools_are_namespaced() {
+        let tool_names = get_tool_names();
+        let tools = get_builtin_tools();
+
+        assert_eq!(tools.len(), 12);
+        assert!(tools.iter().all(|tool| tool_names.contains(&tool.name)));
+        for name in [
+            &tool_names.server_status,
+            &tool_names.restart_server,
+            &tool_names.enable_server,
+            &tool_names.disable_server,
+            &tool_names.server_logs,
+            &tool_names.list_server_tools,
+        ] {
+            assert!(name.starts_with("dockmaster_"));
+            assert!(tools.iter().any(|tool| tool.name == *name));
+        }
+    }
+
+    #[test]
+    #[serial]
+    fn test_disabled_builtin_tools_are_not_offered() {
+        std::env::set_var("DOCKMASTER_DISABLED_TOOLS", "restart_server,server_logs");
+        let tool_names = get_tool_names();
+        let tools = get_builtin_tools();
+        std::env::remove_var("DOCKMASTER_DISABLED_TOOLS");
+
+        assert_eq!(tools.len(), 10);
+        assert!(tool_names.is_disabled(&tool_names.restart_server));
+        assert!(tool_names.is_disabled(&tool_names.server_logs));
+        assert!(!tool_names.is_disabled(&tool_names.server_status));
+        assert!(!tools.iter().any(|tool| tool.name == tool_names.restart_server));
+        // Disabled tools keep their names reserved
+        assert!(tool_names.contains(&tool_names.restart_server));
+    }
+
+    #[test]
+    #[serial]
+    fn test_builtin_tools_acting_on_a_server() {
+        let tool_names = get_tool_names();
+
+        // Tools changing a server also count against the rate limit and wait for approval
+        for name in [
+            &tool_names.configure_server,
+            &tool_names.uninstall_server,
+            &tool_names.set_tool_active,
+            &tool_names.restart_server,
+            &tool_names.enable_server,
+            &tool_names.disable_server,
+        ] {
+            assert!(tool_names.changes_server(name));
+            assert!(!tool_names.reads_server(name));
+        }
+        for name in [
+            &tool_names.server_status,
+            &tool_names.server_logs,
+            &tool_names.list_server_tools,
+        ] {
+            assert!(tool_names.reads_server(name));
+            assert!(!tool_names.changes_server(name));
+        }
+        assert!(!tool_names.changes_server(&tool_names.register_server));
+        assert!(!tool_names.reads_server(&tool_names.list_installed_servers));
+    }
+}
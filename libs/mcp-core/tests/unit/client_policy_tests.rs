@@ -0,0 +1,175 @@
+#[cfg(test)]
+mod tests {
+    use std::{sync::Arc, time::Duration};
+
+    use mcp_core::{
+        core::{
+            mcp_core::MCPCore, mcp_core_profiles_ext::McpCoreProfilesExt,
+            mcp_core_sessions_ext::McpCoreSessionsExt,
+        },
+        mcp_server_implementation::{
+            approvals::ApprovalManager, session_manager::SSESessionManager,
+        },
+        models::types::{ClientPolicy, PendingApproval, Profile, SchemaStrictness},
+    };
+    use serial_test::serial;
+    use tempfile::TempDir;
+
+    use crate::unit::common::setup_core;
+
+    fn pending_approval(id: &str) -> PendingApproval {
+        PendingApproval {
+            id: id.to_string(),
+            session_id: "session".to_string(),
+            client_name: "cursor".to_string(),
+            server_id: "github".to_string(),
+            tool_name: "create_issue".to_string(),
+            arguments: None,
+            requested_at: 0,
+        }
+    }
+
+    #[test]
+    fn test_allows_server() {
+        let mut policy = ClientPolicy {
+            profile: "coding".to_string(),
+            ..Default::default()
+        };
+        assert!(policy.allows_server("github"));
+
+        policy.allowed_servers = Some(vec!["files".to_string()]);
+        assert!(policy.allows_server("files"));
+        assert!(!policy.allows_server("github"));
+    }
+
+    /// Core with the `github` and `files` servers and the `coding` and `research` profiles
+    async fn setup_core_with_profiles() -> (MCPCore, TempDir) {
+        let (mcp_core, temp_dir) = setup_core(&["github", "files"]).await;
+        for name in ["coding", "research"] {
+            let profile = Profile {
+                name: name.to_string(),
+                ..Default::default()
+            };
+            mcp_core.save_profile(profile).await.unwrap();
+        }
+        (mcp_core, temp_dir)
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_save_and_fall_back_to_any_profile_policy() {
+        let (mcp_core, _temp) = setup_core_with_profiles().await;
+        assert!(mcp_core
+            .client_policy(Some("coding"))
+            .await
+            .unwrap()
+            .is_none());
+
+        let any_profile = ClientPolicy {
+            profile: ClientPolicy::ANY_PROFILE.to_string(),
+            max_calls_per_minute: Some(30),
+            ..Default::default()
+        };
+        let coding = ClientPolicy {
+            profile: "coding".to_string(),
+            allowed_servers: Some(vec!["files".to_string()]),
+            max_calls_per_minute: None,
+            require_approval: true,
+            schema_strictness: Some(SchemaStrictness::Strict),
+        };
+        mcp_core
+            .save_client_policy(any_profile.clone())
+            .await
+            .unwrap();
+        mcp_core.save_client_policy(coding.clone()).await.unwrap();
+
+        assert_eq!(
+            mcp_core.client_policy(Some("coding")).await.unwrap(),
+            Some(coding)
+        );
+        assert_eq!(
+            mcp_core.client_policy(Some("research")).await.unwrap(),
+            Some(any_profile.clone())
+        );
+        // Connections without a profile only get the `*` policy
+        assert_eq!(
+            mcp_core.client_policy(None).await.unwrap(),
+            Some(any_profile)
+        );
+        assert_eq!(mcp_core.list_client_policies().await.unwrap().len(), 2);
+
+        mcp_core.delete_client_policy("coding").await.unwrap();
+        assert_eq!(
+            mcp_core
+                .client_policy(Some("coding"))
+                .await
+                .unwrap()
+                .unwrap()
+                .profile,
+            ClientPolicy::ANY_PROFILE
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_save_client_policy_validates_policy() {
+        let (mcp_core, _temp) = setup_core_with_profiles().await;
+
+        let unknown_server = ClientPolicy {
+            profile: "coding".to_string(),
+            allowed_servers: Some(vec!["missing".to_string()]),
+            ..Default::default()
+        };
+        assert!(mcp_core.save_client_policy(unknown_server).await.is_err());
+
+        let unknown_profile = ClientPolicy {
+            profile: "cursor".to_string(),
+            ..Default::default()
+        };
+        assert!(mcp_core.save_client_policy(unknown_profile).await.is_err());
+
+        let no_calls = ClientPolicy {
+            profile: "coding".to_string(),
+            max_calls_per_minute: Some(0),
+            ..Default::default()
+        };
+        assert!(mcp_core.save_client_policy(no_calls).await.is_err());
+        assert!(mcp_core.list_client_policies().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_rate_limit_is_per_profile() {
+        let session_manager = SSESessionManager::new();
+
+        assert!(session_manager.try_acquire_call("coding", 2).await);
+        assert!(session_manager.try_acquire_call("coding", 2).await);
+        assert!(!session_manager.try_acquire_call("coding", 2).await);
+        assert!(session_manager.try_acquire_call("research", 2).await);
+    }
+
+    #[tokio::test]
+    async fn test_approval_is_resolved_or_times_out() {
+        let approvals = Arc::new(ApprovalManager::new());
+
+        let waiting = {
+            let approvals = approvals.clone();
+            tokio::spawn(async move {
+                approvals
+                    .request_approval(pending_approval("approve-me"), Duration::from_secs(10))
+                    .await
+            })
+        };
+        while approvals.pending().await.is_empty() {
+            tokio::task::yield_now().await;
+        }
+        approvals.resolve("approve-me", true).await.unwrap();
+        assert!(waiting.await.unwrap());
+        assert!(approvals.resolve("approve-me", true).await.is_err());
+
+        let approved = approvals
+            .request_approval(pending_approval("ignored"), Duration::from_millis(10))
+            .await;
+        assert!(!approved);
+        assert!(approvals.pending().await.is_empty());
+    }
+}
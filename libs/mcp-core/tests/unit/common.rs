@@ -0,0 +1,150 @@
+//! Fixtures shared by the unit tests
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use mcp_core::{
+    core::{mcp_core::MCPCore, mcp_core_database_ext::McpCoreDatabaseExt},
+    database::db_manager::DBManager,
+    mcp_state::mcp_state::MCPState,
+    models::types::{
+        ServerConfiguration, ServerDefinition, ServerEnvironment, ServerToolInfo, ServerType,
+        ToolTarget,
+    },
+    registry::server_registry::ServerRegistry,
+};
+use serde_json::{json, Value};
+use tempfile::{tempdir, TempDir};
+use tokio::sync::RwLock;
+
+/// Enabled server without configuration
+pub fn server_definition(name: &str) -> ServerDefinition {
+    ServerDefinition {
+        name: name.to_string(),
+        description: format!("{name} server"),
+        enabled: true,
+        tools_type: "node".to_string(),
+        entry_point: None,
+        configuration: None,
+        distribution: None,
+        server_type: ServerType::Package,
+        working_directory: None,
+        executable_path: None,
+    }
+}
+
+/// Environment variable of a server with a default value
+pub fn env_var(value: &str, secret: bool) -> ServerEnvironment {
+    ServerEnvironment {
+        description: String::new(),
+        default: Some(value.to_string()),
+        required: false,
+        secret,
+    }
+}
+
+/// Disabled server run with `npx` and the given environment
+pub fn server_definition_with_env(
+    name: &str,
+    env: HashMap<String, ServerEnvironment>,
+) -> ServerDefinition {
+    ServerDefinition {
+        enabled: false,
+        configuration: Some(ServerConfiguration {
+            command: Some("npx".to_string()),
+            args: None,
+            env: Some(env),
+        }),
+        ..server_definition(name)
+    }
+}
+
+/// Tool of a server taking the arguments of `input_schema`
+pub fn tool_with_schema(name: &str, server_id: &str, input_schema: Value) -> ServerToolInfo {
+    ServerToolInfo::from_value(
+        json!({
+            "name": name,
+            "description": format!("{name} tool"),
+            "inputSchema": input_schema
+        }),
+        server_id.to_string(),
+    )
+    .unwrap()
+}
+
+/// Tool of a server without arguments
+pub fn tool(name: &str, server_id: &str) -> ServerToolInfo {
+    tool_with_schema(name, server_id, json!({"type": "object", "properties": {}}))
+}
+
+/// Core with a migrated database in a temporary directory and the given servers installed
+pub async fn setup_core(server_ids: &[&str]) -> (MCPCore, TempDir) {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let mcp_core = MCPCore::new_with_port(
+        temp_dir.path().join("mcp-dockmaster.db"),
+        temp_dir.path().join("mcp-proxy-server"),
+        0,
+        "mcp-dockmaster-test".to_string(),
+    );
+    mcp_core.apply_database_migrations().await.unwrap();
+    {
+        let registry = mcp_core.tool_registry.read().await;
+        for server_id in server_ids {
+            registry
+                .save_server(server_id, &server_definition(server_id))
+                .unwrap();
+        }
+    }
+    (mcp_core, temp_dir)
+}
+
+/// State with two servers that both expose `search`, as if they had been discovered.
+///
+/// Each server also has a `<server>_only` tool, all tools take the arguments of `input_schema`.
+pub async fn setup_state(input_schema: Value) -> (MCPState, TempDir) {
+    let temp_dir = tempdir().expect("Failed to create temp directory");
+    let db = DBManager::with_path(temp_dir.path().join("mcp-dockmaster.db"))
+        .expect("Failed to create database");
+    db.apply_migrations().expect("Failed to apply migrations");
+
+    let mut server_tools = HashMap::new();
+    for server_id in ["alpha", "beta"] {
+        db.save_server(server_id, &server_definition(server_id))
+            .unwrap();
+        let tools = vec![
+            tool_with_schema("search", server_id, input_schema.clone()),
+            tool_with_schema(
+                &format!("{server_id}_only"),
+                server_id,
+                input_schema.clone(),
+            ),
+        ];
+        for tool in &tools {
+            db.save_server_tool(tool).unwrap();
+        }
+        server_tools.insert(server_id.to_string(), tools);
+    }
+
+    let state = MCPState::new(
+        Arc::new(RwLock::new(ServerRegistry::with_db_manager(db))),
+        Arc::new(RwLock::new(server_tools)),
+        Arc::new(RwLock::new(HashMap::new())),
+    );
+    {
+        let mut tool_index = state.tool_index.write().await;
+        for (name, server_id) in [
+            ("search", "alpha"),
+            ("alpha_only", "alpha"),
+            ("beta_only", "beta"),
+        ] {
+            tool_index.insert(
+                name.to_string(),
+                ToolTarget {
+                    server_id: server_id.to_string(),
+                    tool_name: name.to_string(),
+                },
+            );
+        }
+    }
+    (state, temp_dir)
+}
@@ -0,0 +1,199 @@
+#[cfg(test)]
+mod tests {
+    use mcp_core::{
+        config::ArgumentValidationConfig,
+        core::mcp_core_composite_ext::{tool_result_value, McpCoreCompositeExt},
+        mcp_server_implementation::tools::get_tool_names,
+        models::types::{CompositeStep, CompositeTool},
+        utils::json_template::{render_template, select_items},
+    };
+    use rmcp::model::{CallToolResult, Content};
+    use serde_json::{json, Map};
+    use serial_test::serial;
+
+    use crate::unit::common::{setup_core, tool};
+
+    fn triage_tool() -> CompositeTool {
+        serde_json::from_value(json!({
+            "name": "triage_issues",
+            "description": "Search issues and fetch each of them",
+            "input_schema": {
+                "type": "object",
+                "properties": {"query": {"type": "string"}},
+                "required": ["query"]
+            },
+            "steps": [
+                {
+                    "id": "search",
+                    "server_id": "github",
+                    "tool_id": "search_issues",
+                    "arguments": {"q": "$.input.query"}
+                },
+                {
+                    "id": "fetch",
+                    "server_id": "github",
+                    "tool_id": "get_issue",
+                    "arguments": {"number": "$.item.number"},
+                    "for_each": "$.steps.search.items"
+                }
+            ],
+            "output": {"issues": "$.steps.fetch"}
+        }))
+        .unwrap()
+    }
+
+    #[test]
+    fn test_render_template() {
+        let context = json!({
+            "input": {"query": "is:open"},
+            "steps": {"search": {"items": [{"number": 1}, {"number": 2}]}}
+        });
+        let template = json!({
+            "q": "$.input.query",
+            "numbers": "$.steps.search.items[*].number",
+            "first": "$.steps.search.items[0]",
+            "missing": "$.input.label",
+            "literal": "$$5",
+            "nested": ["$.input.query", 3, "text"]
+        });
+
+        assert_eq!(
+            render_template(&template, &context).unwrap(),
+            json!({
+                "q": "is:open",
+                "numbers": [1, 2],
+                "first": {"number": 1},
+                "missing": null,
+                "literal": "$5",
+                "nested": ["is:open", 3, "text"]
+            })
+        );
+        assert!(render_template(&json!("$.steps[?"), &context).is_err());
+    }
+
+    #[test]
+    fn test_select_items() {
+        let context = json!({"list": [1, 2], "one": "a"});
+
+        assert_eq!(select_items("$.list", &context).unwrap(), vec![json!(1), json!(2)]);
+        assert_eq!(select_items("$.list[*]", &context).unwrap(), vec![json!(1), json!(2)]);
+        assert_eq!(select_items("$.one", &context).unwrap(), vec![json!("a")]);
+        assert!(select_items("$.none", &context).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_tool_result_value() {
+        let result = |content: Vec<Content>| CallToolResult {
+            content,
+            is_error: Some(false),
+        };
+
+        assert_eq!(
+            tool_result_value(&result(vec![Content::text(r#"{"items": []}"#)])),
+            json!({"items": []})
+        );
+        assert_eq!(
+            tool_result_value(&result(vec![Content::text("plain")])),
+            json!("plain")
+        );
+        assert_eq!(
+            tool_result_value(&result(vec![Content::text("1"), Content::text("two")])),
+            json!([1, "two"])
+        );
+    }
+
+    #[test]
+    fn test_composite_arguments_are_checked_against_the_input_schema() {
+        let tool = triage_tool();
+        let config = ArgumentValidationConfig {
+            enabled: true,
+            coerce: true,
+        };
+
+        let arguments = json!({"query": "is:open"}).as_object().cloned();
+        assert_eq!(
+            tool.prepare_arguments(arguments.clone(), &config).unwrap(),
+            arguments
+        );
+        let violations = tool.prepare_arguments(None, &config).unwrap_err();
+        assert_eq!(violations.len(), 1);
+        assert!(violations[0].message.contains("query"));
+        let wrong_type = json!({"query": 42}).as_object().cloned();
+        assert!(tool.prepare_arguments(wrong_type, &config).is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_save_list_and_delete_composite_tools() {
+        let (mcp_core, _temp) = setup_core(&["github"]).await;
+
+        mcp_core.save_composite_tool(triage_tool()).await.unwrap();
+        assert_eq!(
+            mcp_core.list_composite_tools().await.unwrap(),
+            vec![triage_tool()]
+        );
+        assert_eq!(
+            mcp_core.get_composite_tool("triage_issues").await.unwrap(),
+            triage_tool()
+        );
+
+        mcp_core.delete_composite_tool("triage_issues").await.unwrap();
+        assert!(mcp_core.get_composite_tool("triage_issues").await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_save_composite_tool_validates_definition() {
+        let (mcp_core, _temp) = setup_core(&["github"]).await;
+
+        let mut builtin_name = triage_tool();
+        builtin_name.name = get_tool_names().register_server;
+        assert!(mcp_core.save_composite_tool(builtin_name).await.is_err());
+
+        let mut no_steps = triage_tool();
+        no_steps.steps.clear();
+        assert!(mcp_core.save_composite_tool(no_steps).await.is_err());
+
+        let mut duplicate_step = triage_tool();
+        duplicate_step.steps[1].id = "search".to_string();
+        assert!(mcp_core.save_composite_tool(duplicate_step).await.is_err());
+
+        let mut invalid_path = triage_tool();
+        invalid_path.steps[1].for_each = Some("$.steps[".to_string());
+        assert!(mcp_core.save_composite_tool(invalid_path).await.is_err());
+
+        let mut unknown_server = triage_tool();
+        unknown_server.steps.push(CompositeStep {
+            id: "summarize".to_string(),
+            server_id: "missing".to_string(),
+            tool_id: "summarize".to_string(),
+            arguments: Map::new(),
+            for_each: None,
+        });
+        assert!(mcp_core.save_composite_tool(unknown_server).await.is_err());
+
+        assert!(mcp_core.list_composite_tools().await.unwrap().is_empty());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_save_composite_tool_rejects_names_of_stored_tools() {
+        let (mcp_core, _temp) = setup_core(&["github"]).await;
+        // The server is not running, its tool is only stored
+        let mut stopped_tool = tool("triage_issues", "github");
+        stopped_tool.is_active = false;
+        mcp_core
+            .tool_registry
+            .read()
+            .await
+            .save_server_tool(&stopped_tool)
+            .unwrap();
+
+        let error = mcp_core
+            .save_composite_tool(triage_tool())
+            .await
+            .unwrap_err();
+        assert!(error.contains("already used"));
+        assert!(mcp_core.list_composite_tools().await.unwrap().is_empty());
+    }
+}
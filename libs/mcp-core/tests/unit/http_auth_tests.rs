@@ -0,0 +1,228 @@
+#[cfg(test)]
+mod tests {
+    use std::{net::SocketAddr, time::Duration};
+
+    use axum::{middleware, routing::get, Router};
+    use mcp_core::{
+        config::HttpAuthConfig,
+        mcp_installers::{get_generic_config, proxy_server_env},
+        mcp_server_implementation::http_auth::{
+            load_or_create_token, read_discovery, require_token, write_discovery, Discovery,
+            HttpAuth,
+        },
+    };
+    use reqwest::StatusCode;
+    use serde_json::{json, Value};
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    use crate::unit::common::setup_core;
+
+    /// Events of an SSE response, read as they arrive
+    struct EventStream {
+        response: reqwest::Response,
+        buffer: String,
+    }
+
+    impl EventStream {
+        /// Next event with data, as its type and data
+        async fn next_event(&mut self) -> (String, String) {
+            loop {
+                if let Some(end) = self.buffer.find("\n\n") {
+                    let raw: String = self.buffer.drain(..end + 2).collect();
+                    let mut event = "message".to_string();
+                    let mut data = String::new();
+                    for line in raw.lines() {
+                        if let Some(value) = line.strip_prefix("event:") {
+                            event = value.trim().to_string();
+                        } else if let Some(value) = line.strip_prefix("data:") {
+                            data.push_str(value.trim_start());
+                        }
+                    }
+                    if !data.is_empty() {
+                        return (event, data);
+                    }
+                    continue;
+                }
+                let chunk = tokio::time::timeout(Duration::from_secs(10), self.response.chunk())
+                    .await
+                    .expect("no event within 10 seconds")
+                    .unwrap()
+                    .expect("stream closed");
+                self.buffer.push_str(&String::from_utf8_lossy(&chunk));
+            }
+        }
+
+        async fn next_message(&mut self) -> Value {
+            let (event, data) = self.next_event().await;
+            assert_eq!(event, "message");
+            serde_json::from_str(&data).unwrap()
+        }
+    }
+
+    async fn serve_with_token(token: &str) -> SocketAddr {
+        let router = Router::new().route("/sse", get(|| async { "ok" })).layer(
+            middleware::from_fn_with_state(HttpAuth::new(token), require_token),
+        );
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+        addr
+    }
+
+    #[tokio::test]
+    async fn test_requests_require_the_token() {
+        let addr = serve_with_token("install-token").await;
+        let client = reqwest::Client::new();
+        let url = format!("http://{addr}/sse");
+
+        let status = |request: reqwest::RequestBuilder| async move {
+            request.send().await.unwrap().status()
+        };
+        assert_eq!(status(client.get(&url)).await, StatusCode::UNAUTHORIZED);
+        assert_eq!(
+            status(client.get(&url).bearer_auth("other-token")).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(client.get(format!("{url}?token=install-toke"))).await,
+            StatusCode::UNAUTHORIZED
+        );
+        assert_eq!(
+            status(client.get(&url).bearer_auth("install-token")).await,
+            StatusCode::OK
+        );
+        assert_eq!(
+            status(client.get(format!("{url}?session=1&token=install-token"))).await,
+            StatusCode::OK
+        );
+    }
+
+    #[test]
+    fn test_token_is_created_once() {
+        let temp_dir = tempdir().unwrap();
+        let path = temp_dir.path().join("nested").join("discovery.json");
+
+        let token = load_or_create_token(&path).unwrap();
+        assert!(token.len() >= 43);
+        assert_eq!(load_or_create_token(&path).unwrap(), token);
+
+        // Writing the address keeps the token of the install
+        let discovery = Discovery {
+            url: "http://127.0.0.1:11011".to_string(),
+            token: token.clone(),
+        };
+        write_discovery(&path, &discovery).unwrap();
+        assert_eq!(read_discovery(&path).unwrap(), discovery);
+        assert_eq!(load_or_create_token(&path).unwrap(), token);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+
+            // Rewriting a file others could read restricts it again
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            write_discovery(&path, &discovery).unwrap();
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+        }
+
+        let other_dir = tempdir().unwrap();
+        let other = load_or_create_token(&other_dir.path().join("discovery.json")).unwrap();
+        assert_ne!(other, token);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_query_token_round_trip() {
+        let (mut mcp_core, temp_dir) = setup_core(&[]).await;
+        let discovery_file = temp_dir.path().join("discovery.json");
+        mcp_core.http_auth = HttpAuthConfig {
+            enabled: true,
+            discovery_file: Some(discovery_file.clone()),
+        };
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        let router = mcp_core.http_router(addr).unwrap();
+        tokio::spawn(async move { axum::serve(listener, router).await });
+
+        let discovery = read_discovery(&discovery_file).unwrap();
+        assert_eq!(discovery.url, format!("http://{addr}"));
+
+        // The proxy server is installed with where to find the server and its token, never the
+        // token itself
+        let proxy_auth = mcp_core.proxy_auth().unwrap().unwrap();
+        assert_eq!(proxy_auth.discovery_file, discovery_file);
+        let env = proxy_server_env(Some(&proxy_auth)).unwrap();
+        assert!(env.values().all(|value| !value.contains(&discovery.token)));
+        let generic_config = get_generic_config("mcp-proxy-server", Some(&proxy_auth));
+        assert!(generic_config.contains(&discovery_file.display().to_string()));
+        assert!(!generic_config.contains(&discovery.token));
+
+        let client = reqwest::Client::new();
+        let response = client.get(format!("http://{addr}/sse")).send().await;
+        assert_eq!(response.unwrap().status(), StatusCode::UNAUTHORIZED);
+
+        let response = client
+            .get(format!("http://{addr}/sse?token={}", discovery.token))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let mut events = EventStream {
+            response,
+            buffer: String::new(),
+        };
+        let (event, endpoint) = events.next_event().await;
+        assert_eq!(event, "endpoint");
+        assert!(!endpoint.contains("token="));
+
+        // Messages are posted to the endpoint the stream sent, without the token
+        let post = |message: Value| {
+            client
+                .post(format!("http://{addr}{endpoint}"))
+                .json(&message)
+                .send()
+        };
+        let response = post(json!({
+            "jsonrpc": "2.0",
+            "id": 1,
+            "method": "initialize",
+            "params": {
+                "protocolVersion": "2024-11-05",
+                "capabilities": {},
+                "clientInfo": {"name": "query-token-client", "version": "1.0.0"}
+            }
+        }))
+        .await
+        .unwrap();
+        assert!(response.status().is_success());
+        let initialized = events.next_message().await;
+        assert_eq!(initialized["id"], 1);
+        assert!(initialized["result"]["serverInfo"].is_object());
+
+        post(json!({"jsonrpc": "2.0", "method": "notifications/initialized"}))
+            .await
+            .unwrap();
+        post(json!({"jsonrpc": "2.0", "id": 2, "method": "tools/list"}))
+            .await
+            .unwrap();
+        let tools = events.next_message().await;
+        assert_eq!(tools["id"], 2);
+        assert!(!tools["result"]["tools"].as_array().unwrap().is_empty());
+
+        // Sessions of streams opened without the token are not let through
+        let response = client
+            .post(format!("http://{addr}/post?sessionId=unknown"))
+            .json(&json!({"jsonrpc": "2.0", "id": 3, "method": "tools/list"}))
+            .send()
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::UNAUTHORIZED);
+
+        mcp_core.sse_server_cancel_token.cancel();
+    }
+}
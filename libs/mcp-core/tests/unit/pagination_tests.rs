@@ -0,0 +1,53 @@
+#[cfg(test)]
+mod tests {
+    use mcp_core::mcp_server_implementation::pagination::paginate;
+
+    fn names(count: usize) -> Vec<String> {
+        (0..count).map(|i| format!("tool_{i:03}")).collect()
+    }
+
+    #[test]
+    fn test_walks_all_pages_with_cursors() {
+        let mut cursor: Option<String> = None;
+        let mut collected = Vec::new();
+        let mut pages = 0;
+        loop {
+            let page = paginate(names(25), |s| s.as_str(), cursor.as_deref(), 10).unwrap();
+            collected.extend(page.items);
+            pages += 1;
+            match page.next_cursor {
+                Some(next) => cursor = Some(next),
+                None => break,
+            }
+        }
+        assert_eq!(pages, 3);
+        assert_eq!(collected, names(25));
+    }
+
+    #[test]
+    fn test_cursor_is_stable_for_unchanged_list() {
+        let first = paginate(names(5), |s| s.as_str(), None, 2).unwrap();
+        let again = paginate(names(5), |s| s.as_str(), None, 2).unwrap();
+        assert_eq!(first.next_cursor, again.next_cursor);
+    }
+
+    #[test]
+    fn test_cursor_rejected_after_list_changes() {
+        let first = paginate(names(5), |s| s.as_str(), None, 2).unwrap();
+        let cursor = first.next_cursor.unwrap();
+        let result = paginate(names(6), |s| s.as_str(), Some(&cursor), 2);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_invalid_cursor_rejected() {
+        assert!(paginate(names(5), |s| s.as_str(), Some("garbage"), 2).is_err());
+    }
+
+    #[test]
+    fn test_zero_page_size_returns_everything() {
+        let page = paginate(names(250), |s| s.as_str(), None, 0).unwrap();
+        assert_eq!(page.items.len(), 250);
+        assert!(page.next_cursor.is_none());
+    }
+}
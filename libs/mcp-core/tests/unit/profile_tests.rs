@@ -0,0 +1,81 @@
+#[cfg(test)]
+mod tests {
+    use mcp_core::{
+        core::mcp_core_profiles_ext::McpCoreProfilesExt,
+        models::types::{Profile, ProfileTool, SchemaStrictness},
+    };
+    use serial_test::serial;
+
+    use crate::unit::common::{setup_core, tool};
+
+    fn coding_profile() -> Profile {
+        Profile {
+            name: "coding".to_string(),
+            description: "Tools for the editor".to_string(),
+            servers: vec!["github".to_string()],
+            tools: vec![ProfileTool {
+                server_id: "files".to_string(),
+                tool_id: "read_file".to_string(),
+            }],
+            token_budget: None,
+            schema_strictness: None,
+        }
+    }
+
+    #[test]
+    fn test_profile_includes_servers_and_single_tools() {
+        let profile = coding_profile();
+
+        assert!(profile.includes(&tool("create_issue", "github")));
+        assert!(profile.includes(&tool("read_file", "files")));
+        assert!(!profile.includes(&tool("write_file", "files")));
+        assert!(!profile.includes(&tool("read_file", "other")));
+
+        assert!(profile.includes_server("github"));
+        assert!(profile.includes_server("files"));
+        assert!(!profile.includes_server("other"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_save_list_and_delete_profiles() {
+        let (mcp_core, _temp) = setup_core(&["github", "files"]).await;
+
+        mcp_core.save_profile(coding_profile()).await.unwrap();
+        let research = Profile {
+            name: "research".to_string(),
+            servers: vec!["files".to_string()],
+            token_budget: Some(2000),
+            schema_strictness: Some(SchemaStrictness::Standard),
+            ..Default::default()
+        };
+        mcp_core.save_profile(research.clone()).await.unwrap();
+
+        let profiles = mcp_core.list_profiles().await.unwrap();
+        assert_eq!(profiles, vec![coding_profile(), research]);
+
+        // Saving again replaces the profile
+        let mut updated = coding_profile();
+        updated.servers.clear();
+        mcp_core.save_profile(updated.clone()).await.unwrap();
+        assert_eq!(mcp_core.get_profile("coding").await.unwrap(), updated);
+
+        mcp_core.delete_profile("coding").await.unwrap();
+        assert!(mcp_core.get_profile("coding").await.is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_save_profile_validates_name_and_servers() {
+        let (mcp_core, _temp) = setup_core(&["github", "files"]).await;
+
+        let mut profile = coding_profile();
+        profile.name = "coding tools".to_string();
+        assert!(mcp_core.save_profile(profile).await.is_err());
+
+        let mut profile = coding_profile();
+        profile.servers.push("missing".to_string());
+        assert!(mcp_core.save_profile(profile).await.is_err());
+        assert!(mcp_core.list_profiles().await.unwrap().is_empty());
+    }
+}
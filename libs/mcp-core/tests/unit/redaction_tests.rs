@@ -0,0 +1,161 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mcp_core::{
+        core::{mcp_core_proxy_ext::McpCoreProxyExt, mcp_core_secrets_ext::McpCoreSecretsExt},
+        models::types::{ServerConfigUpdateRequest, ServerDefinition},
+        utils::redaction::{
+            is_redacted, redact_env_value, redact_json_values, redact_server, redact_values,
+            REDACTED,
+        },
+    };
+    use serde_json::json;
+    use serial_test::serial;
+
+    use crate::unit::common::{env_var, server_definition_with_env, setup_core};
+
+    fn example_server() -> ServerDefinition {
+        let env = HashMap::from([
+            ("OPENAI_API_KEY".to_string(), env_var("sk-live", false)),
+            ("WEBHOOK".to_string(), env_var("https://hooks/secret", true)),
+            (
+                "GITHUB_TOKEN".to_string(),
+                env_var("${secret:github_pat}", true),
+            ),
+            (
+                "API_URL".to_string(),
+                env_var("https://api.example.com", false),
+            ),
+        ]);
+        server_definition_with_env("example", env)
+    }
+
+    fn env_defaults(definition: &ServerDefinition) -> HashMap<String, String> {
+        definition
+            .configuration
+            .as_ref()
+            .and_then(|configuration| configuration.env.as_ref())
+            .unwrap()
+            .iter()
+            .map(|(key, env)| (key.clone(), env.default.clone().unwrap_or_default()))
+            .collect()
+    }
+
+    #[test]
+    fn test_secret_values_are_redacted() {
+        assert!(is_redacted("OPENAI_API_KEY", false, "sk-live"));
+        assert!(is_redacted("WEBHOOK", true, "https://hooks/secret"));
+        assert!(!is_redacted("API_URL", false, "https://api.example.com"));
+        assert!(!is_redacted("GITHUB_TOKEN", true, ""));
+        assert!(!is_redacted("GITHUB_TOKEN", true, "${secret:github_pat}"));
+        assert_eq!(redact_env_value("SLACK_TOKEN", false, "xoxb"), REDACTED);
+
+        let mut definition = example_server();
+        redact_server(&mut definition);
+        let env = env_defaults(&definition);
+        assert_eq!(env["OPENAI_API_KEY"], REDACTED);
+        assert_eq!(env["WEBHOOK"], REDACTED);
+        assert_eq!(env["GITHUB_TOKEN"], "${secret:github_pat}");
+        assert_eq!(env["API_URL"], "https://api.example.com");
+
+        // Configurations are logged with their debug representation
+        let logged = format!("{:?}", example_server().configuration.unwrap());
+        assert!(!logged.contains("sk-live"));
+        assert!(!logged.contains("hooks/secret"));
+        assert!(logged.contains("https://api.example.com"));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_listed_servers_are_redacted() {
+        let (mcp_core, _temp) = setup_core(&[]).await;
+        mcp_core
+            .tool_registry
+            .read()
+            .await
+            .save_server("example", &example_server())
+            .unwrap();
+
+        let servers = mcp_core.list_servers().await.unwrap();
+        let env = env_defaults(&servers[0].definition);
+        assert_eq!(env["OPENAI_API_KEY"], REDACTED);
+        assert_eq!(env["API_URL"], "https://api.example.com");
+
+        // The UI sends the masked values back with the ones it changed
+        let config = env
+            .into_iter()
+            .map(|(key, value)| match key.as_str() {
+                "API_URL" => (key, "https://api.example.org".to_string()),
+                _ => (key, value),
+            })
+            .collect();
+        let response = mcp_core
+            .update_server_config(ServerConfigUpdateRequest {
+                server_id: "example".to_string(),
+                config,
+            })
+            .await
+            .unwrap();
+        assert!(response.success);
+
+        let registry = mcp_core.tool_registry.read().await;
+        let stored = registry.get_server("example").unwrap();
+        let env = stored.configuration.unwrap().env.unwrap();
+        let api_key = env["OPENAI_API_KEY"].default.clone().unwrap();
+        assert_eq!(registry.decrypt_secret(&api_key).unwrap(), "sk-live");
+        assert_eq!(
+            env["API_URL"].default.as_deref(),
+            Some("https://api.example.org")
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_server_secrets_are_masked_in_free_text() {
+        let (mcp_core, _temp) = setup_core(&[]).await;
+        mcp_core
+            .save_secret("github_pat", "", "ghp-vault")
+            .await
+            .unwrap();
+        mcp_core
+            .tool_registry
+            .read()
+            .await
+            .save_server("example", &example_server())
+            .unwrap();
+
+        let mut secrets = mcp_core.server_secret_values("example").await.unwrap();
+        secrets.sort();
+        assert_eq!(
+            secrets,
+            vec!["ghp-vault", "https://hooks/secret", "sk-live"]
+        );
+
+        let line = "starting with key sk-live, token ghp-vault on https://api.example.com";
+        assert_eq!(
+            redact_values(line, &secrets),
+            format!("starting with key {REDACTED}, token {REDACTED} on https://api.example.com")
+        );
+        // Short values would garble the text
+        assert_eq!(redact_values("a b c", &["b".to_string()]), "a b c");
+    }
+
+    #[test]
+    fn test_secrets_are_masked_in_log_message_data() {
+        let secrets = vec!["sk-live".to_string()];
+        let data = json!({
+            "message": "calling with sk-live",
+            "headers": [{"authorization": "Bearer sk-live"}],
+            "sk-live": 3,
+        });
+        assert_eq!(
+            redact_json_values(data, &secrets),
+            json!({
+                "message": format!("calling with {REDACTED}"),
+                "headers": [{"authorization": format!("Bearer {REDACTED}")}],
+                REDACTED: 3,
+            })
+        );
+    }
+}
@@ -0,0 +1,172 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+    use std::sync::Arc;
+    use std::time::Duration;
+
+    use mcp_core::{
+        config::ResultCacheConfig,
+        database::db_manager::DBManager,
+        mcp_state::{
+            mcp_state::MCPState,
+            result_cache::{canonical_arguments, ResultCache},
+        },
+        models::types::ServerToolInfo,
+        registry::server_registry::ServerRegistry,
+    };
+    use serde_json::{json, Map, Value};
+    use serial_test::serial;
+    use tempfile::tempdir;
+    use tokio::sync::RwLock;
+
+    use crate::unit::common::server_definition;
+
+    const TTL: Duration = Duration::from_secs(60);
+
+    fn arguments(value: Value) -> Option<Map<String, Value>> {
+        value.as_object().cloned()
+    }
+
+    fn cache(max_entries: usize, max_bytes: usize) -> ResultCache {
+        ResultCache::new(ResultCacheConfig {
+            max_entries,
+            max_bytes,
+        })
+    }
+
+    fn tool(annotations: Value) -> ServerToolInfo {
+        ServerToolInfo::from_value(
+            json!({"name": "lookup", "description": "Look up docs", "annotations": annotations}),
+            "docs".to_string(),
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn test_canonical_arguments_ignore_key_order() {
+        let a = arguments(json!({"b": 1, "a": {"y": [1, {"d": 2, "c": 3}], "x": null}}));
+        let b = arguments(json!({"a": {"x": null, "y": [1, {"c": 3, "d": 2}]}, "b": 1}));
+        assert_eq!(canonical_arguments(&a), canonical_arguments(&b));
+        assert_eq!(canonical_arguments(&None), "{}");
+        assert_ne!(
+            canonical_arguments(&arguments(json!({"a": 1}))),
+            canonical_arguments(&arguments(json!({"a": 2})))
+        );
+    }
+
+    #[test]
+    fn test_hits_misses_and_expiry() {
+        let mut cache = cache(10, 1024);
+        let args = arguments(json!({"q": "rust"}));
+
+        assert_eq!(cache.get("docs", "lookup", &args), None);
+        cache.insert("docs", "lookup", &args, json!("result"), TTL);
+        assert_eq!(cache.get("docs", "lookup", &args), Some(json!("result")));
+        assert_eq!(cache.get("docs", "other", &args), None);
+
+        cache.insert("docs", "expired", &args, json!("result"), Duration::ZERO);
+        assert_eq!(cache.get("docs", "expired", &args), None);
+
+        let stats = cache.stats();
+        assert_eq!(stats.hits, 1);
+        assert_eq!(stats.misses, 3);
+        assert_eq!(stats.entries, 1);
+        assert_eq!(stats.bytes, json!("result").to_string().len());
+    }
+
+    #[test]
+    fn test_limits_evict_least_recently_used() {
+        let mut cache = cache(2, 1024);
+        let (a, b, c) = (
+            arguments(json!({"n": 1})),
+            arguments(json!({"n": 2})),
+            arguments(json!({"n": 3})),
+        );
+        cache.insert("docs", "lookup", &a, json!(1), TTL);
+        cache.insert("docs", "lookup", &b, json!(2), TTL);
+        std::thread::sleep(Duration::from_millis(5));
+        cache.get("docs", "lookup", &a);
+        cache.insert("docs", "lookup", &c, json!(3), TTL);
+        assert_eq!(cache.get("docs", "lookup", &b), None);
+        assert_eq!(cache.get("docs", "lookup", &a), Some(json!(1)));
+        assert_eq!(cache.get("docs", "lookup", &c), Some(json!(3)));
+
+        // Results larger than the cache are not stored
+        let mut cache = self::cache(10, 8);
+        cache.insert("docs", "lookup", &a, json!("a long result"), TTL);
+        assert_eq!(cache.stats().entries, 0);
+    }
+
+    #[test]
+    fn test_invalidate() {
+        let mut cache = cache(10, 1024);
+        let args = arguments(json!({}));
+        cache.insert("docs", "lookup", &args, json!(1), TTL);
+        cache.insert("docs", "search", &args, json!(2), TTL);
+        cache.insert("files", "lookup", &args, json!(3), TTL);
+
+        cache.invalidate("docs", Some("lookup"));
+        assert_eq!(cache.stats().entries, 2);
+        cache.invalidate("docs", None);
+        assert_eq!(cache.stats().entries, 1);
+        assert_eq!(cache.get("files", "lookup", &args), Some(json!(3)));
+    }
+
+    #[test]
+    fn test_non_idempotent_tools_are_not_cached() {
+        let mut lookup = tool(json!({"readOnlyHint": true}));
+        assert_eq!(lookup.result_cache_ttl(), None);
+        lookup.cache_ttl = Some(60);
+        assert_eq!(lookup.result_cache_ttl(), Some(TTL));
+
+        let mut write = tool(json!({"idempotentHint": false}));
+        write.cache_ttl = Some(60);
+        assert_eq!(write.result_cache_ttl(), None);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_cache_ttl_is_persisted() {
+        let temp_dir = tempdir().expect("Failed to create temp directory");
+        let db = DBManager::with_path(temp_dir.path().join("mcp-dockmaster.db"))
+            .expect("Failed to create database");
+        db.apply_migrations().expect("Failed to apply migrations");
+        db.save_server("docs", &server_definition("docs")).unwrap();
+        let lookup = tool(json!({}));
+        db.save_server_tool(&lookup).unwrap();
+        let state = MCPState::new(
+            Arc::new(RwLock::new(ServerRegistry::with_db_manager(db))),
+            Arc::new(RwLock::new(HashMap::from([(
+                "docs".to_string(),
+                vec![lookup],
+            )]))),
+            Arc::new(RwLock::new(HashMap::new())),
+        );
+
+        state
+            .set_tool_cache_ttl("docs", "lookup", Some(300))
+            .await
+            .unwrap();
+        let stored = state
+            .tool_registry
+            .read()
+            .await
+            .get_server_tool("lookup", "docs")
+            .unwrap();
+        assert_eq!(stored.cache_ttl, Some(300));
+        assert_eq!(
+            state.server_tools.read().await["docs"][0].cache_ttl,
+            Some(300)
+        );
+
+        assert!(state
+            .set_tool_cache_ttl("docs", "lookup", Some(0))
+            .await
+            .is_err());
+        state
+            .set_tool_cache_ttl("docs", "lookup", None)
+            .await
+            .unwrap();
+        assert_eq!(state.server_tools.read().await["docs"][0].cache_ttl, None);
+    }
+}
@@ -0,0 +1,119 @@
+#[cfg(test)]
+mod tests {
+    use mcp_core::mcp_server_implementation::result_store::{
+        chunks, ResultStore, RESULT_URI_PREFIX,
+    };
+    use rmcp::model::{CallToolResult, Content, ResourceContents};
+
+    fn result(text: &str) -> CallToolResult {
+        CallToolResult {
+            content: vec![Content::text(text)],
+            is_error: Some(false),
+        }
+    }
+
+    fn text(content: &Content) -> &str {
+        &content.as_text().unwrap().text
+    }
+
+    async fn read_chunk(
+        store: &ResultStore,
+        session_id: &str,
+        uri: &str,
+    ) -> Result<String, String> {
+        let result = store.read(session_id, uri, 100).await?;
+        match &result.contents[0] {
+            ResourceContents::TextResourceContents { text, .. } => Ok(text.clone()),
+            _ => Err("not text".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_chunks_split_on_char_boundaries() {
+        assert_eq!(chunks("abcdef", 4), vec!["abcd", "ef"]);
+        assert_eq!(chunks("ééé", 3), vec!["é", "é", "é"]);
+        assert_eq!(chunks("😀a", 2), vec!["😀", "a"]);
+        assert!(chunks("", 4).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_small_results_are_kept() {
+        let store = ResultStore::new(1_000_000);
+        let output = store
+            .limit_output("session", "read_file", result("short"), 1000, 100)
+            .await;
+        assert_eq!(output, result("short"));
+
+        let output = store
+            .limit_output("session", "read_file", result(&"x".repeat(5000)), 0, 100)
+            .await;
+        assert_eq!(output, result(&"x".repeat(5000)));
+        assert!(store.list("session").await.is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_large_results_spill_to_a_resource() {
+        let store = ResultStore::new(1_000_000);
+        let full = (0..1000).map(|i| i.to_string()).collect::<String>();
+        let output = store
+            .limit_output("session", "read_file", result(&full), 1000, 100)
+            .await;
+
+        assert_eq!(output.content.len(), 2);
+        assert!(full.starts_with(text(&output.content[0])));
+        assert!(serde_json::to_string(&output.content).unwrap().len() <= 1000);
+        let marker = text(&output.content[1]);
+        assert!(marker.starts_with("[Output truncated"));
+
+        let resources = store.list("session").await;
+        assert_eq!(resources.len(), 1);
+        let uri = resources[0].uri.clone();
+        assert!(uri.starts_with(RESULT_URI_PREFIX));
+        assert!(marker.contains(&uri));
+
+        let chunk_count = full.len().div_ceil(100);
+        let mut read = read_chunk(&store, "session", &uri).await.unwrap();
+        for chunk in 1..chunk_count {
+            read.push_str(
+                &read_chunk(&store, "session", &format!("{uri}?chunk={chunk}"))
+                    .await
+                    .unwrap(),
+            );
+        }
+        assert_eq!(read, full);
+        assert!(
+            read_chunk(&store, "session", &format!("{uri}?chunk={chunk_count}"))
+                .await
+                .is_err()
+        );
+
+        // Results belong to the session that made the call
+        assert!(read_chunk(&store, "other", &uri).await.is_err());
+        store.remove_session("session").await;
+        assert!(read_chunk(&store, "session", &uri).await.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_oldest_results_are_dropped_over_the_size_limit() {
+        let store = ResultStore::new(250);
+        let first = store.store("session", "read_file", "a".repeat(100)).await;
+        let second = store.store("session", "read_file", "b".repeat(100)).await;
+        let third = store.store("session", "read_file", "c".repeat(100)).await;
+
+        assert!(read_chunk(&store, "session", &first).await.is_err());
+        assert_eq!(
+            read_chunk(&store, "session", &second).await.unwrap(),
+            "b".repeat(100)
+        );
+        assert_eq!(
+            read_chunk(&store, "session", &third).await.unwrap(),
+            "c".repeat(100)
+        );
+
+        // A result larger than the limit replaces every other one but is kept
+        let large = store.store("session", "read_file", "d".repeat(300)).await;
+        let resources = store.list("session").await;
+        assert_eq!(resources.len(), 1);
+        assert_eq!(resources[0].uri, large);
+    }
+}
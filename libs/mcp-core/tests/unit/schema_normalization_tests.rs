@@ -0,0 +1,173 @@
+#[cfg(test)]
+mod tests {
+    use mcp_core::{
+        models::types::{CompositeTool, SchemaStrictness, ServerToolInfo},
+        utils::schema_normalization::{normalize_schema, MAX_SCHEMA_DEPTH},
+    };
+    use serde_json::{json, Map, Value};
+
+    fn object(value: Value) -> Map<String, Value> {
+        value.as_object().unwrap().clone()
+    }
+
+    fn pydantic_schema() -> Map<String, Value> {
+        object(json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "$defs": {
+                "Filter": {
+                    "type": "object",
+                    "properties": {"language": {"type": "string", "format": "iso-639-1"}},
+                    "additionalProperties": false
+                }
+            },
+            "allOf": [
+                {"properties": {"page": {"type": "integer"}}, "required": ["page"]}
+            ],
+            "properties": {
+                "query": {"type": "string", "description": "Search terms"},
+                "filter": {"$ref": "#/$defs/Filter", "description": "Result filter"},
+                "limit": {"anyOf": [{"type": "integer"}, {"type": "null"}]},
+                "sort": {"type": ["string", "null"], "const": "stars"}
+            },
+            "required": ["query"]
+        }))
+    }
+
+    #[test]
+    fn test_off_keeps_schema() {
+        let schema = pydantic_schema();
+        assert_eq!(normalize_schema(&schema, SchemaStrictness::Off), schema);
+    }
+
+    #[test]
+    fn test_standard_inlines_refs_and_flattens_all_of() {
+        let normalized = normalize_schema(&pydantic_schema(), SchemaStrictness::Standard);
+
+        assert_eq!(
+            Value::Object(normalized),
+            json!({
+                "type": "object",
+                "properties": {
+                    "query": {"type": "string", "description": "Search terms"},
+                    "filter": {
+                        "type": "object",
+                        "description": "Result filter",
+                        "properties": {"language": {"type": "string", "format": "iso-639-1"}},
+                        "additionalProperties": false
+                    },
+                    "limit": {"anyOf": [{"type": "integer"}, {"type": "null"}]},
+                    "sort": {"type": ["string", "null"], "const": "stars"},
+                    "page": {"type": "integer"}
+                },
+                "required": ["query", "page"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_strict_keeps_supported_keywords() {
+        let normalized = normalize_schema(&pydantic_schema(), SchemaStrictness::Strict);
+
+        assert_eq!(
+            Value::Object(normalized),
+            json!({
+                "type": "object",
+                "properties": {
+                    "query": {"type": "string", "description": "Search terms"},
+                    "filter": {
+                        "type": "object",
+                        "description": "Result filter",
+                        "properties": {"language": {"type": "string"}}
+                    },
+                    "limit": {"type": "integer"},
+                    "sort": {"type": "string", "enum": ["stars"]},
+                    "page": {"type": "integer"}
+                },
+                "required": ["query", "page"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_recursive_refs_and_depth_are_limited() {
+        let schema = object(json!({
+            "type": "object",
+            "definitions": {
+                "Node": {
+                    "type": "object",
+                    "description": "Tree node",
+                    "properties": {"children": {"type": "array", "items": {"$ref": "#/definitions/Node"}}}
+                }
+            },
+            "properties": {"root": {"$ref": "#/definitions/Node"}}
+        }));
+        let normalized = Value::Object(normalize_schema(&schema, SchemaStrictness::Standard));
+        assert_eq!(
+            normalized["properties"]["root"]["properties"]["children"]["items"],
+            json!({"type": "object", "description": "Tree node"})
+        );
+
+        let mut deep = json!({"type": "string"});
+        for _ in 0..MAX_SCHEMA_DEPTH + 5 {
+            deep = json!({"type": "object", "properties": {"nested": deep}});
+        }
+        let normalized = Value::Object(normalize_schema(
+            deep.as_object().unwrap(),
+            SchemaStrictness::Standard,
+        ));
+        let pointer = "/properties/nested".repeat(MAX_SCHEMA_DEPTH);
+        assert!(normalized
+            .pointer(&pointer)
+            .unwrap()
+            .get("properties")
+            .is_some());
+        let truncated = format!("{pointer}/properties/nested");
+        assert_eq!(
+            normalized.pointer(&truncated),
+            Some(&json!({"type": "object"}))
+        );
+    }
+
+    #[test]
+    fn test_to_tool_normalizes_input_schema() {
+        let tool = ServerToolInfo::from_value(
+            json!({
+                "name": "search",
+                "description": "Search",
+                "inputSchema": Value::Object(pydantic_schema())
+            }),
+            "github".to_string(),
+        )
+        .unwrap();
+
+        let off = tool.clone().to_tool(SchemaStrictness::Off).unwrap();
+        assert!(off.input_schema.contains_key("$schema"));
+
+        let strict = tool.to_tool(SchemaStrictness::Strict).unwrap();
+        assert!(!strict.input_schema.contains_key("$schema"));
+        assert!(!strict.input_schema.contains_key("allOf"));
+        assert_eq!(
+            strict.input_schema["properties"]["limit"],
+            json!({"type": "integer"})
+        );
+    }
+
+    #[test]
+    fn test_composite_to_tool_normalizes_input_schema() {
+        let composite = CompositeTool {
+            name: "triage".to_string(),
+            description: "Triage".to_string(),
+            input_schema: pydantic_schema(),
+            steps: Vec::new(),
+            output: None,
+        };
+
+        let off = composite.to_tool(SchemaStrictness::Off);
+        assert_eq!(*off.input_schema, pydantic_schema());
+
+        let strict = composite.to_tool(SchemaStrictness::Strict);
+        assert!(!strict.input_schema.contains_key("$schema"));
+        assert!(!strict.input_schema.contains_key("$defs"));
+    }
+}
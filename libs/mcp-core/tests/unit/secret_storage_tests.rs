@@ -0,0 +1,179 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use diesel::{sqlite::SqliteConnection, Connection, RunQueryDsl};
+    use mcp_core::{
+        config::SecretKeySource,
+        database::{
+            db_manager::DBManager,
+            migration::{export_servers_to_json, ExportData, SecretExport},
+        },
+        models::types::{ServerDefinition, ServerEnvironment},
+        utils::secrets::{is_encrypted, is_secret_key, SecretCipher, KEY_FILE_NAME},
+    };
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    use crate::unit::common::{env_var, server_definition_with_env};
+
+    fn github_server() -> ServerDefinition {
+        let env = HashMap::from([
+            ("GITHUB_TOKEN".to_string(), env_var("ghp_secret", false)),
+            (
+                "API_URL".to_string(),
+                env_var("https://api.github.com", false),
+            ),
+            ("WEBHOOK".to_string(), env_var("https://hooks/secret", true)),
+        ]);
+        server_definition_with_env("github", env)
+    }
+
+    fn stored_env(db: &DBManager) -> HashMap<String, ServerEnvironment> {
+        db.get_server("github")
+            .unwrap()
+            .configuration
+            .unwrap()
+            .env
+            .unwrap()
+    }
+
+    #[test]
+    fn test_is_secret_key() {
+        assert!(is_secret_key("OPENAI_API_KEY"));
+        assert!(is_secret_key("github_token"));
+        assert!(is_secret_key("CLIENT_SECRET"));
+        assert!(!is_secret_key("API_URL"));
+        assert!(!is_secret_key("KEYBOARD_LAYOUT"));
+    }
+
+    #[test]
+    fn test_key_file_is_created_once() {
+        let temp_dir = tempdir().unwrap();
+        let source = SecretKeySource::File(None);
+
+        let cipher = SecretCipher::load(&source, temp_dir.path()).unwrap();
+        assert!(temp_dir.path().join(KEY_FILE_NAME).exists());
+        let encrypted = cipher.encrypt("ghp_secret").unwrap();
+        assert!(is_encrypted(&encrypted));
+        assert!(!encrypted.contains("ghp_secret"));
+        assert_eq!(cipher.encrypt(&encrypted).unwrap(), encrypted);
+
+        let reloaded = SecretCipher::load(&source, temp_dir.path()).unwrap();
+        assert_eq!(reloaded.decrypt(&encrypted).unwrap(), "ghp_secret");
+        assert_eq!(reloaded.decrypt("plain").unwrap(), "plain");
+
+        let other_path = temp_dir.path().join("other.key");
+        let other = SecretCipher::load(&SecretKeySource::File(Some(other_path)), temp_dir.path());
+        assert!(other.unwrap().decrypt(&encrypted).is_err());
+    }
+
+    #[test]
+    #[serial]
+    fn test_secret_env_values_are_encrypted_at_rest() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("mcp-dockmaster.db");
+        let db = DBManager::with_path(db_path.clone()).unwrap();
+        db.apply_migrations().unwrap();
+        db.save_server("github", &github_server()).unwrap();
+
+        let env = stored_env(&db);
+        let token = &env["GITHUB_TOKEN"];
+        assert!(token.secret);
+        assert!(is_encrypted(token.default.as_deref().unwrap()));
+        assert_eq!(
+            db.decrypt_secret(token.default.as_deref().unwrap())
+                .unwrap(),
+            "ghp_secret"
+        );
+        assert!(is_encrypted(env["WEBHOOK"].default.as_deref().unwrap()));
+        assert!(!env["API_URL"].secret);
+        assert_eq!(
+            env["API_URL"].default.as_deref(),
+            Some("https://api.github.com")
+        );
+
+        // Saving the definition again keeps the values encrypted once
+        let mut definition = db.get_server("github").unwrap();
+        db.save_server("github", &definition).unwrap();
+        assert_eq!(
+            stored_env(&db)["GITHUB_TOKEN"].default,
+            token.default.clone()
+        );
+
+        // Values stored in plaintext before encryption are encrypted in place
+        let mut conn = SqliteConnection::establish(&db_path.to_string_lossy()).unwrap();
+        diesel::sql_query(
+            "UPDATE server_env SET env_value = 'ghp_plain' WHERE env_key = 'GITHUB_TOKEN'",
+        )
+        .execute(&mut conn)
+        .unwrap();
+        assert_eq!(db.encrypt_plaintext_secrets().unwrap(), 1);
+        let token = stored_env(&db)["GITHUB_TOKEN"].default.clone().unwrap();
+        assert_eq!(db.decrypt_secret(&token).unwrap(), "ghp_plain");
+
+        definition.configuration = None;
+        db.save_server("github", &definition).unwrap();
+        assert_eq!(db.encrypt_plaintext_secrets().unwrap(), 0);
+    }
+
+    #[test]
+    #[serial]
+    fn test_secret_flag_of_stored_keys_is_kept() {
+        let temp_dir = tempdir().unwrap();
+        let db = DBManager::with_path(temp_dir.path().join("mcp-dockmaster.db")).unwrap();
+        db.apply_migrations().unwrap();
+        db.save_server("github", &github_server()).unwrap();
+
+        // Unmarking a key whose name looks secret sticks and stores the value in plaintext
+        let mut definition = db.get_server("github").unwrap();
+        let env = definition
+            .configuration
+            .as_mut()
+            .unwrap()
+            .env
+            .as_mut()
+            .unwrap();
+        env.get_mut("GITHUB_TOKEN").unwrap().secret = false;
+        env.insert("NPM_TOKEN".to_string(), env_var("npm_secret", false));
+        db.save_server("github", &definition).unwrap();
+
+        let env = stored_env(&db);
+        assert!(!env["GITHUB_TOKEN"].secret);
+        assert_eq!(env["GITHUB_TOKEN"].default.as_deref(), Some("ghp_secret"));
+        // New keys are still guessed from their name
+        assert!(env["NPM_TOKEN"].secret);
+        assert!(is_encrypted(env["NPM_TOKEN"].default.as_deref().unwrap()));
+
+        db.save_server("github", &db.get_server("github").unwrap())
+            .unwrap();
+        assert!(!stored_env(&db)["GITHUB_TOKEN"].secret);
+    }
+
+    #[test]
+    #[serial]
+    fn test_export_excludes_or_reencrypts_secrets() {
+        let temp_dir = tempdir().unwrap();
+        let db_path = temp_dir.path().join("mcp-dockmaster.db");
+        let db = DBManager::with_path(db_path.clone()).unwrap();
+        db.apply_migrations().unwrap();
+        db.save_server("github", &github_server()).unwrap();
+
+        let exported = |secrets: &SecretExport| -> ExportData {
+            serde_json::from_str(&export_servers_to_json(&db_path, secrets).unwrap()).unwrap()
+        };
+
+        let excluded = exported(&SecretExport::Exclude);
+        let env_vars = &excluded.servers[0].env_vars;
+        assert!(env_vars["GITHUB_TOKEN"].secret);
+        assert_eq!(env_vars["GITHUB_TOKEN"].value, "");
+        assert_eq!(env_vars["API_URL"].value, "https://api.github.com");
+
+        let key_path = temp_dir.path().join("target.key");
+        let reencrypted = exported(&SecretExport::ReEncrypt(key_path.clone()));
+        let token = &reencrypted.servers[0].env_vars["GITHUB_TOKEN"].value;
+        let target = SecretCipher::load(&SecretKeySource::File(Some(key_path)), temp_dir.path());
+        assert_eq!(target.unwrap().decrypt(token).unwrap(), "ghp_secret");
+        assert!(db.decrypt_secret(token).is_err());
+    }
+}
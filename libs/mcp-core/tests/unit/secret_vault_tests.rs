@@ -0,0 +1,144 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mcp_core::{
+        core::mcp_core_secrets_ext::McpCoreSecretsExt,
+        database::db_manager::DBManager,
+        models::types::ServerDefinition,
+        utils::secrets::{is_encrypted, needs_encryption, secret_references},
+        validation::{resolve_template_variables, resolve_template_variables_with_secrets},
+    };
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    use crate::unit::common::{env_var, server_definition_with_env, setup_core};
+
+    fn server_with_token(name: &str, token: &str) -> ServerDefinition {
+        let env = HashMap::from([("GITHUB_TOKEN".to_string(), env_var(token, true))]);
+        server_definition_with_env(name, env)
+    }
+
+    #[test]
+    fn test_secret_references() {
+        assert_eq!(
+            secret_references("${secret:github_pat}"),
+            vec!["github_pat"]
+        );
+        assert_eq!(
+            secret_references("Bearer ${secret:a}:${secret:b} ${HOME}"),
+            vec!["a", "b"]
+        );
+        assert!(secret_references("${secret:unterminated").is_empty());
+        assert!(!needs_encryption("${secret:github_pat}"));
+        assert!(needs_encryption("ghp_plain"));
+    }
+
+    #[test]
+    fn test_secret_resolution() {
+        let lookup = |name: &str| match name {
+            "github_pat" => Ok("ghp_vault".to_string()),
+            // Substituted values are not resolved again
+            "nested" => Ok("${secret:github_pat}".to_string()),
+            _ => Err(format!("Secret {name} not found")),
+        };
+        assert_eq!(
+            resolve_template_variables_with_secrets("token ${secret:github_pat}", lookup).unwrap(),
+            "token ghp_vault"
+        );
+        assert_eq!(
+            resolve_template_variables_with_secrets("${secret:nested}", lookup).unwrap(),
+            "${secret:github_pat}"
+        );
+        assert!(resolve_template_variables_with_secrets("${secret:missing}", lookup).is_err());
+
+        // Without a vault the references are kept for spawn time
+        assert_eq!(
+            resolve_template_variables("${secret:github_pat}").unwrap(),
+            "${secret:github_pat}"
+        );
+    }
+
+    #[test]
+    #[serial]
+    fn test_secrets_are_encrypted_in_the_vault() {
+        let temp_dir = tempdir().unwrap();
+        let db = DBManager::with_path(temp_dir.path().join("mcp-dockmaster.db")).unwrap();
+        db.apply_migrations().unwrap();
+
+        db.save_secret("github_pat", "GitHub PAT", "ghp_vault")
+            .unwrap();
+        assert_eq!(db.get_secret_value("github_pat").unwrap(), "ghp_vault");
+        let secrets = db.get_secrets().unwrap();
+        assert_eq!(secrets.len(), 1);
+        assert_eq!(secrets[0].description, "GitHub PAT");
+
+        db.save_secret("github_pat", "GitHub PAT", "ghp_rotated")
+            .unwrap();
+        assert_eq!(db.get_secret_value("github_pat").unwrap(), "ghp_rotated");
+
+        // References are stored as they are, not encrypted
+        db.save_server(
+            "github",
+            &server_with_token("github", "${secret:github_pat}"),
+        )
+        .unwrap();
+        let token = db
+            .get_server("github")
+            .unwrap()
+            .configuration
+            .unwrap()
+            .env
+            .unwrap()["GITHUB_TOKEN"]
+            .default
+            .clone()
+            .unwrap();
+        assert!(!is_encrypted(&token));
+        assert_eq!(db.encrypt_plaintext_secrets().unwrap(), 0);
+
+        db.delete_secret("github_pat").unwrap();
+        assert!(db.get_secret_value("github_pat").is_err());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_secret_usage_and_deletion() {
+        let (mcp_core, _temp) = setup_core(&[]).await;
+        {
+            let registry = mcp_core.tool_registry.read().await;
+            registry
+                .save_server(
+                    "github",
+                    &server_with_token("github", "${secret:github_pat}"),
+                )
+                .unwrap();
+            registry
+                .save_server(
+                    "issues",
+                    &server_with_token("issues", "${secret:github_pat}"),
+                )
+                .unwrap();
+            registry
+                .save_server("other", &server_with_token("other", "ghp_inline"))
+                .unwrap();
+        }
+
+        assert!(mcp_core.save_secret("bad name", "", "x").await.is_err());
+        let dependents = mcp_core
+            .save_secret("github_pat", "GitHub PAT", "ghp_vault")
+            .await
+            .unwrap();
+        assert_eq!(dependents, vec!["github", "issues"]);
+
+        let secrets = mcp_core.list_secrets().await.unwrap();
+        assert_eq!(secrets[0].name, "github_pat");
+        assert_eq!(secrets[0].servers, vec!["github", "issues"]);
+
+        let error = mcp_core.delete_secret("github_pat").await.unwrap_err();
+        assert!(error.contains("github, issues"));
+
+        mcp_core.save_secret("unused", "", "value").await.unwrap();
+        mcp_core.delete_secret("unused").await.unwrap();
+        assert_eq!(mcp_core.list_secrets().await.unwrap().len(), 1);
+    }
+}
@@ -0,0 +1,109 @@
+#[cfg(test)]
+mod tests {
+    use mcp_core::mcp_server_implementation::session_manager::log_level_severity;
+    use mcp_core::mcp_state::server_logs::{
+        append_server_log, read_server_log, rotated_server_log_path, server_log_path,
+        SERVER_LOGS_DIR_ENV,
+    };
+    use rmcp::model::LoggingLevel;
+    use serial_test::serial;
+    use tempfile::tempdir;
+
+    #[tokio::test]
+    #[serial]
+    async fn test_append_server_log() {
+        let dir = tempdir().unwrap();
+        std::env::set_var(SERVER_LOGS_DIR_ENV, dir.path());
+
+        append_server_log("my-server", "[info] first").await.unwrap();
+        append_server_log("my-server", "[error] second").await.unwrap();
+
+        let content = std::fs::read_to_string(server_log_path("my-server").unwrap()).unwrap();
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("[info] first"));
+        assert!(lines[1].ends_with("[error] second"));
+
+        std::env::remove_var(SERVER_LOGS_DIR_ENV);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_read_server_log_returns_last_lines() {
+        let dir = tempdir().unwrap();
+        std::env::set_var(SERVER_LOGS_DIR_ENV, dir.path());
+
+        assert!(read_server_log("my-server", 10).await.unwrap().is_empty());
+        for line in ["one", "two", "three"] {
+            append_server_log("my-server", line).await.unwrap();
+        }
+        let lines = read_server_log("my-server", 2).await.unwrap();
+        assert_eq!(lines.len(), 2);
+        assert!(lines[0].ends_with("two"));
+        assert!(lines[1].ends_with("three"));
+        assert_eq!(read_server_log("my-server", 10).await.unwrap().len(), 3);
+
+        std::env::remove_var(SERVER_LOGS_DIR_ENV);
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_server_log_is_rotated_and_read_across_files() {
+        let dir = tempdir().unwrap();
+        std::env::set_var(SERVER_LOGS_DIR_ENV, dir.path());
+        std::env::set_var("DOCKMASTER_SERVER_LOG_MAX_BYTES", "100");
+
+        for i in 0..20 {
+            append_server_log("my-server", &format!("line {i}"))
+                .await
+                .unwrap();
+        }
+        let size = std::fs::metadata(server_log_path("my-server").unwrap())
+            .map(|metadata| metadata.len())
+            .unwrap_or_default();
+        assert!(size <= 100);
+        assert!(rotated_server_log_path("my-server").unwrap().exists());
+
+        // The last lines come from the current log and the end of the previous one
+        let lines = read_server_log("my-server", 5).await.unwrap();
+        assert_eq!(lines.len(), 5);
+        for (line, i) in lines.iter().zip(15..20) {
+            assert!(line.ends_with(&format!("line {i}")));
+        }
+
+        std::env::remove_var("DOCKMASTER_SERVER_LOG_MAX_BYTES");
+        std::env::remove_var(SERVER_LOGS_DIR_ENV);
+    }
+
+    #[test]
+    #[serial]
+    fn test_server_log_path_is_sanitized() {
+        let dir = tempdir().unwrap();
+        std::env::set_var(SERVER_LOGS_DIR_ENV, dir.path());
+        let path = server_log_path("../org/server").unwrap();
+        assert_eq!(path, dir.path().join("_2e_2e_2forg_2fserver.log"));
+        assert_eq!(
+            server_log_path("my-server").unwrap(),
+            dir.path().join("my-server.log")
+        );
+        // Ids differing only in the escaped characters get files of their own
+        assert_ne!(
+            server_log_path("a.b").unwrap(),
+            server_log_path("a/b").unwrap()
+        );
+        assert_ne!(
+            server_log_path("a_b").unwrap(),
+            server_log_path("a.b").unwrap()
+        );
+        std::env::remove_var(SERVER_LOGS_DIR_ENV);
+    }
+
+    #[test]
+    fn test_log_level_severity_order() {
+        assert!(log_level_severity(LoggingLevel::Debug) < log_level_severity(LoggingLevel::Info));
+        assert!(log_level_severity(LoggingLevel::Warning) < log_level_severity(LoggingLevel::Error));
+        assert!(
+            log_level_severity(LoggingLevel::Alert) < log_level_severity(LoggingLevel::Emergency)
+        );
+    }
+}
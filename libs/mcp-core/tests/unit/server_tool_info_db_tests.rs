@@ -79,6 +79,12 @@ mod tests {
             server_id: server_id.to_string(),
             proxy_id: Some("proxy1".to_string()),
             is_active: true,
+            title: None,
+            annotations: None,
+            overrides: None,
+            argument_presets: HashMap::new(),
+            cache_ttl: None,
+            size: None,
         };
 
         // Save the tool
@@ -135,6 +141,12 @@ mod tests {
             server_id: server_id.to_string(),
             proxy_id: None,
             is_active: true,
+            title: None,
+            annotations: None,
+            overrides: None,
+            argument_presets: HashMap::new(),
+            cache_ttl: None,
+            size: None,
         };
 
         let tool2 = ServerToolInfo {
@@ -145,6 +157,12 @@ mod tests {
             server_id: server_id.to_string(),
             proxy_id: None,
             is_active: true,
+            title: None,
+            annotations: None,
+            overrides: None,
+            argument_presets: HashMap::new(),
+            cache_ttl: None,
+            size: None,
         };
 
         // Save the tools
@@ -189,6 +207,12 @@ mod tests {
             server_id: server_id.to_string(),
             proxy_id: None,
             is_active: true,
+            title: None,
+            annotations: None,
+            overrides: None,
+            argument_presets: HashMap::new(),
+            cache_ttl: None,
+            size: None,
         };
 
         // Save the tool
@@ -232,6 +256,12 @@ mod tests {
             server_id: server_id.to_string(),
             proxy_id: None,
             is_active: true,
+            title: None,
+            annotations: None,
+            overrides: None,
+            argument_presets: HashMap::new(),
+            cache_ttl: None,
+            size: None,
         };
 
         // Save the tool
@@ -246,6 +276,12 @@ mod tests {
             server_id: server_id.to_string(),
             proxy_id: Some("new_proxy".to_string()),
             is_active: true,
+            title: None,
+            annotations: None,
+            overrides: None,
+            argument_presets: HashMap::new(),
+            cache_ttl: None,
+            size: None,
         };
 
         db.save_server_tool(&updated_tool).unwrap();
@@ -289,6 +325,12 @@ mod tests {
             server_id: server_id.to_string(),
             proxy_id: None,
             is_active: false,
+            title: None,
+            annotations: None,
+            overrides: None,
+            argument_presets: HashMap::new(),
+            cache_ttl: None,
+            size: None,
         };
 
         // Save the tool
@@ -309,6 +351,12 @@ mod tests {
             server_id: server_id.to_string(),
             proxy_id: None,
             is_active: true,
+            title: None,
+            annotations: None,
+            overrides: None,
+            argument_presets: HashMap::new(),
+            cache_ttl: None,
+            size: None,
         };
 
         db.save_server_tool(&updated_tool).unwrap();
@@ -350,6 +398,12 @@ mod tests {
             server_id: server_id.to_string(),
             proxy_id: None,
             is_active: true,
+            title: None,
+            annotations: None,
+            overrides: None,
+            argument_presets: HashMap::new(),
+            cache_ttl: None,
+            size: None,
         };
 
         // Save the tool
@@ -362,4 +416,43 @@ mod tests {
         let result = db.get_server_tool("test_tool", server_id);
         assert!(result.is_err());
     }
+
+    #[test]
+    #[serial]
+    fn test_tool_metadata_is_persisted() {
+        let (db, _temp) = setup_temp_db();
+
+        let server = ServerDefinition {
+            name: "Files".to_string(),
+            description: "A file server".to_string(),
+            enabled: true,
+            tools_type: "node".to_string(),
+            entry_point: None,
+            configuration: None,
+            distribution: None,
+            server_type: ServerType::Package,
+            working_directory: None,
+            executable_path: None,
+        };
+        db.save_server("files", &server).unwrap();
+
+        let tool = ServerToolInfo::from_value(
+            serde_json::json!({
+                "name": "read_file",
+                "description": "Read a file",
+                "title": "Read File",
+                "inputSchema": {"type": "object", "properties": {}},
+                "annotations": {"readOnlyHint": true, "openWorldHint": false}
+            }),
+            "files".to_string(),
+        )
+        .unwrap();
+        db.save_server_tool(&tool).unwrap();
+
+        let retrieved_tool = db.get_server_tool(&tool.id, "files").unwrap();
+        assert_eq!(retrieved_tool.title.as_deref(), Some("Read File"));
+        let annotations = retrieved_tool.annotations.unwrap();
+        assert_eq!(annotations.read_only_hint, Some(true));
+        assert_eq!(annotations.open_world_hint, Some(false));
+    }
 }
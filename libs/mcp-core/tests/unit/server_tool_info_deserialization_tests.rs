@@ -1,6 +1,6 @@
 #[cfg(test)]
 mod tests {
-    use mcp_core::models::types::ServerToolInfo;
+    use mcp_core::models::types::{SchemaStrictness, ServerToolInfo};
     use serde_json::{json, Value};
 
     #[test]
@@ -606,4 +606,38 @@ mod tests {
         assert!(raw_type.iter().any(|v| v.as_str() == Some("boolean")));
         assert!(raw_type.iter().any(|v| v.as_str() == Some("string")));
     }
+
+    #[test]
+    fn test_tool_metadata_round_trip() {
+        let tool_json = json!({
+            "name": "delete_file",
+            "description": "Delete a file",
+            "title": "Delete File",
+            "inputSchema": {
+                "type": "object",
+                "properties": {"path": {"type": "string"}},
+                "required": ["path"]
+            },
+            "annotations": {
+                "readOnlyHint": false,
+                "destructiveHint": true
+            }
+        });
+
+        let tool_info =
+            ServerToolInfo::from_value(tool_json, "files".to_string()).expect("Failed to parse tool");
+        assert_eq!(tool_info.title.as_deref(), Some("Delete File"));
+        let annotations = tool_info.annotations.clone().expect("annotations are kept");
+        assert_eq!(annotations.read_only_hint, Some(false));
+        assert_eq!(annotations.destructive_hint, Some(true));
+
+        // The title is restored in the annotations handed to clients
+        let tool = tool_info.to_tool(SchemaStrictness::Off).expect("Failed to convert to tool");
+        let annotations = tool.annotations.clone().expect("annotations are restored");
+        assert_eq!(annotations.title.as_deref(), Some("Delete File"));
+        assert_eq!(annotations.destructive_hint, Some(true));
+
+        let value: Value = serde_json::to_value(&tool).unwrap();
+        assert_eq!(value["annotations"]["readOnlyHint"], json!(false));
+    }
 }
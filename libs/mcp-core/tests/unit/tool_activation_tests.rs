@@ -0,0 +1,87 @@
+#[cfg(test)]
+mod tests {
+    use serde_json::json;
+    use serial_test::serial;
+
+    use crate::unit::common::{setup_state, tool};
+
+    #[tokio::test]
+    #[serial]
+    async fn test_deactivated_tool_is_persisted_and_not_routed() {
+        let (state, _temp) = setup_state(json!({"type": "object", "properties": {}})).await;
+
+        state.set_tool_active("alpha", "alpha_only", false).await.unwrap();
+
+        let stored = state
+            .tool_registry
+            .read()
+            .await
+            .get_server_tool("alpha_only", "alpha")
+            .unwrap();
+        assert!(!stored.is_active);
+        assert!(state.resolve_tool("alpha_only").await.is_none());
+        let inactive = state.find_inactive_tool("alpha_only").await.unwrap();
+        assert_eq!(inactive.server_id, "alpha");
+
+        state.set_tool_active("alpha", "alpha_only", true).await.unwrap();
+        assert_eq!(
+            state.resolve_tool("alpha_only").await.unwrap().server_id,
+            "alpha"
+        );
+        assert!(state.find_inactive_tool("alpha_only").await.is_none());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_deactivated_tool_hands_name_to_other_server() {
+        let (state, _temp) = setup_state(json!({"type": "object", "properties": {}})).await;
+
+        state.set_tool_active("alpha", "search", false).await.unwrap();
+        assert_eq!(state.resolve_tool("search").await.unwrap().server_id, "beta");
+
+        // Re-enabling gives the name back to the server with the smallest ID
+        state.set_tool_active("alpha", "search", true).await.unwrap();
+        assert_eq!(state.resolve_tool("search").await.unwrap().server_id, "alpha");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_shared_tool_name_has_one_owner_whatever_the_discovery_order() {
+        let (state, _temp) = setup_state(json!({"type": "object", "properties": {}})).await;
+
+        for server_id in ["beta", "alpha", "beta"] {
+            let tools = vec![
+                tool("search", server_id),
+                tool(&format!("{server_id}_only"), server_id),
+            ];
+            state.save_discovered_tools(server_id, tools).await.unwrap();
+            assert_eq!(
+                state.resolve_tool("search").await.unwrap().server_id,
+                "alpha"
+            );
+        }
+
+        // The shadowed tool of beta is not listed
+        let listed: Vec<(String, String)> = state
+            .routed_tools()
+            .await
+            .into_iter()
+            .map(|tool| (tool.server_id, tool.name))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("alpha".to_string(), "search".to_string()),
+                ("alpha".to_string(), "alpha_only".to_string()),
+                ("beta".to_string(), "beta_only".to_string()),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_unknown_tool_is_rejected() {
+        let (state, _temp) = setup_state(json!({"type": "object", "properties": {}})).await;
+        assert!(state.set_tool_active("alpha", "missing", false).await.is_err());
+    }
+}
@@ -0,0 +1,106 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mcp_core::models::types::{ArgumentPreset, SchemaStrictness};
+    use serde_json::{json, Map, Value};
+    use serial_test::serial;
+
+    use crate::unit::common::{setup_state, tool_with_schema};
+
+    /// Input schema of the tools of both servers
+    fn search_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "q": {"type": "string", "description": "query"},
+                "owner": {"type": "string"},
+                "limit": {"type": "integer"}
+            },
+            "required": ["q", "owner"]
+        })
+    }
+
+    fn presets() -> HashMap<String, ArgumentPreset> {
+        HashMap::from([
+            (
+                "owner".to_string(),
+                ArgumentPreset {
+                    value: json!("acme"),
+                    overridable: false,
+                },
+            ),
+            (
+                "limit".to_string(),
+                ArgumentPreset {
+                    value: json!(20),
+                    overridable: true,
+                },
+            ),
+        ])
+    }
+
+    #[test]
+    fn test_to_tool_hides_pinned_arguments() {
+        let mut server_tool = tool_with_schema("search", "alpha", search_schema());
+        server_tool.argument_presets = presets();
+
+        let tool = server_tool.to_tool(SchemaStrictness::Off).unwrap();
+        let properties = tool.input_schema["properties"].as_object().unwrap();
+        assert!(!properties.contains_key("owner"));
+        assert_eq!(properties["limit"]["default"], json!(20));
+        assert_eq!(tool.input_schema["required"], json!(["q"]));
+    }
+
+    #[test]
+    fn test_apply_argument_presets() {
+        let mut server_tool = tool_with_schema("search", "alpha", search_schema());
+        server_tool.argument_presets = presets();
+
+        let arguments: Map<String, Value> = serde_json::from_value(json!({
+            "q": "bug",
+            "owner": "someone-else"
+        }))
+        .unwrap();
+        let arguments = server_tool.apply_argument_presets(Some(arguments)).unwrap();
+        assert_eq!(
+            Value::Object(arguments),
+            json!({"q": "bug", "owner": "acme", "limit": 20})
+        );
+
+        let arguments: Map<String, Value> =
+            serde_json::from_value(json!({"q": "bug", "limit": 5})).unwrap();
+        let arguments = server_tool.apply_argument_presets(Some(arguments)).unwrap();
+        assert_eq!(arguments["limit"], json!(5));
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_argument_presets_are_persisted() {
+        let (state, _temp) = setup_state(search_schema()).await;
+
+        state
+            .set_tool_argument_presets("alpha", "search", presets())
+            .await
+            .unwrap();
+        let stored = state
+            .tool_registry
+            .read()
+            .await
+            .get_server_tool("search", "alpha")
+            .unwrap();
+        assert_eq!(stored.argument_presets, presets());
+
+        let unknown = HashMap::from([(
+            "missing".to_string(),
+            ArgumentPreset {
+                value: json!(true),
+                overridable: false,
+            },
+        )]);
+        assert!(state
+            .set_tool_argument_presets("alpha", "search", unknown)
+            .await
+            .is_err());
+    }
+}
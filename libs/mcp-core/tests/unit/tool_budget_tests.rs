@@ -0,0 +1,123 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mcp_core::{
+        mcp_server_implementation::tool_budget::{fit_token_budget, minify_tool},
+        models::types::{Profile, ProfileTool, ServerToolInfo, ToolSize},
+    };
+    use rmcp::model::Tool;
+    use serde_json::json;
+
+    fn tool(name: &str) -> Tool {
+        let input_schema = json!({
+            "type": "object",
+            "description": "Arguments of the search",
+            "properties": {
+                "query": {"type": "string", "description": "Words to look for in the issues"},
+                "description": {"type": "string", "title": "Description", "default": {"title": "x"}}
+            },
+            "required": ["query"]
+        });
+        Tool {
+            name: name.to_string().into(),
+            description: Some(
+                format!("Search the {name} issues. Results are sorted by relevance.").into(),
+            ),
+            input_schema: Arc::new(input_schema.as_object().unwrap().clone()),
+            annotations: None,
+        }
+    }
+
+    fn total_tokens(tools: &[Tool]) -> usize {
+        tools.iter().map(|tool| ToolSize::of(tool).tokens).sum()
+    }
+
+    #[test]
+    fn test_tool_size() {
+        let tool = tool("github");
+        let size = ToolSize::of(&tool);
+        assert_eq!(size.bytes, serde_json::to_vec(&tool).unwrap().len());
+        assert_eq!(size.tokens, size.bytes.div_ceil(4));
+
+        let sizes = [size, size];
+        let total: ToolSize = sizes.into_iter().sum();
+        assert_eq!(total.bytes, size.bytes * 2);
+        assert_eq!(total.tokens, size.tokens * 2);
+    }
+
+    #[test]
+    fn test_minify_tool() {
+        let minified = minify_tool(&tool("github"));
+
+        assert_eq!(minified.name, "github");
+        assert_eq!(
+            minified.description.as_deref(),
+            Some("Search the github issues.")
+        );
+        assert_eq!(
+            serde_json::Value::Object(minified.input_schema.as_ref().clone()),
+            json!({
+                "type": "object",
+                "properties": {
+                    "query": {"type": "string"},
+                    "description": {"type": "string", "default": {"title": "x"}}
+                },
+                "required": ["query"]
+            })
+        );
+    }
+
+    #[test]
+    fn test_fit_token_budget() {
+        let tools = vec![tool("a"), tool("b"), tool("c")];
+        let full = ToolSize::of(&tool("a")).tokens;
+        let minified = ToolSize::of(&minify_tool(&tool("a"))).tokens;
+        let priority = |tool: &Tool| match tool.name.as_ref() {
+            "a" => None,
+            "b" => Some(0),
+            _ => Some(1),
+        };
+
+        // Within budget nothing changes
+        let fitted = fit_token_budget(tools.clone(), 3 * full, priority);
+        assert_eq!(fitted, tools);
+
+        // The lowest priority tool is minified first
+        let fitted = fit_token_budget(tools.clone(), 2 * full + minified, priority);
+        assert_eq!(fitted[0], tools[0]);
+        assert_eq!(fitted[1], tools[1]);
+        assert_eq!(fitted[2], minify_tool(&tools[2]));
+
+        // Then tools are dropped, the ones without priority are kept
+        let fitted = fit_token_budget(tools.clone(), full + minified, priority);
+        assert_eq!(fitted, vec![tools[0].clone(), minify_tool(&tools[1])]);
+        let fitted = fit_token_budget(tools.clone(), 0, priority);
+        assert_eq!(fitted, vec![tools[0].clone()]);
+        assert!(total_tokens(&fitted) > 0);
+    }
+
+    #[test]
+    fn test_profile_priority() {
+        let tool = |name: &str, server_id: &str| {
+            ServerToolInfo::from_value(
+                json!({"name": name, "description": ""}),
+                server_id.to_string(),
+            )
+            .unwrap()
+        };
+        let profile = Profile {
+            name: "coding".to_string(),
+            servers: vec!["github".to_string(), "slack".to_string()],
+            tools: vec![ProfileTool {
+                server_id: "slack".to_string(),
+                tool_id: "send_message".to_string(),
+            }],
+            ..Default::default()
+        };
+
+        assert_eq!(profile.priority(&tool("send_message", "slack")), 0);
+        assert_eq!(profile.priority(&tool("create_issue", "github")), 1);
+        assert_eq!(profile.priority(&tool("list_channels", "slack")), 2);
+    }
+}
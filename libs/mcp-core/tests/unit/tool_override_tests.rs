@@ -0,0 +1,127 @@
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use mcp_core::models::types::{SchemaStrictness, ToolOverrides};
+    use serde_json::{json, Value};
+    use serial_test::serial;
+
+    use crate::unit::common::{setup_state, tool_with_schema};
+
+    /// Input schema of the tools of both servers
+    fn search_schema() -> Value {
+        json!({
+            "type": "object",
+            "properties": {"q": {"type": "string", "description": "query"}}
+        })
+    }
+
+    fn overrides(name: Option<&str>, description: Option<&str>) -> ToolOverrides {
+        ToolOverrides {
+            name: name.map(str::to_string),
+            description: description.map(str::to_string),
+            argument_descriptions: HashMap::from([(
+                "q".to_string(),
+                "Full text search query".to_string(),
+            )]),
+        }
+    }
+
+    #[test]
+    fn test_to_tool_applies_overrides() {
+        let mut server_tool = tool_with_schema("search", "alpha", search_schema());
+        server_tool.overrides = Some(overrides(Some("search_issues"), Some("Search issues")));
+
+        let tool = server_tool.to_tool(SchemaStrictness::Off).unwrap();
+        assert_eq!(tool.name, "search_issues");
+        assert_eq!(tool.description.as_deref(), Some("Search issues"));
+        assert_eq!(
+            tool.input_schema["properties"]["q"]["description"],
+            "Full text search query"
+        );
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_renamed_tool_routes_to_upstream_name() {
+        let (state, _temp) = setup_state(search_schema()).await;
+
+        state
+            .set_tool_overrides("beta", "search", Some(overrides(Some("beta_search"), None)))
+            .await
+            .unwrap();
+
+        let target = state.resolve_tool("beta_search").await.unwrap();
+        assert_eq!(target.server_id, "beta");
+        assert_eq!(target.tool_name, "search");
+        assert_eq!(state.resolve_tool("search").await.unwrap().server_id, "alpha");
+
+        let stored = state
+            .tool_registry
+            .read()
+            .await
+            .get_server_tool("search", "beta")
+            .unwrap();
+        assert_eq!(stored.exposed_name(), "beta_search");
+
+        // Resetting restores the upstream name, which alpha still owns
+        state.set_tool_overrides("beta", "beta_search", None).await.unwrap();
+        assert!(state.resolve_tool("beta_search").await.is_none());
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_rename_rejects_taken_and_invalid_names() {
+        let (state, _temp) = setup_state(search_schema()).await;
+
+        let taken = state
+            .set_tool_overrides("beta", "beta_only", Some(overrides(Some("alpha_only"), None)))
+            .await;
+        assert!(taken.is_err());
+
+        let invalid = state
+            .set_tool_overrides("beta", "beta_only", Some(overrides(Some("not valid"), None)))
+            .await;
+        assert!(invalid.is_err());
+        assert_eq!(state.resolve_tool("beta_only").await.unwrap().tool_name, "beta_only");
+    }
+
+    #[tokio::test]
+    #[serial]
+    async fn test_overrides_survive_a_tool_missing_from_one_listing() {
+        let (state, _temp) = setup_state(search_schema()).await;
+        state
+            .set_tool_overrides(
+                "alpha",
+                "alpha_only",
+                Some(overrides(Some("alpha_renamed"), None)),
+            )
+            .await
+            .unwrap();
+
+        // The server leaves the tool out once, it is no longer routed but keeps its row
+        let search = tool_with_schema("search", "alpha", search_schema());
+        state
+            .save_discovered_tools("alpha", vec![search.clone()])
+            .await
+            .unwrap();
+        assert!(state.resolve_tool("alpha_renamed").await.is_none());
+        let stored = state
+            .tool_registry
+            .read()
+            .await
+            .get_server_tool("alpha_only", "alpha")
+            .unwrap();
+        assert_eq!(stored.exposed_name(), "alpha_renamed");
+
+        // Once it is listed again, it comes back under the name the user chose
+        let alpha_only = tool_with_schema("alpha_only", "alpha", search_schema());
+        state
+            .save_discovered_tools("alpha", vec![search, alpha_only])
+            .await
+            .unwrap();
+        let target = state.resolve_tool("alpha_renamed").await.unwrap();
+        assert_eq!(target.server_id, "alpha");
+        assert_eq!(target.tool_name, "alpha_only");
+    }
+}
@@ -0,0 +1,79 @@
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use mcp_core::mcp_server_implementation::{
+        tool_search::rank_tools,
+        tools::{get_builtin_tools, get_tool_names, parse_call_tool_request},
+    };
+    use rmcp::model::Tool;
+    use serde_json::{json, Map};
+    use serial_test::serial;
+
+    fn tool(name: &str, description: &str) -> Tool {
+        Tool {
+            name: name.to_string().into(),
+            description: Some(description.to_string().into()),
+            input_schema: Arc::new(Map::new()),
+            annotations: None,
+        }
+    }
+
+    fn tools() -> Vec<Tool> {
+        vec![
+            tool("read_file", "Read the content of a file"),
+            tool("create_issue", "Open a new GitHub issue"),
+            tool("list_issues", "List the issues of a repository"),
+            tool("send_message", "Post a message to a Slack channel"),
+        ]
+    }
+
+    #[test]
+    fn test_rank_tools_matches_names_and_descriptions() {
+        let results = rank_tools(tools(), "issue", 10);
+        let names: Vec<&str> = results.iter().map(|(tool, _)| tool.name.as_ref()).collect();
+        assert_eq!(names.len(), 2);
+        assert!(names.contains(&"create_issue"));
+        assert!(names.contains(&"list_issues"));
+
+        let results = rank_tools(tools(), "slack", 10);
+        assert_eq!(results.len(), 1);
+        assert_eq!(results[0].0.name, "send_message");
+
+        assert!(rank_tools(tools(), "calendar", 10).is_empty());
+    }
+
+    #[test]
+    fn test_rank_tools_respects_limit() {
+        let results = rank_tools(tools(), "file issue message", 2);
+        assert_eq!(results.len(), 2);
+        assert!(results[0].1 >= results[1].1);
+    }
+
+    #[test]
+    #[serial]
+    fn test_discovery_tools_are_offered_in_discovery_mode() {
+        let tool_names = get_tool_names();
+        assert!(!get_builtin_tools()
+            .iter()
+            .any(|tool| tool.name == tool_names.find_tools));
+
+        std::env::set_var("DOCKMASTER_DISCOVERY_MODE", "enabled");
+        let tools = get_builtin_tools();
+        std::env::remove_var("DOCKMASTER_DISCOVERY_MODE");
+        assert!(tools.iter().any(|tool| tool.name == tool_names.find_tools));
+        assert!(tools.iter().any(|tool| tool.name == tool_names.call_tool));
+    }
+
+    #[test]
+    #[serial]
+    fn test_parse_call_tool_request() {
+        let arguments = json!({"name": "read_file", "arguments": {"path": "README.md"}});
+        let request = parse_call_tool_request(arguments.as_object().unwrap().clone()).unwrap();
+        assert_eq!(request.name, "read_file");
+        assert_eq!(request.arguments.unwrap()["path"], "README.md");
+
+        let arguments = json!({"name": get_tool_names().call_tool});
+        assert!(parse_call_tool_request(arguments.as_object().unwrap().clone()).is_err());
+    }
+}